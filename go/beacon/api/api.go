@@ -4,6 +4,7 @@ package api
 import (
 	"context"
 
+	"github.com/oasisprotocol/oasis-core/go/common/crypto/hash"
 	"github.com/oasisprotocol/oasis-core/go/common/errors"
 	"github.com/oasisprotocol/oasis-core/go/common/pubsub"
 )
@@ -180,3 +181,13 @@ type BeaconEvent struct {
 func (ev *BeaconEvent) EventKind() string {
 	return "beacon"
 }
+
+// Event is a beacon event.
+type Event struct {
+	Height int64     `json:"height,omitempty"`
+	TxHash hash.Hash `json:"tx_hash,omitempty"`
+
+	Epoch  *EpochEvent  `json:"epoch,omitempty"`
+	Beacon *BeaconEvent `json:"beacon,omitempty"`
+	VRF    *VRFEvent    `json:"vrf,omitempty"`
+}
@@ -1,8 +1,11 @@
 package results
 
 import (
+	beacon "github.com/oasisprotocol/oasis-core/go/beacon/api"
 	"github.com/oasisprotocol/oasis-core/go/common/errors"
 	governance "github.com/oasisprotocol/oasis-core/go/governance/api"
+	"github.com/oasisprotocol/oasis-core/go/keymanager/churp"
+	"github.com/oasisprotocol/oasis-core/go/keymanager/secrets"
 	registry "github.com/oasisprotocol/oasis-core/go/registry/api"
 	roothash "github.com/oasisprotocol/oasis-core/go/roothash/api"
 	staking "github.com/oasisprotocol/oasis-core/go/staking/api"
@@ -11,10 +14,13 @@ import (
 // Event is a consensus service event that may be emitted during processing of
 // a transaction.
 type Event struct {
-	Staking    *staking.Event    `json:"staking,omitempty"`
-	Registry   *registry.Event   `json:"registry,omitempty"`
-	RootHash   *roothash.Event   `json:"roothash,omitempty"`
-	Governance *governance.Event `json:"governance,omitempty"`
+	Staking         *staking.Event    `json:"staking,omitempty"`
+	Registry        *registry.Event   `json:"registry,omitempty"`
+	RootHash        *roothash.Event   `json:"roothash,omitempty"`
+	Governance      *governance.Event `json:"governance,omitempty"`
+	Beacon          *beacon.Event     `json:"beacon,omitempty"`
+	KeyManager      *secrets.Event    `json:"keymanager,omitempty"`
+	KeyManagerChurp *churp.Event      `json:"churp,omitempty"`
 }
 
 // Error is a transaction execution error.
@@ -229,6 +229,53 @@ type Backend interface {
 	Pruner() StatePruner
 }
 
+// EventDecoder decodes service events from the CometBFT events emitted at the given height by the
+// given transaction. The transaction is nil for events emitted at the beginning or end of a block.
+type EventDecoder[E any] func(tx cmttypes.Tx, height int64, tmEvents []types.Event) ([]E, error)
+
+// GetBlockEvents returns the events emitted at the given height in the order in which they were
+// emitted: at the beginning of the block, by transactions and at the end of the block.
+func GetBlockEvents[E any](ctx context.Context, backend Backend, height int64, decode EventDecoder[E]) ([]E, error) {
+	// Get block results at given height.
+	results, err := backend.GetBlockResults(ctx, height)
+	if err != nil {
+		return nil, fmt.Errorf("failed to get cometbft block results: %w", err)
+	}
+
+	// Get transactions at given height.
+	txns, err := backend.GetTransactions(ctx, height)
+	if err != nil {
+		return nil, fmt.Errorf("failed to get cometbft transactions: %w", err)
+	}
+	if len(txns) != len(results.TxsResults) {
+		return nil, fmt.Errorf("inconsistent number of transactions and results")
+	}
+
+	// Decode events from block results (at the beginning of the block).
+	events, err := decode(nil, results.Height, results.BeginBlockEvents)
+	if err != nil {
+		return nil, err
+	}
+
+	// Decode events from transaction results.
+	for txIdx, txResult := range results.TxsResults {
+		evs, txErr := decode(txns[txIdx], results.Height, txResult.Events)
+		if txErr != nil {
+			return nil, txErr
+		}
+		events = append(events, evs...)
+	}
+
+	// Decode events from block results (at the end of the block).
+	evs, err := decode(nil, results.Height, results.EndBlockEvents)
+	if err != nil {
+		return nil, err
+	}
+	events = append(events, evs...)
+
+	return events, nil
+}
+
 // StatePruneHandler is a handler that is called when versions are pruned
 // from history.
 type StatePruneHandler interface {
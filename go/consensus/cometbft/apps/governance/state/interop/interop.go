@@ -0,0 +1,147 @@
+package interop
+
+import (
+	"context"
+	"fmt"
+
+	"github.com/oasisprotocol/oasis-core/go/common/cbor"
+	"github.com/oasisprotocol/oasis-core/go/common/crypto/signature"
+	"github.com/oasisprotocol/oasis-core/go/common/quantity"
+	"github.com/oasisprotocol/oasis-core/go/common/version"
+	"github.com/oasisprotocol/oasis-core/go/consensus/api/transaction"
+	governanceState "github.com/oasisprotocol/oasis-core/go/consensus/cometbft/apps/governance/state"
+	governance "github.com/oasisprotocol/oasis-core/go/governance/api"
+	staking "github.com/oasisprotocol/oasis-core/go/staking/api"
+	"github.com/oasisprotocol/oasis-core/go/storage/mkvs"
+	upgrade "github.com/oasisprotocol/oasis-core/go/upgrade/api"
+)
+
+var addresses []staking.Address
+
+// InitializeTestGovernanceState must be kept in sync with tests in runtimes/consensus/state/governance.rs.
+func InitializeTestGovernanceState(ctx context.Context, mkvs mkvs.Tree) error {
+	state := governanceState.NewMutableState(mkvs)
+
+	// Populate proposals.
+	passedUpgrade := upgrade.Descriptor{
+		Versioned: cbor.NewVersioned(1),
+		Handler:   "test-handler",
+		Target: version.ProtocolVersions{
+			ConsensusProtocol:        version.Version{Major: 7, Minor: 1},
+			RuntimeHostProtocol:      version.Version{Major: 5},
+			RuntimeCommitteeProtocol: version.Version{Major: 4, Patch: 2},
+		},
+		Epoch: 42,
+	}
+	passed := &governance.Proposal{
+		ID:        1,
+		Submitter: addresses[0],
+		State:     governance.StatePassed,
+		Deposit:   *quantity.NewFromUint64(100),
+		Content: governance.ProposalContent{
+			Upgrade: &governance.UpgradeProposal{Descriptor: passedUpgrade},
+		},
+		CreatedAt: 10,
+		ClosesAt:  20,
+		Results: map[governance.Vote]quantity.Quantity{
+			governance.VoteYes: *quantity.NewFromUint64(300),
+			governance.VoteNo:  *quantity.NewFromUint64(100),
+		},
+		InvalidVotes: 1,
+	}
+	if err := state.SetProposal(ctx, passed); err != nil {
+		return fmt.Errorf("setting proposal: %w", err)
+	}
+	if err := state.SetPendingUpgrade(ctx, passed.ID, &passedUpgrade); err != nil {
+		return fmt.Errorf("setting pending upgrade: %w", err)
+	}
+
+	for _, proposal := range []*governance.Proposal{
+		{
+			ID:        2,
+			Submitter: addresses[1],
+			State:     governance.StateActive,
+			Deposit:   *quantity.NewFromUint64(200),
+			Content: governance.ProposalContent{
+				CancelUpgrade: &governance.CancelUpgradeProposal{ProposalID: 1},
+			},
+			CreatedAt: 25,
+			ClosesAt:  35,
+		},
+		{
+			ID:        3,
+			Submitter: addresses[0],
+			State:     governance.StateActive,
+			Deposit:   *quantity.NewFromUint64(150),
+			Content: governance.ProposalContent{
+				Upgrade: &governance.UpgradeProposal{
+					Descriptor: upgrade.Descriptor{
+						Versioned: cbor.NewVersioned(1),
+						Handler:   "test-handler-2",
+						Target: version.ProtocolVersions{
+							ConsensusProtocol:        version.Version{Major: 8},
+							RuntimeHostProtocol:      version.Version{Major: 5, Minor: 1},
+							RuntimeCommitteeProtocol: version.Version{Major: 4, Minor: 1},
+						},
+						Epoch: 50,
+					},
+				},
+			},
+			CreatedAt: 26,
+			ClosesAt:  30,
+		},
+	} {
+		if err := state.SetActiveProposal(ctx, proposal); err != nil {
+			return fmt.Errorf("setting active proposal: %w", err)
+		}
+	}
+	if err := state.SetNextProposalIdentifier(ctx, 4); err != nil {
+		return fmt.Errorf("setting next proposal identifier: %w", err)
+	}
+
+	// Populate votes.
+	for _, v := range []struct {
+		proposalID uint64
+		voter      staking.Address
+		vote       governance.Vote
+	}{
+		{2, addresses[1], governance.VoteYes},
+		{2, addresses[2], governance.VoteNo},
+		{3, addresses[0], governance.VoteAbstain},
+	} {
+		if err := state.SetVote(ctx, v.proposalID, v.voter, v.vote); err != nil {
+			return fmt.Errorf("setting vote: %w", err)
+		}
+	}
+
+	// Populate consensus parameters.
+	if err := state.SetConsensusParameters(ctx, &governance.ConsensusParameters{
+		GasCosts: transaction.Costs{
+			governance.GasOpSubmitProposal: 1000,
+			governance.GasOpCastVote:       500,
+		},
+		MinProposalDeposit:             *quantity.NewFromUint64(100),
+		VotingPeriod:                   10,
+		StakeThreshold:                 68,
+		UpgradeMinEpochDiff:            40,
+		UpgradeCancelMinEpochDiff:      20,
+		EnableChangeParametersProposal: true,
+		AllowVoteWithoutEntity:         true,
+		AllowProposalMetadata:          true,
+	}); err != nil {
+		return fmt.Errorf("setting consensus parameters: %w", err)
+	}
+
+	return nil
+}
+
+func init() {
+	pk := signature.NewPublicKey("7e57baaad01fffffffffffffffffffffffffffffffffffffffffffffffffffff")
+	pk2 := signature.NewPublicKey("7e57baaad02fffffffffffffffffffffffffffffffffffffffffffffffffffff")
+	pk3 := signature.NewPublicKey("7e57baaad03fffffffffffffffffffffffffffffffffffffffffffffffffffff")
+	addresses = append(addresses,
+		staking.NewAddress(pk),
+		staking.NewAddress(pk2),
+		staking.NewAddress(pk3),
+	)
+}
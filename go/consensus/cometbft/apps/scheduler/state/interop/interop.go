@@ -0,0 +1,76 @@
+package interop
+
+import (
+	"context"
+	"fmt"
+
+	"github.com/oasisprotocol/oasis-core/go/common"
+	"github.com/oasisprotocol/oasis-core/go/common/crypto/signature"
+	schedulerState "github.com/oasisprotocol/oasis-core/go/consensus/cometbft/apps/scheduler/state"
+	scheduler "github.com/oasisprotocol/oasis-core/go/scheduler/api"
+	"github.com/oasisprotocol/oasis-core/go/storage/mkvs"
+)
+
+var (
+	node1   = signature.NewPublicKey("7e57baaad01fffffffffffffffffffffffffffffffffffffffffffffffffffff")
+	node2   = signature.NewPublicKey("7e57baaad02fffffffffffffffffffffffffffffffffffffffffffffffffffff")
+	node3   = signature.NewPublicKey("7e57baaad03fffffffffffffffffffffffffffffffffffffffffffffffffffff")
+	entity1 = signature.NewPublicKey("7e57baaade1fffffffffffffffffffffffffffffffffffffffffffffffffffff")
+	entity2 = signature.NewPublicKey("7e57baaade2fffffffffffffffffffffffffffffffffffffffffffffffffffff")
+)
+
+// InitializeTestSchedulerState must be kept in sync with tests in runtimes/consensus/state/scheduler.rs.
+func InitializeTestSchedulerState(ctx context.Context, mkvs mkvs.Tree) error {
+	state := schedulerState.NewMutableState(mkvs)
+
+	var runtimeID common.Namespace
+	if err := runtimeID.UnmarshalHex("8000000000000000000000000000000000000000000000000000000000000010"); err != nil {
+		return err
+	}
+	var runtimeID2 common.Namespace
+	if err := runtimeID2.UnmarshalHex("8000000000000000000000000000000000000000000000000000000000000011"); err != nil {
+		return err
+	}
+
+	// Populate committees.
+	for _, committee := range []*scheduler.Committee{
+		{
+			Kind: scheduler.KindComputeExecutor,
+			Members: []*scheduler.CommitteeNode{
+				{Role: scheduler.RoleWorker, PublicKey: node1},
+				{Role: scheduler.RoleWorker, PublicKey: node2},
+				{Role: scheduler.RoleBackupWorker, PublicKey: node3},
+			},
+			RuntimeID: runtimeID,
+			ValidFor:  42,
+		},
+		{
+			Kind: scheduler.KindComputeExecutor,
+			Members: []*scheduler.CommitteeNode{
+				{Role: scheduler.RoleWorker, PublicKey: node2},
+				{Role: scheduler.RoleBackupWorker, PublicKey: node1},
+			},
+			RuntimeID: runtimeID2,
+			ValidFor:  43,
+		},
+	} {
+		if err := state.PutCommittee(ctx, committee); err != nil {
+			return fmt.Errorf("setting committee: %w", err)
+		}
+	}
+
+	// Populate validators.
+	if err := state.PutCurrentValidators(ctx, map[signature.PublicKey]*scheduler.Validator{
+		node1: {ID: node1, EntityID: entity1, VotingPower: 10},
+		node2: {ID: node2, EntityID: entity2, VotingPower: 20},
+	}); err != nil {
+		return fmt.Errorf("setting current validators: %w", err)
+	}
+	if err := state.PutPendingValidators(ctx, map[signature.PublicKey]*scheduler.Validator{
+		node3: {ID: node3, EntityID: entity1, VotingPower: 5},
+	}); err != nil {
+		return fmt.Errorf("setting pending validators: %w", err)
+	}
+
+	return nil
+}
@@ -135,6 +135,12 @@ func (app *stakingApplication) ExecuteMessage(ctx *api.Context, kind, msg interf
 			return app.addEscrow(ctx, state, m.AddEscrow)
 		case m.ReclaimEscrow != nil:
 			return app.reclaimEscrow(ctx, state, m.ReclaimEscrow)
+		case m.Allow != nil:
+			return nil, app.allow(ctx, state, m.Allow)
+		case m.Burn != nil:
+			return nil, app.burn(ctx, state, m.Burn)
+		case m.AmendCommissionSchedule != nil:
+			return nil, app.amendCommissionSchedule(ctx, state, m.AmendCommissionSchedule)
 		default:
 			return nil, staking.ErrInvalidArgument
 		}
@@ -174,6 +174,11 @@ func (app *stakingApplication) burn(ctx *api.Context, state *stakingState.Mutabl
 		return err
 	}
 
+	// Check if account messages are allowed.
+	if ctx.IsMessageExecution() && !params.AllowAccountMessages {
+		return staking.ErrForbidden
+	}
+
 	// Return early for simulation as we only need gas accounting.
 	if ctx.IsSimulation() {
 		return nil
@@ -549,6 +554,11 @@ func (app *stakingApplication) amendCommissionSchedule(
 		return err
 	}
 
+	// Check if account messages are allowed.
+	if ctx.IsMessageExecution() && !params.AllowAccountMessages {
+		return staking.ErrForbidden
+	}
+
 	// Return early for simulation as we only need gas accounting.
 	if ctx.IsSimulation() {
 		return nil
@@ -619,6 +629,11 @@ func (app *stakingApplication) allow(
 		return err
 	}
 
+	// Check if account messages are allowed.
+	if ctx.IsMessageExecution() && !params.AllowAccountMessages {
+		return staking.ErrForbidden
+	}
+
 	// Return early for simulation as we only need gas accounting.
 	if ctx.IsSimulation() {
 		return nil
@@ -9,7 +9,9 @@ import (
 	"github.com/oasisprotocol/oasis-core/go/common/crypto/signature"
 	"github.com/oasisprotocol/oasis-core/go/common/quantity"
 	abciAPI "github.com/oasisprotocol/oasis-core/go/consensus/cometbft/api"
+	roothashApi "github.com/oasisprotocol/oasis-core/go/consensus/cometbft/apps/roothash/api"
 	stakingState "github.com/oasisprotocol/oasis-core/go/consensus/cometbft/apps/staking/state"
+	"github.com/oasisprotocol/oasis-core/go/roothash/api/message"
 	staking "github.com/oasisprotocol/oasis-core/go/staking/api"
 )
 
@@ -854,6 +856,86 @@ func TestAllowEscrowMessages(t *testing.T) {
 	}, reclaimResult, "reclaim escrow result should be correct")
 }
 
+func TestAllowAccountMessages(t *testing.T) {
+	require := require.New(t)
+	var err error
+
+	appState := abciAPI.NewMockApplicationState(&abciAPI.MockApplicationStateConfig{})
+	ctx := appState.NewContext(abciAPI.ContextEndBlock)
+	defer ctx.Close()
+
+	stakeState := stakingState.NewMutableState(ctx.State())
+	app := &stakingApplication{
+		state: appState,
+	}
+	err = stakeState.SetConsensusParameters(ctx, &staking.ConsensusParameters{
+		MaxAllowances: 1,
+	})
+	require.NoError(err, "setting staking consensus parameters should not error")
+
+	pk1 := signature.NewPublicKey("aaafffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff")
+	addr1 := staking.NewAddress(pk1)
+	pk2 := signature.NewPublicKey("bbbfffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff")
+	addr2 := staking.NewAddress(pk2)
+
+	err = stakeState.SetAccount(ctx, addr1, &staking.Account{
+		General: staking.GeneralAccount{
+			Balance: *quantity.NewFromUint64(50),
+		},
+	})
+	require.NoError(err, "SetAccount")
+
+	txCtx := appState.NewContext(abciAPI.ContextDeliverTx)
+	defer txCtx.Close()
+	txCtx.SetTxSigner(pk1)
+	txCtx = txCtx.WithMessageExecution()
+
+	allowMsg := &message.StakingMessage{
+		Allow: &staking.Allow{
+			Beneficiary:  addr2,
+			AmountChange: *quantity.NewFromUint64(10),
+		},
+	}
+	burnMsg := &message.StakingMessage{
+		Burn: &staking.Burn{
+			Amount: *quantity.NewFromUint64(10),
+		},
+	}
+	amendMsg := &message.StakingMessage{
+		AmendCommissionSchedule: &staking.AmendCommissionSchedule{},
+	}
+
+	// Account messages should not be allowed by default.
+	for _, msg := range []*message.StakingMessage{allowMsg, burnMsg, amendMsg} {
+		result, msgErr := app.ExecuteMessage(txCtx, roothashApi.RuntimeMessageStaking, msg)
+		require.Equal(staking.ErrForbidden, msgErr, "account message should be denied")
+		require.Nil(result, "failed account message result should be nil")
+	}
+
+	err = stakeState.SetConsensusParameters(ctx, &staking.ConsensusParameters{
+		MaxAllowances:        1,
+		AllowAccountMessages: true,
+	})
+	require.NoError(err, "setting staking consensus parameters should not error")
+
+	// Account messages should be allowed.
+	_, err = app.ExecuteMessage(txCtx, roothashApi.RuntimeMessageStaking, allowMsg)
+	require.NoError(err, "allow message should be allowed")
+	acct, err := stakeState.Account(txCtx, addr1)
+	require.NoError(err, "Account")
+	require.EqualValues(*quantity.NewFromUint64(10), acct.General.Allowances[addr2], "allowance should be updated")
+
+	_, err = app.ExecuteMessage(txCtx, roothashApi.RuntimeMessageStaking, burnMsg)
+	require.NoError(err, "burn message should be allowed")
+	acct, err = stakeState.Account(txCtx, addr1)
+	require.NoError(err, "Account")
+	require.EqualValues(*quantity.NewFromUint64(40), acct.General.Balance, "balance should be reduced")
+
+	// A message with no fields set should be rejected.
+	_, err = app.ExecuteMessage(txCtx, roothashApi.RuntimeMessageStaking, &message.StakingMessage{})
+	require.Equal(staking.ErrInvalidArgument, err, "empty message should be rejected")
+}
+
 func TestTransfer(t *testing.T) {
 	require := require.New(t)
 	var err error
@@ -5,6 +5,7 @@ package beacon
 import (
 	"context"
 	"encoding/hex"
+	"errors"
 	"fmt"
 	"sync"
 
@@ -309,6 +310,11 @@ func (sc *serviceClient) DeliverBlock(ctx context.Context, height int64) error {
 	return nil
 }
 
+// GetEvents returns the beacon events at specified block height.
+func (sc *serviceClient) GetEvents(ctx context.Context, height int64) ([]*beaconAPI.Event, error) {
+	return tmAPI.GetBlockEvents(ctx, sc.backend, height, EventsFromCometBFT)
+}
+
 func (sc *serviceClient) DeliverEvent(_ context.Context, height int64, _ cmttypes.Tx, ev *cmtabcitypes.Event) error {
 	for _, pair := range ev.GetAttributes() {
 		key := pair.GetKey()
@@ -438,3 +444,66 @@ func New(ctx context.Context, backend tmAPI.Backend) (ServiceClient, error) {
 
 	return sc, nil
 }
+
+// EventsFromCometBFT extracts beacon events from CometBFT events.
+func EventsFromCometBFT(
+	tx cmttypes.Tx,
+	height int64,
+	tmEvents []cmtabcitypes.Event,
+) ([]*beaconAPI.Event, error) {
+	var txHash hash.Hash
+	switch tx {
+	case nil:
+		txHash.Empty()
+	default:
+		txHash = hash.NewFromBytes(tx)
+	}
+
+	var evs []*beaconAPI.Event
+	var errs error
+	for _, tmEv := range tmEvents {
+		// Ignore events that don't relate to the beacon app.
+		if tmEv.GetType() != app.EventType {
+			continue
+		}
+
+		for _, pair := range tmEv.GetAttributes() {
+			key := pair.GetKey()
+			val := pair.GetValue()
+
+			switch {
+			case events.IsAttributeKind(key, &beaconAPI.EpochEvent{}):
+				// Epoch event.
+				var e beaconAPI.EpochEvent
+				if err := events.DecodeValue(val, &e); err != nil {
+					errs = errors.Join(errs, fmt.Errorf("beacon: corrupt Epoch event: %w", err))
+					continue
+				}
+
+				evs = append(evs, &beaconAPI.Event{Height: height, TxHash: txHash, Epoch: &e})
+			case events.IsAttributeKind(key, &beaconAPI.BeaconEvent{}):
+				// Beacon event.
+				var e beaconAPI.BeaconEvent
+				if err := events.DecodeValue(val, &e); err != nil {
+					errs = errors.Join(errs, fmt.Errorf("beacon: corrupt Beacon event: %w", err))
+					continue
+				}
+
+				evs = append(evs, &beaconAPI.Event{Height: height, TxHash: txHash, Beacon: &e})
+			case events.IsAttributeKind(key, &beaconAPI.VRFEvent{}):
+				// VRF event.
+				var e beaconAPI.VRFEvent
+				if err := events.DecodeValue(val, &e); err != nil {
+					errs = errors.Join(errs, fmt.Errorf("beacon: corrupt VRF event: %w", err))
+					continue
+				}
+
+				evs = append(evs, &beaconAPI.Event{Height: height, TxHash: txHash, VRF: &e})
+			default:
+				errs = errors.Join(errs, fmt.Errorf("beacon: unknown event type: key: %s, val: %s", key, val))
+			}
+		}
+	}
+
+	return evs, errs
+}
@@ -0,0 +1,58 @@
+package beacon
+
+import (
+	"testing"
+
+	cmtabcitypes "github.com/cometbft/cometbft/abci/types"
+	"github.com/stretchr/testify/require"
+
+	beaconAPI "github.com/oasisprotocol/oasis-core/go/beacon/api"
+	"github.com/oasisprotocol/oasis-core/go/common/crypto/hash"
+	tmAPI "github.com/oasisprotocol/oasis-core/go/consensus/cometbft/api"
+	app "github.com/oasisprotocol/oasis-core/go/consensus/cometbft/apps/beacon"
+)
+
+func TestEventsFromCometBFT(t *testing.T) {
+	require := require.New(t)
+
+	tmEvents := []cmtabcitypes.Event{
+		tmAPI.NewEventBuilder(app.AppName).
+			TypedAttribute(&beaconAPI.EpochEvent{Epoch: 5}).
+			TypedAttribute(&beaconAPI.BeaconEvent{Beacon: []byte("beacon")}).
+			TypedAttribute(&beaconAPI.VRFEvent{Epoch: 5, SubmitAfter: 10}).
+			Event(),
+		// Events of other applications should be ignored.
+		tmAPI.NewEventBuilder("other").
+			TypedAttribute(&beaconAPI.EpochEvent{Epoch: 6}).
+			Event(),
+	}
+
+	evs, err := EventsFromCometBFT(nil, 42, tmEvents)
+	require.NoError(err, "EventsFromCometBFT")
+	require.Len(evs, 3)
+
+	var emptyHash hash.Hash
+	emptyHash.Empty()
+	for _, ev := range evs {
+		require.EqualValues(42, ev.Height)
+		require.Equal(emptyHash, ev.TxHash)
+	}
+	require.Equal(&beaconAPI.EpochEvent{Epoch: 5}, evs[0].Epoch)
+	require.Equal(&beaconAPI.BeaconEvent{Beacon: []byte("beacon")}, evs[1].Beacon)
+	require.Equal(&beaconAPI.VRFEvent{Epoch: 5, SubmitAfter: 10}, evs[2].VRF)
+
+	// Transaction events should include the transaction hash.
+	tx := []byte("tx")
+	evs, err = EventsFromCometBFT(tx, 42, tmEvents[:1])
+	require.NoError(err, "EventsFromCometBFT")
+	require.Len(evs, 3)
+	require.Equal(hash.NewFromBytes(tx), evs[0].TxHash)
+
+	// Unknown events should fail.
+	unknown := cmtabcitypes.Event{
+		Type:       app.EventType,
+		Attributes: []cmtabcitypes.EventAttribute{{Key: "unknown", Value: "value"}},
+	}
+	_, err = EventsFromCometBFT(nil, 42, []cmtabcitypes.Event{unknown})
+	require.Error(err, "unknown events should fail")
+}
@@ -2,14 +2,19 @@ package churp
 
 import (
 	"context"
+	"errors"
+	"fmt"
 
 	cmtabcitypes "github.com/cometbft/cometbft/abci/types"
+	cmttypes "github.com/cometbft/cometbft/types"
 	"github.com/eapache/channels"
 
+	"github.com/oasisprotocol/oasis-core/go/common/crypto/hash"
 	"github.com/oasisprotocol/oasis-core/go/common/logging"
 	"github.com/oasisprotocol/oasis-core/go/common/pubsub"
 	consensus "github.com/oasisprotocol/oasis-core/go/consensus/api"
 	"github.com/oasisprotocol/oasis-core/go/consensus/api/events"
+	tmapi "github.com/oasisprotocol/oasis-core/go/consensus/cometbft/api"
 	app "github.com/oasisprotocol/oasis-core/go/consensus/cometbft/apps/keymanager"
 	"github.com/oasisprotocol/oasis-core/go/keymanager/churp"
 	"github.com/oasisprotocol/oasis-core/go/registry/api"
@@ -18,6 +23,7 @@ import (
 type ServiceClient struct {
 	logger *logging.Logger
 
+	backend        tmapi.Backend
 	querier        *app.QueryFactory
 	statusNotifier *pubsub.Broker
 }
@@ -71,6 +77,11 @@ func (sc *ServiceClient) WatchStatuses() (<-chan *churp.Status, *pubsub.Subscrip
 	return ch, sub
 }
 
+// GetEvents returns the key manager CHURP events at specified block height.
+func (sc *ServiceClient) GetEvents(ctx context.Context, height int64) ([]*churp.Event, error) {
+	return tmapi.GetBlockEvents(ctx, sc.backend, height, EventsFromCometBFT)
+}
+
 func (sc *ServiceClient) DeliverEvent(ev *cmtabcitypes.Event) error {
 	for _, pair := range ev.GetAttributes() {
 		key := pair.GetKey()
@@ -104,9 +115,10 @@ func (sc *ServiceClient) DeliverEvent(ev *cmtabcitypes.Event) error {
 
 // New constructs a new CometBFT backed key manager CHURP management Backend
 // instance.
-func New(ctx context.Context, querier *app.QueryFactory) (*ServiceClient, error) {
+func New(ctx context.Context, backend tmapi.Backend, querier *app.QueryFactory) (*ServiceClient, error) {
 	sc := ServiceClient{
 		logger:  logging.GetLogger("cometbft/keymanager/churp"),
+		backend: backend,
 		querier: querier,
 	}
 	sc.statusNotifier = pubsub.NewBrokerEx(func(ch channels.Channel) {
@@ -126,3 +138,56 @@ func New(ctx context.Context, querier *app.QueryFactory) (*ServiceClient, error)
 
 	return &sc, nil
 }
+
+// EventsFromCometBFT extracts key manager CHURP events from CometBFT events.
+func EventsFromCometBFT(
+	tx cmttypes.Tx,
+	height int64,
+	tmEvents []cmtabcitypes.Event,
+) ([]*churp.Event, error) {
+	var txHash hash.Hash
+	switch tx {
+	case nil:
+		txHash.Empty()
+	default:
+		txHash = hash.NewFromBytes(tx)
+	}
+
+	var evs []*churp.Event
+	var errs error
+	for _, tmEv := range tmEvents {
+		// Ignore events that don't relate to the key manager app.
+		if tmEv.GetType() != app.EventType {
+			continue
+		}
+
+		// Other key manager events (e.g. secrets events) share the event type and are ignored.
+		for _, pair := range tmEv.GetAttributes() {
+			key := pair.GetKey()
+			val := pair.GetValue()
+
+			switch {
+			case events.IsAttributeKind(key, &churp.CreateEvent{}):
+				// Create event.
+				var e churp.CreateEvent
+				if err := events.DecodeValue(val, &e); err != nil {
+					errs = errors.Join(errs, fmt.Errorf("keymanager/churp: corrupt Create event: %w", err))
+					continue
+				}
+
+				evs = append(evs, &churp.Event{Height: height, TxHash: txHash, Create: &e})
+			case events.IsAttributeKind(key, &churp.UpdateEvent{}):
+				// Update event.
+				var e churp.UpdateEvent
+				if err := events.DecodeValue(val, &e); err != nil {
+					errs = errors.Join(errs, fmt.Errorf("keymanager/churp: corrupt Update event: %w", err))
+					continue
+				}
+
+				evs = append(evs, &churp.Event{Height: height, TxHash: txHash, Update: &e})
+			}
+		}
+	}
+
+	return evs, errs
+}
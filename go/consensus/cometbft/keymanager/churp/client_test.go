@@ -0,0 +1,39 @@
+package churp
+
+import (
+	"testing"
+
+	cmtabcitypes "github.com/cometbft/cometbft/abci/types"
+	"github.com/stretchr/testify/require"
+
+	tmapi "github.com/oasisprotocol/oasis-core/go/consensus/cometbft/api"
+	app "github.com/oasisprotocol/oasis-core/go/consensus/cometbft/apps/keymanager"
+	"github.com/oasisprotocol/oasis-core/go/keymanager/churp"
+	"github.com/oasisprotocol/oasis-core/go/keymanager/secrets"
+)
+
+func TestEventsFromCometBFT(t *testing.T) {
+	require := require.New(t)
+
+	status := &churp.Status{Identity: churp.Identity{ID: 1}, Threshold: 2}
+	tmEvents := []cmtabcitypes.Event{
+		tmapi.NewEventBuilder(app.AppName).
+			TypedAttribute(&churp.CreateEvent{Status: status}).
+			// Key manager secrets events share the event type and should be ignored.
+			TypedAttribute(&secrets.StatusUpdateEvent{}).
+			TypedAttribute(&churp.UpdateEvent{Status: status}).
+			Event(),
+	}
+
+	evs, err := EventsFromCometBFT(nil, 42, tmEvents)
+	require.NoError(err, "EventsFromCometBFT")
+	require.Len(evs, 2)
+	require.EqualValues(42, evs[0].Height)
+	require.NotNil(evs[0].Create)
+	require.Equal(status.ID, evs[0].Create.Status.ID)
+	require.Nil(evs[0].Update)
+	require.EqualValues(42, evs[1].Height)
+	require.NotNil(evs[1].Update)
+	require.Equal(status.Threshold, evs[1].Update.Status.Threshold)
+	require.Nil(evs[1].Create)
+}
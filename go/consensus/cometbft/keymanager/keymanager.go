@@ -75,12 +75,12 @@ func New(ctx context.Context, backend tmapi.Backend) (ServiceClient, error) {
 
 	querier := a.QueryFactory().(*app.QueryFactory)
 
-	secretsClient, err := secrets.New(ctx, querier)
+	secretsClient, err := secrets.New(ctx, backend, querier)
 	if err != nil {
 		return nil, fmt.Errorf("cometbft/keymanager: failed to create secrets client: %w", err)
 	}
 
-	churpClient, err := churp.New(ctx, querier)
+	churpClient, err := churp.New(ctx, backend, querier)
 	if err != nil {
 		return nil, fmt.Errorf("cometbft/keymanager: failed to create churp client: %w", err)
 	}
@@ -4,14 +4,19 @@ package secrets
 
 import (
 	"context"
+	"errors"
+	"fmt"
 
 	cmtabcitypes "github.com/cometbft/cometbft/abci/types"
+	cmttypes "github.com/cometbft/cometbft/types"
 	"github.com/eapache/channels"
 
+	"github.com/oasisprotocol/oasis-core/go/common/crypto/hash"
 	"github.com/oasisprotocol/oasis-core/go/common/logging"
 	"github.com/oasisprotocol/oasis-core/go/common/pubsub"
 	consensus "github.com/oasisprotocol/oasis-core/go/consensus/api"
 	"github.com/oasisprotocol/oasis-core/go/consensus/api/events"
+	tmapi "github.com/oasisprotocol/oasis-core/go/consensus/cometbft/api"
 	app "github.com/oasisprotocol/oasis-core/go/consensus/cometbft/apps/keymanager"
 	"github.com/oasisprotocol/oasis-core/go/keymanager/secrets"
 	registry "github.com/oasisprotocol/oasis-core/go/registry/api"
@@ -20,6 +25,7 @@ import (
 type ServiceClient struct {
 	logger *logging.Logger
 
+	backend           tmapi.Backend
 	querier           *app.QueryFactory
 	statusNotifier    *pubsub.Broker
 	mstSecretNotifier *pubsub.Broker
@@ -95,6 +101,11 @@ func (sc *ServiceClient) WatchEphemeralSecrets() (<-chan *secrets.SignedEncrypte
 	return ch, sub
 }
 
+// GetEvents returns the key manager secrets events at specified block height.
+func (sc *ServiceClient) GetEvents(ctx context.Context, height int64) ([]*secrets.Event, error) {
+	return tmapi.GetBlockEvents(ctx, sc.backend, height, EventsFromCometBFT)
+}
+
 func (sc *ServiceClient) DeliverEvent(ev *cmtabcitypes.Event) error {
 	for _, pair := range ev.GetAttributes() {
 		if events.IsAttributeKind(pair.GetKey(), &secrets.StatusUpdateEvent{}) {
@@ -138,9 +149,10 @@ func (sc *ServiceClient) DeliverEvent(ev *cmtabcitypes.Event) error {
 
 // New constructs a new CometBFT backed key manager secrets management Backend
 // instance.
-func New(ctx context.Context, querier *app.QueryFactory) (*ServiceClient, error) {
+func New(ctx context.Context, backend tmapi.Backend, querier *app.QueryFactory) (*ServiceClient, error) {
 	sc := ServiceClient{
 		logger:            logging.GetLogger("cometbft/keymanager/secrets"),
+		backend:           backend,
 		querier:           querier,
 		mstSecretNotifier: pubsub.NewBroker(false),
 		ephSecretNotifier: pubsub.NewBroker(false),
@@ -162,3 +174,65 @@ func New(ctx context.Context, querier *app.QueryFactory) (*ServiceClient, error)
 
 	return &sc, nil
 }
+
+// EventsFromCometBFT extracts key manager secrets events from CometBFT events.
+func EventsFromCometBFT(
+	tx cmttypes.Tx,
+	height int64,
+	tmEvents []cmtabcitypes.Event,
+) ([]*secrets.Event, error) {
+	var txHash hash.Hash
+	switch tx {
+	case nil:
+		txHash.Empty()
+	default:
+		txHash = hash.NewFromBytes(tx)
+	}
+
+	var evs []*secrets.Event
+	var errs error
+	for _, tmEv := range tmEvents {
+		// Ignore events that don't relate to the key manager app.
+		if tmEv.GetType() != app.EventType {
+			continue
+		}
+
+		// Other key manager events (e.g. CHURP events) share the event type and are ignored.
+		for _, pair := range tmEv.GetAttributes() {
+			key := pair.GetKey()
+			val := pair.GetValue()
+
+			switch {
+			case events.IsAttributeKind(key, &secrets.StatusUpdateEvent{}):
+				// Status update event.
+				var e secrets.StatusUpdateEvent
+				if err := events.DecodeValue(val, &e); err != nil {
+					errs = errors.Join(errs, fmt.Errorf("keymanager: corrupt StatusUpdate event: %w", err))
+					continue
+				}
+
+				evs = append(evs, &secrets.Event{Height: height, TxHash: txHash, StatusUpdate: &e})
+			case events.IsAttributeKind(key, &secrets.MasterSecretPublishedEvent{}):
+				// Master secret published event.
+				var e secrets.MasterSecretPublishedEvent
+				if err := events.DecodeValue(val, &e); err != nil {
+					errs = errors.Join(errs, fmt.Errorf("keymanager: corrupt MasterSecretPublished event: %w", err))
+					continue
+				}
+
+				evs = append(evs, &secrets.Event{Height: height, TxHash: txHash, MasterSecretPublished: &e})
+			case events.IsAttributeKind(key, &secrets.EphemeralSecretPublishedEvent{}):
+				// Ephemeral secret published event.
+				var e secrets.EphemeralSecretPublishedEvent
+				if err := events.DecodeValue(val, &e); err != nil {
+					errs = errors.Join(errs, fmt.Errorf("keymanager: corrupt EphemeralSecretPublished event: %w", err))
+					continue
+				}
+
+				evs = append(evs, &secrets.Event{Height: height, TxHash: txHash, EphemeralSecretPublished: &e})
+			}
+		}
+	}
+
+	return evs, errs
+}
@@ -11,6 +11,7 @@ import (
 	cmttypes "github.com/cometbft/cometbft/types"
 
 	consensus "github.com/oasisprotocol/oasis-core/go/consensus/api"
+	"github.com/oasisprotocol/oasis-core/go/p2p/rpc"
 )
 
 // ClientService is a CometBFT consensus light client service.
@@ -25,6 +26,10 @@ type ClientService interface {
 type Client interface {
 	consensus.LightClient
 
+	// GetPeerLightBlock queries peers for a specific light block, bypassing the local full node
+	// and the local light block store.
+	GetPeerLightBlock(ctx context.Context, height int64) (*consensus.LightBlock, rpc.PeerFeedback, error)
+
 	// GetVerifiedLightBlock returns a verified light block.
 	GetVerifiedLightBlock(ctx context.Context, height int64) (*cmttypes.LightBlock, error)
 
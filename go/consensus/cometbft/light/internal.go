@@ -77,6 +77,11 @@ func (lc *lightClient) GetLightBlock(ctx context.Context, height int64) (*consen
 	})
 }
 
+// GetPeerLightBlock implements api.Client.
+func (lc *lightClient) GetPeerLightBlock(ctx context.Context, height int64) (*consensus.LightBlock, rpc.PeerFeedback, error) {
+	return lc.GetLightBlock(ctx, height)
+}
+
 // GetParameters implements api.Client.
 func (lc *lightClient) GetParameters(ctx context.Context, height int64) (*consensus.Parameters, rpc.PeerFeedback, error) {
 	return tryProviders(ctx, lc, func(p api.Provider) (*consensus.Parameters, rpc.PeerFeedback, error) {
@@ -326,6 +326,17 @@ func (c *client) GetLightBlock(ctx context.Context, height int64) (*consensus.Li
 	return nil, nil, mergedErr
 }
 
+// GetPeerLightBlock implements api.Client.
+func (c *client) GetPeerLightBlock(ctx context.Context, height int64) (*consensus.LightBlock, rpc.PeerFeedback, error) {
+	select {
+	case <-c.initCh:
+	case <-ctx.Done():
+		return nil, nil, ctx.Err()
+	}
+
+	return c.lc.GetLightBlock(ctx, height)
+}
+
 // GetParameters implements api.Client.
 func (c *client) GetParameters(ctx context.Context, height int64) (*consensus.Parameters, rpc.PeerFeedback, error) {
 	select {
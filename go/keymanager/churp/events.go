@@ -1,6 +1,9 @@
 package churp
 
-import "github.com/oasisprotocol/oasis-core/go/consensus/api/events"
+import (
+	"github.com/oasisprotocol/oasis-core/go/common/crypto/hash"
+	"github.com/oasisprotocol/oasis-core/go/consensus/api/events"
+)
 
 var (
 	// eventNameCreate is the event name for create events.
@@ -29,3 +32,12 @@ type UpdateEvent struct {
 func (ev *UpdateEvent) EventKind() string {
 	return eventNameUpdate
 }
+
+// Event is a key manager CHURP event.
+type Event struct {
+	Height int64     `json:"height,omitempty"`
+	TxHash hash.Hash `json:"tx_hash,omitempty"`
+
+	Create *CreateEvent `json:"create,omitempty"`
+	Update *UpdateEvent `json:"update,omitempty"`
+}
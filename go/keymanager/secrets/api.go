@@ -10,6 +10,7 @@ import (
 	beacon "github.com/oasisprotocol/oasis-core/go/beacon/api"
 	"github.com/oasisprotocol/oasis-core/go/common"
 	"github.com/oasisprotocol/oasis-core/go/common/cbor"
+	"github.com/oasisprotocol/oasis-core/go/common/crypto/hash"
 	"github.com/oasisprotocol/oasis-core/go/common/crypto/signature"
 	"github.com/oasisprotocol/oasis-core/go/common/errors"
 	"github.com/oasisprotocol/oasis-core/go/common/pubsub"
@@ -394,3 +395,13 @@ type EphemeralSecretPublishedEvent struct {
 func (ev *EphemeralSecretPublishedEvent) EventKind() string {
 	return "ephemeral_secret"
 }
+
+// Event is a key manager secrets event.
+type Event struct {
+	Height int64     `json:"height,omitempty"`
+	TxHash hash.Hash `json:"tx_hash,omitempty"`
+
+	StatusUpdate             *StatusUpdateEvent             `json:"status_update,omitempty"`
+	MasterSecretPublished    *MasterSecretPublishedEvent    `json:"master_secret_published,omitempty"`
+	EphemeralSecretPublished *EphemeralSecretPublishedEvent `json:"ephemeral_secret_published,omitempty"`
+}
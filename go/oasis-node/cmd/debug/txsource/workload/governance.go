@@ -359,6 +359,9 @@ func (g *governanceWorkload) doChangeParametersProposal() error { // nolint: goc
 		if randBool() {
 			pc.AllowEscrowMessages = &params.AllowEscrowMessages
 		}
+		if randBool() {
+			pc.AllowAccountMessages = &params.AllowAccountMessages
+		}
 		if randBool() {
 			pc.MaxAllowances = &params.MaxAllowances
 		}
@@ -117,6 +117,9 @@ func (c *keyManagerRPCClient) fetchPublicKey(ctx context.Context, generation uin
 		}
 		return nil, fmt.Errorf(msg)
 	}
+	if rsp.Body.StructuredError != nil {
+		return nil, fmt.Errorf(rsp.Body.StructuredError.Message)
+	}
 
 	var key secrets.SignedPublicKey
 	if err = cbor.Unmarshal(rsp.Body.Success, &key); err != nil {
@@ -162,6 +165,9 @@ func (c *keyManagerRPCClient) fetchEphemeralPublicKey(ctx context.Context, epoch
 		}
 		return nil, fmt.Errorf(msg)
 	}
+	if rsp.Body.StructuredError != nil {
+		return nil, fmt.Errorf(rsp.Body.StructuredError.Message)
+	}
 
 	var key secrets.SignedPublicKey
 	if err = cbor.Unmarshal(rsp.Body.Success, &key); err != nil {
@@ -256,6 +256,7 @@ func (sc *txSourceImpl) Fixture() (*oasis.NetworkFixture, error) {
 			FeeSplitWeightVote:        *quantity.NewFromUint64(1),
 			FeeSplitWeightNextPropose: *quantity.NewFromUint64(1),
 			AllowEscrowMessages:       true,
+			AllowAccountMessages:      true,
 			Thresholds: map[staking.ThresholdKind]quantity.Quantity{
 				staking.KindEntity:            *quantity.NewFromUint64(0),
 				staking.KindNodeValidator:     *quantity.NewFromUint64(0),
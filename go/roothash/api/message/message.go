@@ -50,6 +50,10 @@ type StakingMessage struct {
 	Withdraw      *staking.Withdraw      `json:"withdraw,omitempty"`
 	AddEscrow     *staking.Escrow        `json:"add_escrow,omitempty"`
 	ReclaimEscrow *staking.ReclaimEscrow `json:"reclaim_escrow,omitempty"`
+
+	Allow                   *staking.Allow                   `json:"allow,omitempty"`
+	Burn                    *staking.Burn                    `json:"burn,omitempty"`
+	AmendCommissionSchedule *staking.AmendCommissionSchedule `json:"amend_commission_schedule,omitempty"`
 }
 
 // ValidateBasic performs basic validation of the runtime message.
@@ -71,6 +75,21 @@ func (sm *StakingMessage) ValidateBasic() error {
 		// No validation at this time.
 		setFields++
 	}
+	if sm.Allow != nil {
+		// No validation at this time.
+		setFields++
+	}
+	if sm.Burn != nil {
+		// No validation at this time.
+		setFields++
+	}
+	if sm.AmendCommissionSchedule != nil {
+		amendment := sm.AmendCommissionSchedule.Amendment
+		if len(amendment.Rates) == 0 && len(amendment.Bounds) == 0 {
+			return fmt.Errorf("staking runtime message has an empty commission schedule amendment")
+		}
+		setFields++
+	}
 	switch setFields {
 	case 0:
 		return fmt.Errorf("staking runtime message has no fields set")
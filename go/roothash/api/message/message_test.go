@@ -1,6 +1,7 @@
 package message
 
 import (
+	"encoding/hex"
 	"testing"
 	"time"
 
@@ -80,6 +81,11 @@ func TestMessageValidateBasic(t *testing.T) {
 		{"StakingMultipleFieldsSet", Message{Staking: &StakingMessage{Transfer: &staking.Transfer{}, Withdraw: &staking.Withdraw{}}}, false},
 		{"StakingAllFieldsSet", Message{Staking: &StakingMessage{Transfer: &staking.Transfer{}, Withdraw: &staking.Withdraw{}, AddEscrow: &staking.Escrow{}, ReclaimEscrow: &staking.ReclaimEscrow{}}}, false},
 		{"ValidStaking", Message{Staking: &StakingMessage{Transfer: &staking.Transfer{}}}, true},
+		{"ValidStakingAllow", Message{Staking: &StakingMessage{Allow: &staking.Allow{}}}, true},
+		{"ValidStakingBurn", Message{Staking: &StakingMessage{Burn: &staking.Burn{}}}, true},
+		{"StakingAmendCommissionScheduleEmpty", Message{Staking: &StakingMessage{AmendCommissionSchedule: &staking.AmendCommissionSchedule{}}}, false},
+		{"ValidStakingAmendCommissionSchedule", Message{Staking: &StakingMessage{AmendCommissionSchedule: &staking.AmendCommissionSchedule{Amendment: staking.CommissionSchedule{Rates: []staking.CommissionRateStep{{}}}}}}, true},
+		{"StakingAllowAndBurnSet", Message{Staking: &StakingMessage{Allow: &staking.Allow{}, Burn: &staking.Burn{}}}, false},
 		{"RegistryNoFieldsSet", Message{Registry: &RegistryMessage{}}, false},
 		{"RegistryInvalid", Message{Registry: &RegistryMessage{UpdateRuntime: nil}}, false},
 		{"ValidRegistry", Message{Registry: &RegistryMessage{UpdateRuntime: &registry.Runtime{}}}, true},
@@ -96,6 +102,53 @@ func TestMessageValidateBasic(t *testing.T) {
 	}
 }
 
+func TestStakingMessageEncoding(t *testing.T) {
+	require := require.New(t)
+
+	var beneficiary staking.Address
+	err := beneficiary.UnmarshalBinary([]byte{
+		0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01,
+		0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01,
+	})
+	require.NoError(err, "UnmarshalBinary")
+
+	// NOTE: These cases should be synced with tests in runtime/src/consensus/roothash/message.rs.
+	for _, tc := range []struct {
+		msg             StakingMessage
+		expectedEncoded string
+	}{
+		{
+			StakingMessage{Allow: &staking.Allow{
+				Beneficiary:  beneficiary,
+				Negative:     true,
+				AmountChange: *quantity.NewFromUint64(10),
+			}},
+			"a165616c6c6f77a3686e65676174697665f56b62656e6566696369617279550101010101010101010101010101010101010101016d616d6f756e745f6368616e6765410a",
+		},
+		{
+			StakingMessage{Burn: &staking.Burn{
+				Amount: *quantity.NewFromUint64(1000),
+			}},
+			"a1646275726ea166616d6f756e744203e8",
+		},
+		{
+			StakingMessage{AmendCommissionSchedule: &staking.AmendCommissionSchedule{
+				Amendment: staking.CommissionSchedule{
+					Rates: []staking.CommissionRateStep{{Rate: *quantity.NewFromUint64(50_000)}},
+				},
+			}},
+			"a17819616d656e645f636f6d6d697373696f6e5f7363686564756c65a169616d656e646d656e74a165726174657381a1647261746542c350",
+		},
+	} {
+		require.Equal(tc.expectedEncoded, hex.EncodeToString(cbor.Marshal(tc.msg)), "serialization should match")
+
+		var dec StakingMessage
+		err = cbor.Unmarshal(cbor.Marshal(tc.msg), &dec)
+		require.NoError(err, "Unmarshal")
+		require.EqualValues(tc.msg, dec, "serialization should round-trip")
+	}
+}
+
 func newTestRuntime() *registry.Runtime {
 	ent, _, _ := entity.TestEntity()
 
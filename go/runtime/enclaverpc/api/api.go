@@ -42,10 +42,18 @@ type Request struct {
 	Args   interface{} `json:"args"`
 }
 
+// Error is a structured EnclaveRPC error identified by its module and code.
+type Error struct {
+	Module  string `json:"module,omitempty"`
+	Code    uint32 `json:"code,omitempty"`
+	Message string `json:"message,omitempty"`
+}
+
 // Body is an EnclaveRPC response body.
 type Body struct {
-	Success cbor.RawMessage `json:",omitempty"`
-	Error   *string         `json:",omitempty"`
+	Success         cbor.RawMessage `json:",omitempty"`
+	Error           *string         `json:",omitempty"`
+	StructuredError *Error          `json:",omitempty"`
 }
 
 // Response is an EnclaveRPC response.
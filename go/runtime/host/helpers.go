@@ -58,6 +58,9 @@ type RichRuntime interface {
 
 	// ConsensusSync requests the runtime to sync its light client up to the given consensus height.
 	ConsensusSync(ctx context.Context, height uint64) error
+
+	// GetMetrics requests the runtime to report the current values of its metrics.
+	GetMetrics(ctx context.Context) ([]protocol.RuntimeMetric, error)
 }
 
 type richRuntime struct {
@@ -164,6 +167,9 @@ func (r *richRuntime) LocalRPC(
 	case msg.Response.Body.Success != nil:
 	case msg.Response.Body.Error != nil:
 		return fmt.Errorf("rpc failure: '%s'", *msg.Response.Body.Error)
+	case msg.Response.Body.StructuredError != nil:
+		se := msg.Response.Body.StructuredError
+		return fmt.Errorf("rpc failure: %w", errors.FromCode(se.Module, se.Code, se.Message))
 	default:
 		return fmt.Errorf("unknown rpc response status: '%+v'", msg)
 	}
@@ -193,6 +199,29 @@ func (r *richRuntime) ConsensusSync(ctx context.Context, height uint64) error {
 	return nil
 }
 
+// Implements RichRuntime.
+func (r *richRuntime) GetMetrics(ctx context.Context) ([]protocol.RuntimeMetric, error) {
+	info, err := r.GetInfo(ctx)
+	if err != nil {
+		return nil, err
+	}
+	if info.ProtocolVersion.ToU64() < protocol.ProtocolVersionMetrics.ToU64() {
+		// Older runtimes do not export any metrics.
+		return nil, nil
+	}
+
+	resp, err := r.Call(ctx, &protocol.Body{
+		RuntimeMetricsRequest: &protocol.Empty{},
+	})
+	switch {
+	case err != nil:
+		return nil, err
+	case resp.RuntimeMetricsResponse == nil:
+		return nil, errors.WithContext(ErrInternal, "malformed runtime response")
+	}
+	return resp.RuntimeMetricsResponse.Metrics, nil
+}
+
 // NewRichRuntime creates a new higher-level wrapper for a given runtime. It provides additional
 // convenience functions for talking with a runtime.
 func NewRichRuntime(rt Runtime) RichRuntime {
@@ -0,0 +1,100 @@
+package host
+
+import (
+	"context"
+	"fmt"
+	"time"
+
+	"github.com/prometheus/client_golang/prometheus"
+
+	"github.com/oasisprotocol/oasis-core/go/common"
+	"github.com/oasisprotocol/oasis-core/go/common/logging"
+	"github.com/oasisprotocol/oasis-core/go/runtime/host/protocol"
+)
+
+const (
+	// runtimeMetricsPrefix is the prefix of all metrics exported from within the runtime.
+	runtimeMetricsPrefix = "oasis_"
+	// runtimeMetricsTimeout is the maximum amount of time to wait for the runtime to report its
+	// metrics during a single collection.
+	runtimeMetricsTimeout = 1 * time.Second
+)
+
+var errMalformedMetric = fmt.Errorf("runtime: malformed metric")
+
+type runtimeMetricsCollector struct {
+	runtimeID common.Namespace
+	rt        RichRuntime
+
+	logger *logging.Logger
+}
+
+// Implements prometheus.Collector.
+func (c *runtimeMetricsCollector) Describe(chan<- *prometheus.Desc) {
+	// Runtime metrics are only known once collected, so this is an unchecked collector.
+}
+
+// Implements prometheus.Collector.
+func (c *runtimeMetricsCollector) Collect(ch chan<- prometheus.Metric) {
+	ctx, cancel := context.WithTimeout(context.Background(), runtimeMetricsTimeout)
+	defer cancel()
+
+	metrics, err := c.rt.GetMetrics(ctx)
+	if err != nil {
+		c.logger.Debug("failed to fetch runtime metrics",
+			"err", err,
+		)
+		return
+	}
+
+	for _, m := range metrics {
+		metric, err := c.convertMetric(m)
+		if err != nil {
+			c.logger.Debug("skipping malformed runtime metric",
+				"name", m.Name,
+				"err", err,
+			)
+			continue
+		}
+		ch <- metric
+	}
+}
+
+func (c *runtimeMetricsCollector) convertMetric(m protocol.RuntimeMetric) (prometheus.Metric, error) {
+	desc := prometheus.NewDesc(
+		runtimeMetricsPrefix+m.Name,
+		m.Help,
+		nil,
+		prometheus.Labels{"runtime": c.runtimeID.String()},
+	)
+
+	switch {
+	case m.Value.Counter != nil:
+		return prometheus.NewConstMetric(desc, prometheus.CounterValue, float64(*m.Value.Counter))
+	case m.Value.Gauge != nil:
+		return prometheus.NewConstMetric(desc, prometheus.GaugeValue, float64(*m.Value.Gauge))
+	case m.Value.Histogram != nil:
+		buckets := make(map[float64]uint64, len(m.Value.Histogram.Buckets))
+		for _, b := range m.Value.Histogram.Buckets {
+			buckets[float64(b.UpperBound)] = b.Count
+		}
+		return prometheus.NewConstHistogram(
+			desc,
+			m.Value.Histogram.Count,
+			float64(m.Value.Histogram.Sum),
+			buckets,
+		)
+	default:
+		return nil, errMalformedMetric
+	}
+}
+
+// NewRuntimeMetricsCollector creates a new prometheus collector that exports the metrics collected
+// inside the given runtime. Metrics are fetched from the runtime on each collection.
+func NewRuntimeMetricsCollector(runtimeID common.Namespace, rt RichRuntime) prometheus.Collector {
+	return &runtimeMetricsCollector{
+		runtimeID: runtimeID,
+		rt:        rt,
+		logger:    logging.GetLogger("runtime/host/metrics").With("runtime_id", runtimeID),
+	}
+}
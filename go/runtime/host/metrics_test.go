@@ -0,0 +1,76 @@
+package host
+
+import (
+	"context"
+	"testing"
+
+	"github.com/prometheus/client_golang/prometheus"
+	"github.com/stretchr/testify/require"
+
+	"github.com/oasisprotocol/oasis-core/go/common"
+	"github.com/oasisprotocol/oasis-core/go/runtime/host/protocol"
+)
+
+type metricsRuntime struct {
+	RichRuntime
+
+	metrics []protocol.RuntimeMetric
+}
+
+func (r *metricsRuntime) GetMetrics(context.Context) ([]protocol.RuntimeMetric, error) {
+	return r.metrics, nil
+}
+
+func TestRuntimeMetricsCollector(t *testing.T) {
+	require := require.New(t)
+
+	counter := uint64(42)
+	gauge := int64(-1)
+	rt := &metricsRuntime{
+		metrics: []protocol.RuntimeMetric{
+			{
+				Name:  "runtime_test_counter",
+				Help:  "Test counter.",
+				Value: protocol.RuntimeMetricValue{Counter: &counter},
+			},
+			{
+				Name:  "runtime_test_gauge",
+				Help:  "Test gauge.",
+				Value: protocol.RuntimeMetricValue{Gauge: &gauge},
+			},
+			{
+				Name: "runtime_test_histogram",
+				Help: "Test histogram.",
+				Value: protocol.RuntimeMetricValue{Histogram: &protocol.RuntimeMetricHistogram{
+					Buckets: []protocol.RuntimeMetricHistogramBucket{
+						{UpperBound: 10, Count: 1},
+						{UpperBound: 100, Count: 2},
+					},
+					Count: 3,
+					Sum:   250,
+				}},
+			},
+			{
+				Name: "runtime_test_malformed",
+				Help: "Metric without a value.",
+			},
+		},
+	}
+
+	var runtimeID common.Namespace
+	collector := NewRuntimeMetricsCollector(runtimeID, rt)
+
+	ch := make(chan prometheus.Metric, len(rt.metrics))
+	collector.Collect(ch)
+	close(ch)
+
+	var descs []string
+	for m := range ch {
+		descs = append(descs, m.Desc().String())
+	}
+	require.Len(descs, 3, "malformed metrics should be skipped")
+	require.Contains(descs[0], `"oasis_runtime_test_counter"`)
+	require.Contains(descs[0], runtimeID.String())
+	require.Contains(descs[1], `"oasis_runtime_test_gauge"`)
+	require.Contains(descs[2], `"oasis_runtime_test_histogram"`)
+}
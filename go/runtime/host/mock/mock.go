@@ -184,6 +184,17 @@ func (r *runtime) Call(ctx context.Context, body *protocol.Body) (*protocol.Body
 	case body.RuntimeConsensusSyncRequest != nil:
 		// Nothing to be done, but we need to indicate success.
 		return &protocol.Body{RuntimeConsensusSyncResponse: &protocol.Empty{}}, nil
+	case body.RuntimeMetricsRequest != nil:
+		requests := uint64(1)
+		return &protocol.Body{RuntimeMetricsResponse: &protocol.RuntimeMetricsResponse{
+			Metrics: []protocol.RuntimeMetric{
+				{
+					Name:  "runtime_mock_requests",
+					Help:  "Number of requests processed by the mock runtime.",
+					Value: protocol.RuntimeMetricValue{Counter: &requests},
+				},
+			},
+		}}, nil
 	default:
 		return nil, fmt.Errorf("(mock) method not supported")
 	}
@@ -330,6 +330,7 @@ func (c *connection) call(ctx context.Context, body *Body) (result *Body, err er
 	id := c.nextRequestID
 	c.nextRequestID++
 	c.pendingRequests[id] = respCh
+	info := c.info
 	c.Unlock()
 
 	defer func() {
@@ -343,6 +344,11 @@ func (c *connection) call(ctx context.Context, body *Body) (result *Body, err er
 		MessageType: MessageRequest,
 		Body:        *body,
 	}
+	// Only propagate the span context to runtimes that support it as older runtimes reject
+	// messages with unknown fields.
+	if info != nil && info.ProtocolVersion.ToU64() >= ProtocolVersionSpanContext.ToU64() {
+		msg.SpanContext = SpanContextFromContext(ctx)
+	}
 
 	// Queue the message.
 	if err = c.sendMessage(ctx, &msg); err != nil {
@@ -440,6 +446,11 @@ func (c *connection) handleMessage(ctx context.Context, message *Message) {
 			return
 		}
 
+		// Propagate the span context of the request, if any.
+		if message.SpanContext != nil {
+			ctx = WithSpanContext(ctx, message.SpanContext)
+		}
+
 		// Call actual handler.
 		body, err := c.handler.Handle(ctx, &message.Body)
 		if err != nil {
@@ -583,6 +594,24 @@ func (c *connection) InitHost(ctx context.Context, conn net.Conn, hi *HostInfo)
 		)
 	}
 
+	// Inform the runtime about the protocol version supported by the host so that it knows which
+	// requests it can make. Runtimes that do not support this request assume an older host.
+	if info.ProtocolVersion.ToU64() >= ProtocolVersionHostVersion.ToU64() {
+		rsp, err = c.call(ctx, &Body{RuntimeHostVersionRequest: &RuntimeHostVersionRequest{
+			Version: version.RuntimeHostProtocol,
+		}})
+		switch {
+		default:
+		case err != nil:
+			return nil, fmt.Errorf("rhp: error while sending host version: %w", err)
+		case rsp.RuntimeHostVersionResponse == nil:
+			c.logger.Error("unexpected response to RuntimeHostVersionRequest",
+				"response", rsp,
+			)
+			return nil, fmt.Errorf("rhp: unexpected response to RuntimeHostVersionRequest")
+		}
+	}
+
 	rtVersion := info.RuntimeVersion
 	c.logger.Info("runtime host protocol initialized", "runtime_version", rtVersion)
 
@@ -16,21 +16,34 @@ import (
 
 type testHandler struct {
 	calls int
+
+	protocolVersion *version.Version
+	hostVersion     *version.Version
+	spanContext     *SpanContext
 }
 
 // Implements Handler.
-func (h *testHandler) Handle(_ context.Context, body *Body) (*Body, error) {
+func (h *testHandler) Handle(ctx context.Context, body *Body) (*Body, error) {
 	// We need to handle RuntimeInfoRequest for initialization to complete.
 	if body.RuntimeInfoRequest != nil {
+		// Need to use the correct version.
+		protocolVersion := version.RuntimeHostProtocol
+		if h.protocolVersion != nil {
+			protocolVersion = *h.protocolVersion
+		}
 		return &Body{
 			RuntimeInfoResponse: &RuntimeInfoResponse{
-				// Need to use the correct version.
-				ProtocolVersion: version.RuntimeHostProtocol,
+				ProtocolVersion: protocolVersion,
 			},
 		}, nil
 	}
+	if body.RuntimeHostVersionRequest != nil {
+		h.hostVersion = &body.RuntimeHostVersionRequest.Version
+		return &Body{RuntimeHostVersionResponse: &Empty{}}, nil
+	}
 
 	h.calls++
+	h.spanContext = SpanContextFromContext(ctx)
 	return body, nil
 }
 
@@ -62,6 +75,8 @@ func TestEchoRequestResponse(t *testing.T) {
 	require.NoError(err, "A.InitGuest()")
 	_, err = protoB.InitHost(context.Background(), connB, &HostInfo{})
 	require.NoError(err, "B.InitHost()")
+	require.NotNil(handlerA.hostVersion, "host version should be sent to the runtime")
+	require.EqualValues(version.RuntimeHostProtocol, *handlerA.hostVersion)
 
 	require.Panics(func() { _, _ = protoA.InitHost(context.Background(), connA, &HostInfo{}) }, "connection reinit should panic")
 	require.Panics(func() { _ = protoA.InitGuest(connA) }, "connection reinit should panic")
@@ -102,6 +117,67 @@ func TestEchoRequestResponse(t *testing.T) {
 	require.EqualValues(version.RuntimeHostProtocol, info.ProtocolVersion)
 }
 
+func TestInitHostOlderRuntime(t *testing.T) {
+	require := require.New(t)
+	runtimeID := common.NewTestNamespaceFromSeed([]byte("test conn"), 0)
+
+	logger := logging.GetLogger("test")
+	connA, connB := net.Pipe()
+	handlerA := &testHandler{
+		protocolVersion: &version.Version{Major: version.RuntimeHostProtocol.Major},
+	}
+	protoA, err := NewConnection(logger, runtimeID, handlerA)
+	require.NoError(err, "A.New()")
+	protoB, err := NewConnection(logger, runtimeID, &testHandler{})
+	require.NoError(err, "B.New()")
+
+	err = protoA.InitGuest(connA)
+	require.NoError(err, "A.InitGuest()")
+	_, err = protoB.InitHost(context.Background(), connB, &HostInfo{})
+	require.NoError(err, "B.InitHost()")
+	require.Nil(handlerA.hostVersion, "host version should not be sent to older runtimes")
+	require.EqualValues(0, handlerA.calls, "Handler A must not be called")
+
+	sc := &SpanContext{TraceID: [16]byte{1}, SpanID: [8]byte{2}, Sampled: true}
+	_, err = protoB.Call(WithSpanContext(context.Background(), sc), &Body{Empty: &Empty{}})
+	require.NoError(err, "B.Call()")
+	require.EqualValues(1, handlerA.calls, "Handler A must be called")
+	require.Nil(handlerA.spanContext, "span context should not be sent to older runtimes")
+
+	protoA.Close()
+	protoB.Close()
+}
+
+func TestSpanContextPropagation(t *testing.T) {
+	require := require.New(t)
+	runtimeID := common.NewTestNamespaceFromSeed([]byte("test conn"), 0)
+
+	logger := logging.GetLogger("test")
+	connA, connB := net.Pipe()
+	handlerA := &testHandler{}
+	protoA, err := NewConnection(logger, runtimeID, handlerA)
+	require.NoError(err, "A.New()")
+	protoB, err := NewConnection(logger, runtimeID, &testHandler{})
+	require.NoError(err, "B.New()")
+
+	err = protoA.InitGuest(connA)
+	require.NoError(err, "A.InitGuest()")
+	_, err = protoB.InitHost(context.Background(), connB, &HostInfo{})
+	require.NoError(err, "B.InitHost()")
+
+	sc := &SpanContext{TraceID: [16]byte{1}, SpanID: [8]byte{2}, Sampled: true}
+	_, err = protoB.Call(WithSpanContext(context.Background(), sc), &Body{Empty: &Empty{}})
+	require.NoError(err, "B.Call()")
+	require.EqualValues(sc, handlerA.spanContext, "span context should be propagated")
+
+	_, err = protoB.Call(context.Background(), &Body{Empty: &Empty{}})
+	require.NoError(err, "B.Call()")
+	require.Nil(handlerA.spanContext, "no span context should be propagated")
+
+	protoA.Close()
+	protoB.Close()
+}
+
 func TestBigMessage(t *testing.T) {
 	require := require.New(t)
 	runtimeID := common.NewTestNamespaceFromSeed([]byte("test conn"), 0)
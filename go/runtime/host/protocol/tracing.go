@@ -0,0 +1,20 @@
+package protocol
+
+import "context"
+
+type spanContextKey struct{}
+
+// WithSpanContext returns a copy of the parent context with the given span context attached.
+//
+// Requests sent to the runtime using the returned context carry the span context so that the
+// runtime can correlate its logs with the span and attach the same context to any requests it
+// makes to the host while processing them.
+func WithSpanContext(ctx context.Context, sc *SpanContext) context.Context {
+	return context.WithValue(ctx, spanContextKey{}, sc)
+}
+
+// SpanContextFromContext returns the span context attached to the given context, if any.
+func SpanContextFromContext(ctx context.Context) *SpanContext {
+	sc, _ := ctx.Value(spanContextKey{}).(*SpanContext)
+	return sc
+}
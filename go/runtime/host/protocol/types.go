@@ -32,6 +32,20 @@ import (
 // NOTE: Bump RuntimeProtocol version in go/common/version if you
 //       change any of the structures below.
 
+var (
+	// ProtocolVersionHostVersion is the first runtime host protocol version that supports the
+	// RuntimeHostVersionRequest message.
+	ProtocolVersionHostVersion = version.Version{Major: 5, Minor: 2, Patch: 0}
+
+	// ProtocolVersionMetrics is the first runtime host protocol version that supports the
+	// RuntimeMetricsRequest message.
+	ProtocolVersionMetrics = version.Version{Major: 5, Minor: 2, Patch: 0}
+
+	// ProtocolVersionSpanContext is the first runtime host protocol version that supports span
+	// contexts in messages.
+	ProtocolVersionSpanContext = version.Version{Major: 5, Minor: 2, Patch: 0}
+)
+
 // MessageType is a message type.
 type MessageType uint8
 
@@ -60,9 +74,20 @@ const (
 
 // Message is a protocol message.
 type Message struct {
-	ID          uint64      `json:"id"`
-	MessageType MessageType `json:"message_type"`
-	Body        Body        `json:"body"`
+	ID          uint64       `json:"id"`
+	MessageType MessageType  `json:"message_type"`
+	Body        Body         `json:"body"`
+	SpanContext *SpanContext `json:"span_context,omitempty"`
+}
+
+// SpanContext is the tracing span context propagated with a request.
+type SpanContext struct {
+	// TraceID is the trace identifier.
+	TraceID [16]byte `json:"trace_id"`
+	// SpanID is the identifier of the span that issued the request.
+	SpanID [8]byte `json:"span_id"`
+	// Sampled indicates whether the trace has been sampled.
+	Sampled bool `json:"sampled,omitempty"`
 }
 
 // Body is a protocol message body.
@@ -107,6 +132,10 @@ type Body struct {
 	RuntimeConsensusSyncResponse                  *Empty                                        `json:",omitempty"`
 	RuntimeNotifyRequest                          *RuntimeNotifyRequest                         `json:",omitempty"`
 	RuntimeNotifyResponse                         *Empty                                        `json:",omitempty"`
+	RuntimeMetricsRequest                         *Empty                                        `json:",omitempty"`
+	RuntimeMetricsResponse                        *RuntimeMetricsResponse                       `json:",omitempty"`
+	RuntimeHostVersionRequest                     *RuntimeHostVersionRequest                    `json:",omitempty"`
+	RuntimeHostVersionResponse                    *Empty                                        `json:",omitempty"`
 
 	// Host interface.
 	HostRPCCallRequest               *HostRPCCallRequest               `json:",omitempty"`
@@ -119,6 +148,8 @@ type Body struct {
 	HostLocalStorageSetResponse      *Empty                            `json:",omitempty"`
 	HostFetchConsensusBlockRequest   *HostFetchConsensusBlockRequest   `json:",omitempty"`
 	HostFetchConsensusBlockResponse  *HostFetchConsensusBlockResponse  `json:",omitempty"`
+	HostFetchWitnessBlockRequest     *HostFetchWitnessBlockRequest     `json:",omitempty"`
+	HostFetchWitnessBlockResponse    *HostFetchWitnessBlockResponse    `json:",omitempty"`
 	HostFetchConsensusEventsRequest  *HostFetchConsensusEventsRequest  `json:",omitempty"`
 	HostFetchConsensusEventsResponse *HostFetchConsensusEventsResponse `json:",omitempty"`
 	HostFetchTxBatchRequest          *HostFetchTxBatchRequest          `json:",omitempty"`
@@ -487,6 +518,57 @@ type RuntimeConsensusSyncRequest struct {
 	Height uint64 `json:"height"`
 }
 
+// RuntimeHostVersionRequest is a request to the runtime informing it about the runtime host
+// protocol version supported by the host.
+//
+// The request is only sent to runtimes that support ProtocolVersionHostVersion and runtimes must
+// assume that hosts which never send it do not support any requests introduced after that version.
+type RuntimeHostVersionRequest struct {
+	// Version is the runtime host protocol version supported by the host.
+	Version version.Version `json:"version"`
+}
+
+// RuntimeMetricsResponse is a runtime metrics response message body.
+type RuntimeMetricsResponse struct {
+	// Metrics are the current values of all metrics registered inside the runtime.
+	Metrics []RuntimeMetric `json:"metrics,omitempty"`
+}
+
+// RuntimeMetric is a metric collected inside the runtime.
+type RuntimeMetric struct {
+	// Name is the metric name.
+	Name string `json:"name"`
+	// Help is the human-readable metric description.
+	Help string `json:"help"`
+	// Value is the metric value.
+	Value RuntimeMetricValue `json:"value"`
+}
+
+// RuntimeMetricValue is the value of a runtime metric. Exactly one of the fields is set.
+type RuntimeMetricValue struct {
+	Counter   *uint64                 `json:"counter,omitempty"`
+	Gauge     *int64                  `json:"gauge,omitempty"`
+	Histogram *RuntimeMetricHistogram `json:"histogram,omitempty"`
+}
+
+// RuntimeMetricHistogram is the value of a runtime histogram metric.
+type RuntimeMetricHistogram struct {
+	// Buckets are the cumulative histogram buckets.
+	Buckets []RuntimeMetricHistogramBucket `json:"buckets"`
+	// Count is the total number of observations.
+	Count uint64 `json:"count"`
+	// Sum is the sum of all observed values.
+	Sum uint64 `json:"sum"`
+}
+
+// RuntimeMetricHistogramBucket is a cumulative runtime histogram bucket.
+type RuntimeMetricHistogramBucket struct {
+	// UpperBound is the inclusive upper bound of the bucket.
+	UpperBound uint64 `json:"upper_bound"`
+	// Count is the number of observations less than or equal to the upper bound.
+	Count uint64 `json:"count"`
+}
+
 // HostRPCCallRequest is a host RPC call request message body.
 type HostRPCCallRequest struct {
 	Endpoint string          `json:"endpoint"`
@@ -501,8 +583,12 @@ type HostRPCCallRequest struct {
 	// This enables the runtime to notify the node whether the given peer should continue to be used
 	// or not based on higher-level logic that lives in the runtime.
 	//
-	// In case no feedback is given success is assumed.
+	// In case no feedback is given success is assumed once the same node handles another call.
 	PeerFeedback *enclaverpc.PeerFeedback `json:"pf,omitempty"`
+	// PeerFeedbackNode is the optional identity of the node the peer feedback refers to. In case
+	// it is set, the feedback is for the last RPC call handled by the given node instead of the
+	// last RPC call under the given endpoint. This allows runtimes to perform concurrent calls.
+	PeerFeedbackNode *signature.PublicKey `json:"pf_node,omitempty"`
 }
 
 // HostRPCCallResponse is a host RPC call response message body.
@@ -564,15 +650,30 @@ type HostFetchConsensusBlockResponse struct {
 	Block consensus.LightBlock `json:"block"`
 }
 
+// HostFetchWitnessBlockRequest is a request to host to fetch the given consensus light block
+// directly from consensus peers, bypassing the local consensus node.
+type HostFetchWitnessBlockRequest struct {
+	Height uint64 `json:"height"`
+}
+
+// HostFetchWitnessBlockResponse is a response from host fetching the given consensus light block
+// from consensus peers.
+type HostFetchWitnessBlockResponse struct {
+	Block consensus.LightBlock `json:"block"`
+}
+
 // EventKind is the consensus event kind.
 type EventKind uint8
 
 // Supported consensus event kinds.
 const (
-	EventKindStaking    EventKind = 1
-	EventKindRegistry   EventKind = 2
-	EventKindRootHash   EventKind = 3
-	EventKindGovernance EventKind = 4
+	EventKindStaking         EventKind = 1
+	EventKindRegistry        EventKind = 2
+	EventKindRootHash        EventKind = 3
+	EventKindGovernance      EventKind = 4
+	EventKindBeacon          EventKind = 5
+	EventKindKeyManager      EventKind = 6
+	EventKindKeyManagerChurp EventKind = 7
 )
 
 // HostFetchConsensusEventsRequest is a request to host to fetch the consensus events for the given
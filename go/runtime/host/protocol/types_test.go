@@ -1,9 +1,12 @@
 package protocol
 
 import (
+	"encoding/hex"
 	"testing"
 
 	"github.com/stretchr/testify/require"
+
+	"github.com/oasisprotocol/oasis-core/go/common/cbor"
 )
 
 func TestBody_Type(t *testing.T) {
@@ -30,3 +33,35 @@ func TestBody_Type(t *testing.T) {
 	// All members are nil, expect empty string.
 	require.Equal(t, b.Type(), "")
 }
+
+func TestMessageSpanContextSerialization(t *testing.T) {
+	require := require.New(t)
+
+	msg := Message{
+		ID:          42,
+		MessageType: MessageRequest,
+		Body:        Body{Empty: &Empty{}},
+		SpanContext: &SpanContext{
+			TraceID: [16]byte{1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1},
+			SpanID:  [8]byte{2, 2, 2, 2, 2, 2, 2, 2},
+			Sampled: true,
+		},
+	}
+
+	// NOTE: This must be kept in sync with runtime/src/common/trace.rs.
+	enc := cbor.Marshal(msg)
+	require.EqualValues(
+		"a4626964182a64626f6479a165456d707479a06c6d6573736167655f74797065016c7370616e5f636f6e74657874a36773616d706c6564f5677370616e5f69644802020202020202026874726163655f69645001010101010101010101010101010101",
+		hex.EncodeToString(enc),
+	)
+
+	var dec Message
+	err := cbor.Unmarshal(enc, &dec)
+	require.NoError(err, "Unmarshal")
+	require.EqualValues(msg, dec)
+
+	// Messages without a span context must not include the field.
+	msg.SpanContext = nil
+	enc = cbor.Marshal(msg)
+	require.EqualValues("a3626964182a64626f6479a165456d707479a06c6d6573736167655f7479706501", hex.EncodeToString(enc))
+}
@@ -19,6 +19,7 @@ type Client interface {
 	// members. The latter can be restricted by specifying a non-empty list of allowed nodes.
 	//
 	// The provided peer feedback is optional feedback on the peer that handled the last EnclaveRPC
-	// request (if any) which may be used to inform the routing decision.
-	CallEnclave(ctx context.Context, data []byte, nodes []signature.PublicKey, kind enclaverpc.Kind, pf *enclaverpc.PeerFeedback) ([]byte, signature.PublicKey, error)
+	// request (if any) which may be used to inform the routing decision. In case a feedback node
+	// is given, the feedback refers to the last request handled by that node instead.
+	CallEnclave(ctx context.Context, data []byte, nodes []signature.PublicKey, kind enclaverpc.Kind, pf *enclaverpc.PeerFeedback, pfNode *signature.PublicKey) ([]byte, signature.PublicKey, error)
 }
@@ -8,6 +8,7 @@ import (
 	"time"
 
 	"github.com/eapache/channels"
+	"github.com/prometheus/client_golang/prometheus"
 
 	beacon "github.com/oasisprotocol/oasis-core/go/beacon/api"
 	"github.com/oasisprotocol/oasis-core/go/common"
@@ -20,7 +21,10 @@ import (
 	consensus "github.com/oasisprotocol/oasis-core/go/consensus/api"
 	"github.com/oasisprotocol/oasis-core/go/consensus/api/transaction"
 	consensusResults "github.com/oasisprotocol/oasis-core/go/consensus/api/transaction/results"
+	"github.com/oasisprotocol/oasis-core/go/keymanager/churp"
 	"github.com/oasisprotocol/oasis-core/go/keymanager/secrets"
+	"github.com/oasisprotocol/oasis-core/go/oasis-node/cmd/common/metrics"
+	"github.com/oasisprotocol/oasis-core/go/p2p/rpc"
 	registry "github.com/oasisprotocol/oasis-core/go/registry/api"
 	"github.com/oasisprotocol/oasis-core/go/runtime/bundle"
 	"github.com/oasisprotocol/oasis-core/go/runtime/bundle/component"
@@ -96,6 +100,13 @@ func (n *RuntimeHostNode) ProvisionHostedRuntime(ctx context.Context) (host.Rich
 	notifier := n.factory.NewRuntimeHostNotifier(ctx, agg)
 	rr := host.NewRichRuntime(agg)
 
+	// Export the metrics collected inside the runtime.
+	if metrics.Enabled() {
+		if err = prometheus.Register(host.NewRuntimeMetricsCollector(runtime.ID(), rr)); err != nil {
+			return nil, nil, fmt.Errorf("failed to register runtime metrics collector: %w", err)
+		}
+	}
+
 	n.Lock()
 	n.agg = agg.(*multi.Aggregate)
 	n.runtime = rr
@@ -246,7 +257,7 @@ func (h *runtimeHostHandler) handleHostRPCCall(
 		if err != nil {
 			return nil, err
 		}
-		res, node, err := kmCli.CallEnclave(ctx, rq.Request, rq.Nodes, rq.Kind, rq.PeerFeedback)
+		res, node, err := kmCli.CallEnclave(ctx, rq.Request, rq.Nodes, rq.Kind, rq.PeerFeedback, rq.PeerFeedbackNode)
 		if err != nil {
 			return nil, err
 		}
@@ -341,6 +352,32 @@ func (h *runtimeHostHandler) handleHostFetchConsensusBlock(
 	return &protocol.HostFetchConsensusBlockResponse{Block: *blk}, nil
 }
 
+// peerLightClient is implemented by light clients that can fetch light blocks directly from
+// consensus peers, bypassing the local consensus node.
+type peerLightClient interface {
+	GetPeerLightBlock(ctx context.Context, height int64) (*consensus.LightBlock, rpc.PeerFeedback, error)
+}
+
+func (h *runtimeHostHandler) handleHostFetchWitnessBlock(
+	ctx context.Context,
+	rq *protocol.HostFetchWitnessBlockRequest,
+) (*protocol.HostFetchWitnessBlockResponse, error) {
+	lc, err := h.env.GetLightClient()
+	if err != nil {
+		return nil, err
+	}
+	plc, ok := lc.(peerLightClient)
+	if !ok {
+		return nil, fmt.Errorf("method not supported")
+	}
+	blk, _, err := plc.GetPeerLightBlock(ctx, int64(rq.Height))
+	if err != nil {
+		return nil, fmt.Errorf("witness light block fetch failure: %w", err)
+	}
+
+	return &protocol.HostFetchWitnessBlockResponse{Block: *blk}, nil
+}
+
 func (h *runtimeHostHandler) handleHostFetchConsensusEvents(
 	ctx context.Context,
 	rq *protocol.HostFetchConsensusEventsRequest,
@@ -383,12 +420,54 @@ func (h *runtimeHostHandler) handleHostFetchConsensusEvents(
 		for _, gev := range gevs {
 			evs = append(evs, &consensusResults.Event{Governance: gev})
 		}
+	case protocol.EventKindBeacon:
+		bevs, err := fetchEvents[beacon.Event](ctx, h.consensus.Beacon(), rq.Height)
+		if err != nil {
+			return nil, err
+		}
+		evs = make([]*consensusResults.Event, 0, len(bevs))
+		for _, bev := range bevs {
+			evs = append(evs, &consensusResults.Event{Beacon: bev})
+		}
+	case protocol.EventKindKeyManager:
+		kevs, err := fetchEvents[secrets.Event](ctx, h.consensus.KeyManager().Secrets(), rq.Height)
+		if err != nil {
+			return nil, err
+		}
+		evs = make([]*consensusResults.Event, 0, len(kevs))
+		for _, kev := range kevs {
+			evs = append(evs, &consensusResults.Event{KeyManager: kev})
+		}
+	case protocol.EventKindKeyManagerChurp:
+		cevs, err := fetchEvents[churp.Event](ctx, h.consensus.KeyManager().Churp(), rq.Height)
+		if err != nil {
+			return nil, err
+		}
+		evs = make([]*consensusResults.Event, 0, len(cevs))
+		for _, cev := range cevs {
+			evs = append(evs, &consensusResults.Event{KeyManagerChurp: cev})
+		}
 	default:
 		return nil, fmt.Errorf("method not supported")
 	}
 	return &protocol.HostFetchConsensusEventsResponse{Events: evs}, nil
 }
 
+// eventsGetter is implemented by consensus service backends that can return the events emitted at
+// a given height, even though this is not part of their public interface.
+type eventsGetter[E any] interface {
+	GetEvents(ctx context.Context, height int64) ([]*E, error)
+}
+
+// fetchEvents returns the events emitted at the given height by the given consensus service backend.
+func fetchEvents[E any](ctx context.Context, backend any, height uint64) ([]*E, error) {
+	getter, ok := backend.(eventsGetter[E])
+	if !ok {
+		return nil, fmt.Errorf("method not supported")
+	}
+	return getter.GetEvents(ctx, int64(height))
+}
+
 func (h *runtimeHostHandler) handleHostFetchGenesisHeight(
 	ctx context.Context,
 ) (*protocol.HostFetchGenesisHeightResponse, error) {
@@ -526,6 +605,9 @@ func (h *runtimeHostHandler) Handle(ctx context.Context, rq *protocol.Body) (*pr
 	case rq.HostFetchConsensusBlockRequest != nil:
 		// Consensus light client.
 		rsp.HostFetchConsensusBlockResponse, err = h.handleHostFetchConsensusBlock(ctx, rq.HostFetchConsensusBlockRequest)
+	case rq.HostFetchWitnessBlockRequest != nil:
+		// Consensus light client witness.
+		rsp.HostFetchWitnessBlockResponse, err = h.handleHostFetchWitnessBlock(ctx, rq.HostFetchWitnessBlockRequest)
 	case rq.HostFetchConsensusEventsRequest != nil:
 		// Consensus events.
 		rsp.HostFetchConsensusEventsResponse, err = h.handleHostFetchConsensusEvents(ctx, rq.HostFetchConsensusEventsRequest)
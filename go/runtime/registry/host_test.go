@@ -0,0 +1,173 @@
+package registry
+
+import (
+	"context"
+	"testing"
+
+	"github.com/stretchr/testify/require"
+
+	beacon "github.com/oasisprotocol/oasis-core/go/beacon/api"
+	consensus "github.com/oasisprotocol/oasis-core/go/consensus/api"
+	keymanager "github.com/oasisprotocol/oasis-core/go/keymanager/api"
+	"github.com/oasisprotocol/oasis-core/go/keymanager/churp"
+	"github.com/oasisprotocol/oasis-core/go/keymanager/secrets"
+	"github.com/oasisprotocol/oasis-core/go/p2p/rpc"
+	"github.com/oasisprotocol/oasis-core/go/runtime/host/protocol"
+)
+
+type testEventsBackend[E any] struct {
+	height int64
+	events []*E
+}
+
+func (b *testEventsBackend[E]) GetEvents(_ context.Context, height int64) ([]*E, error) {
+	b.height = height
+	return b.events, nil
+}
+
+type testBeacon struct {
+	beacon.Backend
+	testEventsBackend[beacon.Event]
+}
+
+type testSecrets struct {
+	secrets.Backend
+	testEventsBackend[secrets.Event]
+}
+
+type testChurp struct {
+	churp.Backend
+	testEventsBackend[churp.Event]
+}
+
+type testKeyManager struct {
+	keymanager.Backend
+
+	secrets secrets.Backend
+	churp   churp.Backend
+}
+
+func (km *testKeyManager) Secrets() secrets.Backend {
+	return km.secrets
+}
+
+func (km *testKeyManager) Churp() churp.Backend {
+	return km.churp
+}
+
+type testConsensus struct {
+	consensus.Backend
+
+	beacon     beacon.Backend
+	keymanager keymanager.Backend
+}
+
+func (c *testConsensus) Beacon() beacon.Backend {
+	return c.beacon
+}
+
+func (c *testConsensus) KeyManager() keymanager.Backend {
+	return c.keymanager
+}
+
+type testLightClient struct {
+	consensus.LightClient
+}
+
+type testPeerLightClient struct {
+	testLightClient
+
+	height int64
+}
+
+func (lc *testPeerLightClient) GetPeerLightBlock(_ context.Context, height int64) (*consensus.LightBlock, rpc.PeerFeedback, error) {
+	lc.height = height
+	return &consensus.LightBlock{Height: height}, rpc.NewNopPeerFeedback(), nil
+}
+
+type testEnvironment struct {
+	RuntimeHostHandlerEnvironment
+
+	lc consensus.LightClient
+}
+
+func (env *testEnvironment) GetLightClient() (consensus.LightClient, error) {
+	return env.lc, nil
+}
+
+func TestHandleHostFetchConsensusEvents(t *testing.T) {
+	require := require.New(t)
+	ctx := context.Background()
+
+	bcn := &testBeacon{}
+	bcn.events = []*beacon.Event{{Height: 42, Epoch: &beacon.EpochEvent{Epoch: 5}}}
+	scs := &testSecrets{}
+	scs.events = []*secrets.Event{{Height: 42, StatusUpdate: &secrets.StatusUpdateEvent{}}}
+	chp := &testChurp{}
+	chp.events = []*churp.Event{
+		{Height: 42, Create: &churp.CreateEvent{}},
+		{Height: 42, Update: &churp.UpdateEvent{}},
+	}
+	h := &runtimeHostHandler{
+		consensus: &testConsensus{
+			beacon:     bcn,
+			keymanager: &testKeyManager{secrets: scs, churp: chp},
+		},
+	}
+
+	rsp, err := h.handleHostFetchConsensusEvents(ctx, &protocol.HostFetchConsensusEventsRequest{
+		Height: 42,
+		Kind:   protocol.EventKindBeacon,
+	})
+	require.NoError(err, "beacon events")
+	require.EqualValues(42, bcn.height)
+	require.Len(rsp.Events, 1)
+	require.Equal(bcn.events[0], rsp.Events[0].Beacon)
+
+	rsp, err = h.handleHostFetchConsensusEvents(ctx, &protocol.HostFetchConsensusEventsRequest{
+		Height: 42,
+		Kind:   protocol.EventKindKeyManager,
+	})
+	require.NoError(err, "key manager events")
+	require.EqualValues(42, scs.height)
+	require.Len(rsp.Events, 1)
+	require.Equal(scs.events[0], rsp.Events[0].KeyManager)
+
+	rsp, err = h.handleHostFetchConsensusEvents(ctx, &protocol.HostFetchConsensusEventsRequest{
+		Height: 42,
+		Kind:   protocol.EventKindKeyManagerChurp,
+	})
+	require.NoError(err, "key manager CHURP events")
+	require.EqualValues(42, chp.height)
+	require.Len(rsp.Events, 2)
+	require.Equal(chp.events[0], rsp.Events[0].KeyManagerChurp)
+	require.Equal(chp.events[1], rsp.Events[1].KeyManagerChurp)
+
+	// Backends that cannot return events should fail.
+	h.consensus = &testConsensus{beacon: struct{ beacon.Backend }{}}
+	_, err = h.handleHostFetchConsensusEvents(ctx, &protocol.HostFetchConsensusEventsRequest{
+		Height: 42,
+		Kind:   protocol.EventKindBeacon,
+	})
+	require.Error(err, "beacon events should not be supported")
+}
+
+func TestHandleHostFetchWitnessBlock(t *testing.T) {
+	require := require.New(t)
+	ctx := context.Background()
+
+	lc := &testPeerLightClient{}
+	h := &runtimeHostHandler{
+		env: &testEnvironment{lc: lc},
+	}
+
+	rsp, err := h.handleHostFetchWitnessBlock(ctx, &protocol.HostFetchWitnessBlockRequest{Height: 42})
+	require.NoError(err, "witness block")
+	require.EqualValues(42, lc.height)
+	require.EqualValues(42, rsp.Block.Height)
+
+	// Light clients that cannot query peers directly should fail.
+	h.env = &testEnvironment{lc: &testLightClient{}}
+	_, err = h.handleHostFetchWitnessBlock(ctx, &protocol.HostFetchWitnessBlockRequest{Height: 42})
+	require.Error(err, "witness blocks should not be supported")
+}
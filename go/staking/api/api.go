@@ -1257,6 +1257,10 @@ type ConsensusParameters struct { // nolint: maligned
 	// and ReclaimEscrow via runtime messages.
 	AllowEscrowMessages bool `json:"allow_escrow_messages,omitempty"`
 
+	// AllowAccountMessages can be used to allow runtimes to perform Allow,
+	// Burn and AmendCommissionSchedule via runtime messages.
+	AllowAccountMessages bool `json:"allow_account_messages,omitempty"`
+
 	// MaxAllowances is the maximum number of allowances an account can have. Zero means disabled.
 	MaxAllowances uint32 `json:"max_allowances,omitempty"`
 
@@ -1307,6 +1311,9 @@ type ConsensusParameterChanges struct {
 	// AllowEscrowMessages is the new allow escrow messages flag.
 	AllowEscrowMessages *bool `json:"allow_escrow_messages,omitempty"`
 
+	// AllowAccountMessages is the new allow account messages flag.
+	AllowAccountMessages *bool `json:"allow_account_messages,omitempty"`
+
 	// MaxAllowances is the new maximum number of allowances.
 	MaxAllowances *uint32 `json:"max_allowances,omitempty"`
 
@@ -1355,6 +1362,9 @@ func (c *ConsensusParameterChanges) Apply(params *ConsensusParameters) error {
 	if c.AllowEscrowMessages != nil {
 		params.AllowEscrowMessages = *c.AllowEscrowMessages
 	}
+	if c.AllowAccountMessages != nil {
+		params.AllowAccountMessages = *c.AllowAccountMessages
+	}
 	if c.MaxAllowances != nil {
 		params.MaxAllowances = *c.MaxAllowances
 	}
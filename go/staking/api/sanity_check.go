@@ -79,6 +79,7 @@ func (c *ConsensusParameterChanges) SanityCheck() error {
 		c.DisableTransfers == nil &&
 		c.DisableDelegation == nil &&
 		c.AllowEscrowMessages == nil &&
+		c.AllowAccountMessages == nil &&
 		c.MaxAllowances == nil &&
 		c.FeeSplitWeightPropose == nil &&
 		c.FeeSplitWeightVote == nil &&
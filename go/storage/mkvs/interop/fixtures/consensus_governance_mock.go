@@ -0,0 +1,56 @@
+package fixtures
+
+import (
+	"context"
+	"fmt"
+	"time"
+
+	"github.com/oasisprotocol/oasis-core/go/common"
+	"github.com/oasisprotocol/oasis-core/go/consensus/cometbft/api"
+	governanceInterop "github.com/oasisprotocol/oasis-core/go/consensus/cometbft/apps/governance/state/interop"
+	storage "github.com/oasisprotocol/oasis-core/go/storage/api"
+	"github.com/oasisprotocol/oasis-core/go/storage/mkvs"
+	db "github.com/oasisprotocol/oasis-core/go/storage/mkvs/db/api"
+	"github.com/oasisprotocol/oasis-core/go/storage/mkvs/node"
+)
+
+const consensusGovernanceMockName = "consensus_governance_mock"
+
+var consensusGovernanceMockFixture = consensusGovernanceMock{}
+
+// consensusGovernanceMock is kept separate from consensusMock so that the governance state
+// does not change the root hash that the other consensus state interop tests depend on.
+type consensusGovernanceMock struct{}
+
+func (c *consensusGovernanceMock) Name() string {
+	return consensusGovernanceMockName
+}
+
+func (c *consensusGovernanceMock) Populate(ctx context.Context, ndb db.NodeDB) (*node.Root, error) {
+	var err error
+	testRoot := storage.Root{
+		Type:    storage.RootTypeState,
+		Version: 1,
+	}
+
+	// Use a dummy ABCI InitChain context, as SetConsensusParameters methods require a specific ABCI context.
+	ctx = api.NewContext(ctx, api.ContextInitChain, time.Time{}, nil, nil, nil, 0, nil, 0)
+
+	mkvsTree := mkvs.New(nil, ndb, node.RootTypeState, mkvs.WithoutWriteLog())
+	if err = governanceInterop.InitializeTestGovernanceState(ctx, mkvsTree); err != nil {
+		return nil, fmt.Errorf("consensus-governance-mock: failed to initialize governance state: %w", err)
+	}
+	_, testRoot.Hash, err = mkvsTree.Commit(ctx, common.Namespace{}, 1)
+	if err != nil {
+		return nil, fmt.Errorf("consensus-governance-mock: failed to commit tree: %w", err)
+	}
+	if err = ndb.Finalize([]node.Root{testRoot}); err != nil {
+		return nil, fmt.Errorf("consensus-governance-mock: failed to finalize test root: %w", err)
+	}
+
+	return &testRoot, nil
+}
+
+func init() {
+	Register(&consensusGovernanceMockFixture)
+}
@@ -0,0 +1,51 @@
+package fixtures
+
+import (
+	"context"
+	"fmt"
+
+	"github.com/oasisprotocol/oasis-core/go/common"
+	schedulerInterop "github.com/oasisprotocol/oasis-core/go/consensus/cometbft/apps/scheduler/state/interop"
+	storage "github.com/oasisprotocol/oasis-core/go/storage/api"
+	"github.com/oasisprotocol/oasis-core/go/storage/mkvs"
+	db "github.com/oasisprotocol/oasis-core/go/storage/mkvs/db/api"
+	"github.com/oasisprotocol/oasis-core/go/storage/mkvs/node"
+)
+
+const consensusSchedulerMockName = "consensus_scheduler_mock"
+
+var consensusSchedulerMockFixture = consensusSchedulerMock{}
+
+// consensusSchedulerMock is kept separate from consensusMock so that the scheduler state
+// does not change the root hash that the other consensus state interop tests depend on.
+type consensusSchedulerMock struct{}
+
+func (c *consensusSchedulerMock) Name() string {
+	return consensusSchedulerMockName
+}
+
+func (c *consensusSchedulerMock) Populate(ctx context.Context, ndb db.NodeDB) (*node.Root, error) {
+	var err error
+	testRoot := storage.Root{
+		Type:    storage.RootTypeState,
+		Version: 1,
+	}
+
+	mkvsTree := mkvs.New(nil, ndb, node.RootTypeState, mkvs.WithoutWriteLog())
+	if err = schedulerInterop.InitializeTestSchedulerState(ctx, mkvsTree); err != nil {
+		return nil, fmt.Errorf("consensus-scheduler-mock: failed to initialize scheduler state: %w", err)
+	}
+	_, testRoot.Hash, err = mkvsTree.Commit(ctx, common.Namespace{}, 1)
+	if err != nil {
+		return nil, fmt.Errorf("consensus-scheduler-mock: failed to commit tree: %w", err)
+	}
+	if err = ndb.Finalize([]node.Root{testRoot}); err != nil {
+		return nil, fmt.Errorf("consensus-scheduler-mock: failed to finalize test root: %w", err)
+	}
+
+	return &testRoot, nil
+}
+
+func init() {
+	Register(&consensusSchedulerMockFixture)
+}
@@ -36,7 +36,11 @@ type KeyManagerClientWrapper struct {
 	nt           *nodeTracker
 	logger       *logging.Logger
 
-	lastPeerFeedback rpc.PeerFeedback
+	// peerFeedback contains the peer feedback instances of the last EnclaveRPC call handled by
+	// each node for which no feedback has been received yet.
+	peerFeedback map[signature.PublicKey]rpc.PeerFeedback
+	// lastNode is the node that handled the last EnclaveRPC call.
+	lastNode *signature.PublicKey
 }
 
 // Initialized returns a channel that gets closed when the client is initialized.
@@ -83,7 +87,8 @@ func (km *KeyManagerClientWrapper) SetKeyManagerID(id *common.Namespace) {
 		km.nt.Start()
 	}
 
-	km.lastPeerFeedback = nil
+	km.peerFeedback = make(map[signature.PublicKey]rpc.PeerFeedback)
+	km.lastNode = nil
 }
 
 // CallEnclave implements runtimeKeymanager.Client.
@@ -93,28 +98,35 @@ func (km *KeyManagerClientWrapper) CallEnclave(
 	nodes []signature.PublicKey,
 	kind enclaverpc.Kind,
 	pf *enclaverpc.PeerFeedback,
+	pfNode *signature.PublicKey,
 ) ([]byte, signature.PublicKey, error) {
 	var node signature.PublicKey
 
 	km.l.Lock()
 	cli := km.cli
-	lastPf := km.lastPeerFeedback
+	var lastPf rpc.PeerFeedback
+	if pf != nil {
+		// Feedback refers to the last call handled by the given node or, if no node is given,
+		// to the last call.
+		if pfNode == nil {
+			pfNode = km.lastNode
+		}
+		if pfNode != nil {
+			lastPf = km.peerFeedback[*pfNode]
+			delete(km.peerFeedback, *pfNode)
+		}
+	}
 	km.l.Unlock()
 
 	if cli == nil {
 		return nil, node, fmt.Errorf("key manager not available")
 	}
 
-	// Propagate peer feedback on the last EnclaveRPC call to guide routing decision.
+	// Propagate peer feedback on the previous EnclaveRPC call to guide routing decision.
 	if lastPf != nil {
-		// If no feedback has been provided by the runtime, treat previous call as success.
-		if pf == nil {
-			pfv := enclaverpc.PeerFeedbackSuccess
-			pf = &pfv
-		}
-
 		km.logger.Debug("received peer feedback from runtime",
 			"peer_feedback", *pf,
+			"node", *pfNode,
 		)
 
 		switch *pf {
@@ -153,7 +165,12 @@ func (km *KeyManagerClientWrapper) CallEnclave(
 	// Store peer feedback instance that we can use.
 	km.l.Lock()
 	if km.cli == cli { // Key manager could get updated while we are doing the call.
-		km.lastPeerFeedback = nextPf
+		// If no feedback has been provided by the runtime, treat previous call as success.
+		if prevPf, ok := km.peerFeedback[node]; ok {
+			prevPf.RecordSuccess()
+		}
+		km.peerFeedback[node] = nextPf
+		km.lastNode = &node
 	}
 	km.l.Unlock()
 
@@ -167,6 +184,7 @@ func NewKeyManagerClientWrapper(p2p p2p.Service, consensus consensus.Backend, ch
 		consensus:    consensus,
 		chainContext: chainContext,
 		logger:       logger,
+		peerFeedback: make(map[signature.PublicKey]rpc.PeerFeedback),
 	}
 }
 
@@ -222,7 +222,7 @@ impl Churp {
     /// needs to be kept secret and generated only for authorized nodes.
     pub fn share_reduction_switch_point(
         &self,
-        _ctx: &RpcContext,
+        ctx: &RpcContext,
         req: &QueryRequest,
     ) -> Result<Vec<u8>> {
         let status = self.verify_next_handoff(req.id, req.runtime_id, req.epoch)?;
@@ -232,15 +232,7 @@ impl Churp {
             return Err(Error::InvalidHandoff.into());
         }
 
-        let node_id = req.node_id.as_ref().ok_or(Error::NotAuthenticated)?;
-        if !status.applications.contains_key(node_id) {
-            return Err(Error::NotInCommittee.into());
-        }
-        #[cfg(target_env = "sgx")]
-        {
-            self.verify_node_id(_ctx, node_id)?;
-            self.verify_enclave(_ctx, &status.policy)?;
-        }
+        let node_id = self.authorize_node(ctx, req, &status)?;
 
         match status.suite_id {
             SuiteId::NistP384Sha3_384 => {
@@ -287,7 +279,7 @@ impl Churp {
     /// needs to be kept secret and generated only for authorized nodes.
     pub fn share_distribution_switch_point(
         &self,
-        _ctx: &RpcContext,
+        ctx: &RpcContext,
         req: &QueryRequest,
     ) -> Result<Vec<u8>> {
         let status = self.verify_next_handoff(req.id, req.runtime_id, req.epoch)?;
@@ -297,15 +289,7 @@ impl Churp {
             return Err(Error::InvalidHandoff.into());
         }
 
-        let node_id = req.node_id.as_ref().ok_or(Error::NotAuthenticated)?;
-        if !status.applications.contains_key(node_id) {
-            return Err(Error::NotInCommittee.into());
-        }
-        #[cfg(target_env = "sgx")]
-        {
-            self.verify_node_id(_ctx, node_id)?;
-            self.verify_enclave(_ctx, &status.policy)?;
-        }
+        let node_id = self.authorize_node(ctx, req, &status)?;
 
         match status.suite_id {
             SuiteId::NistP384Sha3_384 => {
@@ -355,20 +339,12 @@ impl Churp {
     /// for authorized nodes.
     pub fn bivariate_share(
         &self,
-        _ctx: &RpcContext,
+        ctx: &RpcContext,
         req: &QueryRequest,
     ) -> Result<EncodedVerifiableSecretShare> {
         let status = self.verify_next_handoff(req.id, req.runtime_id, req.epoch)?;
 
-        let node_id = req.node_id.as_ref().ok_or(Error::NotAuthenticated)?;
-        if !status.applications.contains_key(node_id) {
-            return Err(Error::NotInCommittee.into());
-        }
-        #[cfg(target_env = "sgx")]
-        {
-            self.verify_node_id(_ctx, node_id)?;
-            self.verify_enclave(_ctx, &status.policy)?;
-        }
+        let node_id = self.authorize_node(ctx, req, &status)?;
 
         match status.suite_id {
             SuiteId::NistP384Sha3_384 => {
@@ -1232,6 +1208,40 @@ impl Churp {
         Ok(status)
     }
 
+    /// Returns the key manager runtime ID.
+    pub(super) fn runtime_id(&self) -> Namespace {
+        self.runtime_id
+    }
+
+    /// Authorizes the calling node to receive secret data derived for
+    /// the node given in the request and returns the node's ID.
+    ///
+    /// The node must be a member of the next committee and, in SGX builds,
+    /// the session must be established with the node's registered enclave
+    /// which is allowed to join the committee by the policy.
+    ///
+    /// The given status must be the one against which the handoff was
+    /// verified, so that the request is authorized and served using
+    /// the same view of the consensus layer.
+    fn authorize_node<'a>(
+        &self,
+        _ctx: &RpcContext,
+        req: &'a QueryRequest,
+        status: &Status,
+    ) -> Result<&'a PublicKey> {
+        let node_id = req.node_id.as_ref().ok_or(Error::NotAuthenticated)?;
+        if !status.applications.contains_key(node_id) {
+            return Err(Error::NotInCommittee.into());
+        }
+        #[cfg(target_env = "sgx")]
+        {
+            self.verify_node_id(_ctx, node_id)?;
+            self.verify_enclave(_ctx, &status.policy)?;
+        }
+
+        Ok(node_id)
+    }
+
     /// Verifies the node ID by comparing the session's runtime attestation
     /// key (RAK) with the one published in the consensus layer.
     #[cfg(target_env = "sgx")]
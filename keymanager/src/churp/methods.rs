@@ -1,5 +1,6 @@
 //! CHURP methods exported to remote clients via enclave RPC.
 use oasis_core_runtime::enclave_rpc::{
+    access::AccessPolicy as RpcAccessPolicy,
     dispatcher::{
         Handler as RpcHandler, Method as RpcMethod, MethodDescriptor as RpcMethodDescriptor,
     },
@@ -37,36 +38,48 @@ impl RpcHandler for Churp {
                 RpcMethodDescriptor {
                     name: METHOD_VERIFICATION_MATRIX.to_string(),
                     kind: RpcKind::InsecureQuery,
+                    access: RpcAccessPolicy::Any,
                 },
                 move |_ctx: &_, req: &_| self.verification_matrix(req),
-            ),
+            )
+            .with_schema("keymanager/churp.QueryRequest", "bytes"),
             /* Noise sessions */
             RpcMethod::new(
                 RpcMethodDescriptor {
                     name: METHOD_SHARE_REDUCTION_POINT.to_string(),
                     kind: RpcKind::NoiseSession,
+                    access: self.node_access_policy(),
                 },
                 move |ctx: &_, req: &_| self.share_reduction_switch_point(ctx, req),
-            ),
+            )
+            .with_schema("keymanager/churp.QueryRequest", "bytes"),
             RpcMethod::new(
                 RpcMethodDescriptor {
                     name: METHOD_SHARE_DISTRIBUTION_POINT.to_string(),
                     kind: RpcKind::NoiseSession,
+                    access: self.node_access_policy(),
                 },
                 move |ctx: &_, req: &_| self.share_distribution_switch_point(ctx, req),
-            ),
+            )
+            .with_schema("keymanager/churp.QueryRequest", "bytes"),
             RpcMethod::new(
                 RpcMethodDescriptor {
                     name: METHOD_BIVARIATE_SHARE.to_string(),
                     kind: RpcKind::NoiseSession,
+                    access: self.node_access_policy(),
                 },
                 move |ctx: &_, req: &_| self.bivariate_share(ctx, req),
+            )
+            .with_schema(
+                "keymanager/churp.QueryRequest",
+                "keymanager/churp.EncodedVerifiableSecretShare",
             ),
             /* Local queries */
             RpcMethod::new(
                 RpcMethodDescriptor {
                     name: METHOD_INIT.to_string(),
                     kind: RpcKind::LocalQuery,
+                    access: RpcAccessPolicy::LocalOnly,
                 },
                 move |_ctx: &_, req: &_| self.init(req),
             ),
@@ -74,6 +87,7 @@ impl RpcHandler for Churp {
                 RpcMethodDescriptor {
                     name: METHOD_SHARE_REDUCTION.to_string(),
                     kind: RpcKind::LocalQuery,
+                    access: RpcAccessPolicy::LocalOnly,
                 },
                 move |_ctx: &_, req: &_| self.share_reduction(req),
             ),
@@ -81,6 +95,7 @@ impl RpcHandler for Churp {
                 RpcMethodDescriptor {
                     name: METHOD_SHARE_DISTRIBUTION.to_string(),
                     kind: RpcKind::LocalQuery,
+                    access: RpcAccessPolicy::LocalOnly,
                 },
                 move |_ctx: &_, req: &_| self.share_distribution(req),
             ),
@@ -88,6 +103,7 @@ impl RpcHandler for Churp {
                 RpcMethodDescriptor {
                     name: METHOD_PROACTIVIZATION.to_string(),
                     kind: RpcKind::LocalQuery,
+                    access: RpcAccessPolicy::LocalOnly,
                 },
                 move |_ctx: &_, req: &_| self.proactivization(req),
             ),
@@ -95,6 +111,7 @@ impl RpcHandler for Churp {
                 RpcMethodDescriptor {
                     name: METHOD_CONFIRM.to_string(),
                     kind: RpcKind::LocalQuery,
+                    access: RpcAccessPolicy::LocalOnly,
                 },
                 move |_ctx: &_, req: &_| self.confirmation(req),
             ),
@@ -102,9 +119,21 @@ impl RpcHandler for Churp {
                 RpcMethodDescriptor {
                     name: METHOD_FINALIZE.to_string(),
                     kind: RpcKind::LocalQuery,
+                    access: RpcAccessPolicy::LocalOnly,
                 },
                 move |_ctx: &_, req: &_| self.finalize(req),
             ),
         ]
     }
 }
+
+impl Churp {
+    /// Returns the access policy of methods which reveal secret data to
+    /// the calling node.
+    ///
+    /// The calling node itself is authorized by the methods, using the same
+    /// status against which the handoff is verified.
+    fn node_access_policy(&'static self) -> RpcAccessPolicy {
+        RpcAccessPolicy::Runtimes(std::iter::once(self.runtime_id()).collect())
+    }
+}
@@ -20,7 +20,7 @@ use oasis_core_runtime::{
         state::{beacon::ImmutableState as BeaconState, keymanager::Status as KeyManagerStatus},
         verifier::Verifier,
     },
-    enclave_rpc::{client::RpcClient, session},
+    enclave_rpc::{client::RpcClient, resumption::TicketCache, selector::LatencyWeighted, session},
     identity::Identity,
     protocol::Protocol,
 };
@@ -103,16 +103,19 @@ impl RemoteClient {
     ) -> Self {
         Self::new(
             runtime_id,
-            RpcClient::new_runtime(
+            RpcClient::new_runtime_with_peer_selector(
                 session::Builder::default()
                     .remote_enclaves(enclaves)
                     .quote_policy(policy)
                     .local_identity(identity)
                     .consensus_verifier(Some(consensus_verifier.clone()))
-                    .remote_runtime_id(km_runtime_id),
+                    .remote_runtime_id(km_runtime_id)
+                    .resumption(Some(Arc::new(TicketCache::default()))),
                 protocol,
                 KEY_MANAGER_ENDPOINT,
                 nodes,
+                // Avoid key manager nodes that are slow to respond.
+                Box::new(LatencyWeighted),
             ),
             consensus_verifier,
             keys_cache_sizes,
@@ -1,17 +1,32 @@
 use futures::executor::block_on;
 use oasis_core_runtime::{
     dispatcher::{Initializer, PostInitState, PreInitState},
-    enclave_rpc::dispatcher::Handler,
+    enclave_rpc::{
+        dispatcher::Handler,
+        ratelimit::{Limit, Limits},
+    },
     host::Host,
 };
 
 use crate::{
-    churp::Churp,
+    api::METHOD_GET_OR_CREATE_KEYS,
+    churp::{Churp, METHOD_BIVARIATE_SHARE},
     policy::{set_trusted_signers, TrustedSigners},
 };
 
 use super::secrets::Secrets;
 
+/// Request rate limits of the key manager enclave.
+///
+/// Key derivation and bivariate share computation are the most expensive requests so they get
+/// their own, stricter limits on top of the overall per-peer limit.
+fn rate_limits() -> Limits {
+    Limits::default()
+        .per_peer(Limit::new(500, 100.0))
+        .per_method(METHOD_GET_OR_CREATE_KEYS, Limit::new(200, 50.0))
+        .per_method(METHOD_BIVARIATE_SHARE, Limit::new(10, 1.0))
+}
+
 /// Initialize a keymanager with trusted signers.
 pub fn new_keymanager(signers: TrustedSigners) -> Box<dyn Initializer> {
     // Initializer.
@@ -39,6 +54,7 @@ pub fn new_keymanager(signers: TrustedSigners) -> Box<dyn Initializer> {
 
         state.rpc_dispatcher.add_methods(secrets.methods());
         state.rpc_dispatcher.add_methods(churp.methods());
+        state.rpc_demux.set_rate_limits(rate_limits());
 
         // No transaction dispatcher.
         PostInitState::default()
@@ -46,3 +62,175 @@ pub fn new_keymanager(signers: TrustedSigners) -> Box<dyn Initializer> {
 
     Box::new(init)
 }
+
+#[cfg(test)]
+mod test {
+    use std::{
+        io::{Read, Write},
+        os::unix::net::{UnixListener, UnixStream},
+    };
+
+    use oasis_core_runtime::{
+        common::{crypto::signature::PublicKey, namespace::Namespace},
+        config::Config,
+        consensus::tendermint,
+        enclave_rpc::types::{
+            Body as RpcBody, DescribeResponse, Kind as RpcKind, Request as RpcRequest,
+            Response as RpcResponse, METHOD_DESCRIBE,
+        },
+        start_runtime,
+        types::{Body, Message, MessageType, RuntimeInfoRequest},
+    };
+
+    use crate::{
+        api::{
+            LOCAL_METHOD_INIT, METHOD_GET_OR_CREATE_EPHEMERAL_KEYS, METHOD_GET_OR_CREATE_KEYS,
+            METHOD_GET_PUBLIC_EPHEMERAL_KEY, METHOD_GET_PUBLIC_KEY,
+            METHOD_REPLICATE_EPHEMERAL_SECRET, METHOD_REPLICATE_MASTER_SECRET,
+        },
+        churp::{
+            METHOD_BIVARIATE_SHARE, METHOD_INIT, METHOD_SHARE_DISTRIBUTION_POINT,
+            METHOD_SHARE_REDUCTION_POINT, METHOD_VERIFICATION_MATRIX,
+        },
+        policy::TrustedSigners,
+    };
+
+    use super::new_keymanager;
+
+    fn write_message(stream: &mut UnixStream, message: Message) {
+        let buffer = cbor::to_vec(message);
+        stream
+            .write_all(&(buffer.len() as u32).to_be_bytes())
+            .unwrap();
+        stream.write_all(&buffer).unwrap();
+    }
+
+    fn read_message(stream: &mut UnixStream) -> Message {
+        let mut length = [0u8; 4];
+        stream.read_exact(&mut length).unwrap();
+        let mut buffer = vec![0u8; u32::from_be_bytes(length) as usize];
+        stream.read_exact(&mut buffer).unwrap();
+        cbor::from_slice(&buffer).unwrap()
+    }
+
+    /// Send a request to the runtime and wait for its response, serving any requests that
+    /// the runtime makes to the host in the meantime.
+    fn call(stream: &mut UnixStream, id: u64, body: Body) -> Body {
+        write_message(
+            stream,
+            Message {
+                id,
+                message_type: MessageType::Request,
+                body,
+                ..Default::default()
+            },
+        );
+
+        loop {
+            let message = read_message(stream);
+            match message.message_type {
+                MessageType::Response if message.id == id => return message.body,
+                MessageType::Request => {
+                    let body = match message.body {
+                        Body::HostIdentityRequest {} => Body::HostIdentityResponse {
+                            node_id: PublicKey::default(),
+                        },
+                        body => panic!("unexpected host request: {:?}", body),
+                    };
+                    write_message(
+                        stream,
+                        Message {
+                            id: message.id,
+                            message_type: MessageType::Response,
+                            body,
+                            ..Default::default()
+                        },
+                    );
+                }
+                _ => panic!("unexpected message: {:?}", message),
+            }
+        }
+    }
+
+    #[test]
+    fn test_describe() {
+        // Start the key manager runtime connected to a mock host.
+        let path = std::env::temp_dir().join(format!("km-describe-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = UnixListener::bind(&path).unwrap();
+        std::env::set_var("OASIS_WORKER_HOST", &path);
+        std::thread::spawn(|| {
+            start_runtime(new_keymanager(TrustedSigners::default()), Config::default())
+        });
+        let (mut stream, _) = listener.accept().unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        let rsp = call(
+            &mut stream,
+            1,
+            Body::RuntimeInfoRequest(RuntimeInfoRequest {
+                runtime_id: Namespace::default(),
+                consensus_backend: tendermint::BACKEND_NAME.to_string(),
+                consensus_protocol_version: Default::default(),
+                consensus_chain_context: "test".to_string(),
+                local_config: Default::default(),
+            }),
+        );
+        assert!(matches!(rsp, Body::RuntimeInfoResponse(_)), "{:?}", rsp);
+
+        let request = RpcRequest {
+            method: METHOD_DESCRIBE.to_string(),
+            args: cbor::Value::Simple(cbor::SimpleValue::NullValue),
+        };
+        let rsp = call(
+            &mut stream,
+            2,
+            Body::RuntimeRPCCallRequest {
+                request: cbor::to_vec(request),
+                kind: RpcKind::InsecureQuery,
+                peer_id: vec![],
+            },
+        );
+        let rsp: RpcResponse = match rsp {
+            Body::RuntimeRPCCallResponse { response } => cbor::from_slice(&response).unwrap(),
+            rsp => panic!("unexpected response: {:?}", rsp),
+        };
+        let rsp: DescribeResponse = match rsp.body {
+            RpcBody::Success(value) => cbor::from_value(value).unwrap(),
+            body => panic!("unexpected response: {:?}", body),
+        };
+
+        // All methods exposed to remote clients should be described with their schemas.
+        for name in [
+            METHOD_GET_OR_CREATE_KEYS,
+            METHOD_GET_PUBLIC_KEY,
+            METHOD_GET_OR_CREATE_EPHEMERAL_KEYS,
+            METHOD_GET_PUBLIC_EPHEMERAL_KEY,
+            METHOD_REPLICATE_MASTER_SECRET,
+            METHOD_REPLICATE_EPHEMERAL_SECRET,
+            METHOD_VERIFICATION_MATRIX,
+            METHOD_SHARE_REDUCTION_POINT,
+            METHOD_SHARE_DISTRIBUTION_POINT,
+            METHOD_BIVARIATE_SHARE,
+        ]
+        .iter()
+        {
+            let method = rsp
+                .methods
+                .iter()
+                .find(|m| m.name == *name)
+                .unwrap_or_else(|| panic!("method {} should be described", name));
+            assert!(!method.request.is_empty(), "{} request schema", name);
+            assert!(!method.response.is_empty(), "{} response schema", name);
+        }
+
+        // Local methods should not be described.
+        for name in [LOCAL_METHOD_INIT, METHOD_INIT].iter() {
+            assert!(
+                rsp.methods.iter().all(|m| m.name != *name),
+                "local method {} should not be described",
+                name
+            );
+        }
+    }
+}
@@ -34,6 +34,7 @@ use oasis_core_runtime::{
         verifier::Verifier,
     },
     enclave_rpc::{
+        access::AccessPolicy as RpcAccessPolicy,
         dispatcher::{Handler, Method as RpcMethod, MethodDescriptor as RpcMethodDescriptor},
         types::Kind as RpcKind,
         Context as RpcContext,
@@ -140,12 +141,8 @@ impl Secrets {
     }
 
     /// See `Kdf::get_or_create_keys`.
-    pub fn get_or_create_keys(
-        &self,
-        ctx: &RpcContext,
-        req: &LongTermKeyRequest,
-    ) -> Result<KeyPair> {
-        Self::authorize_private_key_generation(ctx, &req.runtime_id)?;
+    pub fn get_or_create_keys(&self, req: &LongTermKeyRequest) -> Result<KeyPair> {
+        // Authorization is checked by the access policy of the method.
         self.validate_height_freshness(req.height)?;
 
         Kdf::global().get_or_create_longterm_keys(
@@ -173,12 +170,8 @@ impl Secrets {
     }
 
     /// See `Kdf::get_or_create_ephemeral_keys`.
-    pub fn get_or_create_ephemeral_keys(
-        &self,
-        ctx: &RpcContext,
-        req: &EphemeralKeyRequest,
-    ) -> Result<KeyPair> {
-        Self::authorize_private_key_generation(ctx, &req.runtime_id)?;
+    pub fn get_or_create_ephemeral_keys(&self, req: &EphemeralKeyRequest) -> Result<KeyPair> {
+        // Authorization is checked by the access policy of the method.
         self.validate_ephemeral_key_epoch(req.epoch)?;
         self.validate_height_freshness(req.height)?;
 
@@ -201,10 +194,8 @@ impl Secrets {
     /// See `Kdf::replicate_master_secret`.
     pub fn replicate_master_secret(
         &self,
-        ctx: &RpcContext,
         req: &ReplicateMasterSecretRequest,
     ) -> Result<ReplicateMasterSecretResponse> {
-        Self::authorize_secret_replication(ctx)?;
         self.validate_height_freshness(req.height)?;
 
         let master_secret = Kdf::global().replicate_master_secret(&self.storage, req.generation)?;
@@ -219,10 +210,8 @@ impl Secrets {
     /// See `Kdf::replicate_ephemeral_secret`.
     pub fn replicate_ephemeral_secret(
         &self,
-        ctx: &RpcContext,
         req: &ReplicateEphemeralSecretRequest,
     ) -> Result<ReplicateEphemeralSecretResponse> {
-        Self::authorize_secret_replication(ctx)?;
         self.validate_height_freshness(req.height)?;
 
         let ephemeral_secret = Kdf::global().replicate_ephemeral_secret(req.epoch)?;
@@ -667,49 +656,78 @@ impl Handler for Secrets {
                 RpcMethodDescriptor {
                     name: METHOD_GET_OR_CREATE_KEYS.to_string(),
                     kind: RpcKind::NoiseSession,
+                    access: RpcAccessPolicy::request(|ctx, req: &LongTermKeyRequest| {
+                        Self::authorize_private_key_generation(ctx, &req.runtime_id)
+                    }),
                 },
-                move |ctx: &_, req: &_| self.get_or_create_keys(ctx, req),
-            ),
+                move |_ctx: &_, req: &_| self.get_or_create_keys(req),
+            )
+            .with_schema("keymanager.LongTermKeyRequest", "keymanager.KeyPair"),
             RpcMethod::new(
                 RpcMethodDescriptor {
                     name: METHOD_GET_PUBLIC_KEY.to_string(),
                     kind: RpcKind::InsecureQuery,
+                    access: RpcAccessPolicy::Any,
                 },
                 move |_ctx: &_, req: &_| self.get_public_key(req),
+            )
+            .with_schema(
+                "keymanager.LongTermKeyRequest",
+                "keymanager.SignedPublicKey",
             ),
             RpcMethod::new(
                 RpcMethodDescriptor {
                     name: METHOD_GET_OR_CREATE_EPHEMERAL_KEYS.to_string(),
                     kind: RpcKind::NoiseSession,
+                    access: RpcAccessPolicy::request(|ctx, req: &EphemeralKeyRequest| {
+                        Self::authorize_private_key_generation(ctx, &req.runtime_id)
+                    }),
                 },
-                move |ctx: &_, req: &_| self.get_or_create_ephemeral_keys(ctx, req),
-            ),
+                move |_ctx: &_, req: &_| self.get_or_create_ephemeral_keys(req),
+            )
+            .with_schema("keymanager.EphemeralKeyRequest", "keymanager.KeyPair"),
             RpcMethod::new(
                 RpcMethodDescriptor {
                     name: METHOD_GET_PUBLIC_EPHEMERAL_KEY.to_string(),
                     kind: RpcKind::InsecureQuery,
+                    access: RpcAccessPolicy::Any,
                 },
                 move |_ctx: &_, req: &_| self.get_public_ephemeral_key(req),
+            )
+            .with_schema(
+                "keymanager.EphemeralKeyRequest",
+                "keymanager.SignedPublicKey",
             ),
             RpcMethod::new(
                 RpcMethodDescriptor {
                     name: METHOD_REPLICATE_MASTER_SECRET.to_string(),
                     kind: RpcKind::NoiseSession,
+                    access: RpcAccessPolicy::custom(Self::authorize_secret_replication),
                 },
-                move |ctx: &_, req: &_| self.replicate_master_secret(ctx, req),
+                move |_ctx: &_, req: &_| self.replicate_master_secret(req),
+            )
+            .with_schema(
+                "keymanager.ReplicateMasterSecretRequest",
+                "keymanager.ReplicateMasterSecretResponse",
             ),
             RpcMethod::new(
                 RpcMethodDescriptor {
                     name: METHOD_REPLICATE_EPHEMERAL_SECRET.to_string(),
                     kind: RpcKind::NoiseSession,
+                    access: RpcAccessPolicy::custom(Self::authorize_secret_replication),
                 },
-                move |ctx: &_, req: &_| self.replicate_ephemeral_secret(ctx, req),
+                move |_ctx: &_, req: &_| self.replicate_ephemeral_secret(req),
+            )
+            .with_schema(
+                "keymanager.ReplicateEphemeralSecretRequest",
+                "keymanager.ReplicateEphemeralSecretResponse",
             ),
             // Register local methods, for use by the node key manager component.
             RpcMethod::new(
                 RpcMethodDescriptor {
                     name: LOCAL_METHOD_INIT.to_string(),
                     kind: RpcKind::LocalQuery,
+                    access: RpcAccessPolicy::LocalOnly,
                 },
                 move |_ctx: &_, req: &_| self.init_kdf(req),
             ),
@@ -717,6 +735,7 @@ impl Handler for Secrets {
                 RpcMethodDescriptor {
                     name: LOCAL_METHOD_GENERATE_MASTER_SECRET.to_string(),
                     kind: RpcKind::LocalQuery,
+                    access: RpcAccessPolicy::LocalOnly,
                 },
                 move |_ctx: &_, req: &_| self.generate_master_secret(req),
             ),
@@ -724,6 +743,7 @@ impl Handler for Secrets {
                 RpcMethodDescriptor {
                     name: LOCAL_METHOD_GENERATE_EPHEMERAL_SECRET.to_string(),
                     kind: RpcKind::LocalQuery,
+                    access: RpcAccessPolicy::LocalOnly,
                 },
                 move |_ctx: &_, req: &_| self.generate_ephemeral_secret(req),
             ),
@@ -731,6 +751,7 @@ impl Handler for Secrets {
                 RpcMethodDescriptor {
                     name: LOCAL_METHOD_LOAD_MASTER_SECRET.to_string(),
                     kind: RpcKind::LocalQuery,
+                    access: RpcAccessPolicy::LocalOnly,
                 },
                 move |_ctx: &_, req: &_| self.load_master_secret(req),
             ),
@@ -738,6 +759,7 @@ impl Handler for Secrets {
                 RpcMethodDescriptor {
                     name: LOCAL_METHOD_LOAD_EPHEMERAL_SECRET.to_string(),
                     kind: RpcKind::LocalQuery,
+                    access: RpcAccessPolicy::LocalOnly,
                 },
                 move |_ctx: &_, req: &_| self.load_ephemeral_secret(req),
             ),
@@ -0,0 +1,215 @@
+//! Registered error codes.
+//!
+//! Errors that need to be sent across the wire are converted into the serializable
+//! [`types::Error`] which carries a module name and a code. This module makes sure that the codes
+//! are assigned in a structured way so that clients can reliably branch on them.
+// NOTE: This should be kept in sync with go/common/errors/errors.go.
+use crate::types;
+
+/// Module name used when the module is unknown.
+pub const UNKNOWN_MODULE: &str = "unknown";
+
+/// The reserved "no error" code.
+pub const CODE_NO_ERROR: u32 = 0;
+
+/// The code used for errors from an unknown module.
+pub const CODE_UNKNOWN_ERROR: u32 = 1;
+
+/// An error that has a registered module name and code.
+///
+/// Instead of implementing this trait manually, use the [`impl_error_codes!`] macro which also
+/// verifies (at compile time) that all codes are valid and unique within the module.
+///
+/// [`impl_error_codes!`]: crate::impl_error_codes
+pub trait ErrorCode: std::error::Error {
+    /// Name of the module emitting the error.
+    const MODULE: &'static str;
+
+    /// Error code. It must be unique within the module and must not be equal to the reserved
+    /// "no error" code.
+    fn code(&self) -> u32;
+
+    /// Convert the error into a serializable error.
+    fn to_serializable(&self) -> types::Error {
+        types::Error::new(Self::MODULE, self.code(), &self.to_string())
+    }
+
+    /// Whether the given serializable error has the same module and code as this error.
+    fn matches(&self, err: &types::Error) -> bool {
+        err.is(Self::MODULE, self.code())
+    }
+}
+
+/// Check that none of the given codes is reserved and that there are no duplicates.
+pub const fn validate_codes(codes: &[u32]) -> bool {
+    let mut i = 0;
+    while i < codes.len() {
+        if codes[i] == CODE_NO_ERROR {
+            return false;
+        }
+
+        let mut j = i + 1;
+        while j < codes.len() {
+            if codes[i] == codes[j] {
+                return false;
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Check that there are no duplicate module names.
+pub const fn validate_modules(modules: &[&str]) -> bool {
+    let mut i = 0;
+    while i < modules.len() {
+        let mut j = i + 1;
+        while j < modules.len() {
+            if str_eq(modules[i], modules[j]) {
+                return false;
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+    true
+}
+
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Register error codes for an error type.
+///
+/// This implements [`ErrorCode`] and the conversion into the serializable [`types::Error`]. Codes
+/// are checked at compile time to be non-zero and unique within the module. Multiple variants may
+/// share a code by combining them with `|`, which should only be used to retain codes that were
+/// already in use on the wire.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// impl_error_codes!(Error, "demux", {
+///     Error::MalformedPayload(_) => 1,
+///     Error::MalformedRequestMethod => 2,
+/// });
+/// ```
+#[macro_export]
+macro_rules! impl_error_codes {
+    ($name:ty, $module:expr, { $($($variant:pat_param)|+ => $code:expr),+ $(,)? }) => {
+        impl $crate::common::errors::ErrorCode for $name {
+            const MODULE: &'static str = $module;
+
+            fn code(&self) -> u32 {
+                match self {
+                    $($($variant)|+ => $code,)+
+                }
+            }
+        }
+
+        const _: () = assert!(
+            $crate::common::errors::validate_codes(&[$($code),+]),
+            concat!("invalid or duplicate error codes in module: ", $module),
+        );
+
+        impl From<$name> for $crate::types::Error {
+            fn from(e: $name) -> Self {
+                $crate::common::errors::ErrorCode::to_serializable(&e)
+            }
+        }
+    };
+}
+
+/// Register the modules of the given error types, making sure (at compile time) that no module name
+/// is used by more than one error type.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// register_error_modules!(demux::Error, dispatcher::DispatcherError);
+/// ```
+#[macro_export]
+macro_rules! register_error_modules {
+    ($($name:ty),+ $(,)?) => {
+        const _: () = assert!(
+            $crate::common::errors::validate_modules(&[
+                $(<$name as $crate::common::errors::ErrorCode>::MODULE),+
+            ]),
+            "error module registered more than once",
+        );
+    };
+}
+
+// All error modules of this crate. New error types must be added here.
+register_error_modules!(
+    crate::consensus::state::StateError,
+    crate::consensus::verifier::Error,
+    crate::dispatcher::DispatcherError,
+    crate::dispatcher::RequestError,
+    crate::enclave_rpc::access::AccessError,
+    crate::enclave_rpc::demux::Error,
+    crate::enclave_rpc::stream::StreamError,
+    crate::protocol::ProtocolError,
+);
+
+#[cfg(test)]
+mod test {
+    use thiserror::Error;
+
+    use super::*;
+
+    #[derive(Error, Debug)]
+    enum TestError {
+        #[error("first")]
+        First,
+        #[error("second: {0}")]
+        Second(String),
+        #[error("third")]
+        Third,
+    }
+
+    impl_error_codes!(TestError, "test", {
+        TestError::First => 1,
+        TestError::Second(_) | TestError::Third => 2,
+    });
+
+    #[test]
+    fn test_validate_codes() {
+        assert!(validate_codes(&[1, 2, 3]));
+        assert!(validate_codes(&[42]));
+        assert!(!validate_codes(&[1, 2, 1]));
+        assert!(!validate_codes(&[0, 1]));
+    }
+
+    #[test]
+    fn test_validate_modules() {
+        assert!(validate_modules(&["demux", "protocol", "rhp/dispatcher"]));
+        assert!(validate_modules(&["dispatcher", "rhp/dispatcher"]));
+        assert!(!validate_modules(&["demux", "protocol", "demux"]));
+    }
+
+    #[test]
+    fn test_error_codes() {
+        let err: types::Error = TestError::Second("foo".to_string()).into();
+        assert_eq!(err.module, "test");
+        assert_eq!(err.code, 2);
+        assert_eq!(err.message, "second: foo");
+
+        assert!(TestError::Second("bar".to_string()).matches(&err));
+        assert!(!TestError::First.matches(&err));
+        assert!(TestError::Third.matches(&err));
+    }
+}
@@ -1,15 +1,26 @@
 //! Logging subsystem for runtimes.
-use std::sync::{Mutex, Once};
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Write as _},
+    io::{self, Write},
+    str::FromStr,
+    sync::{Mutex, Once, RwLock},
+};
 
+use anyhow::{anyhow, Error};
 use lazy_static::lazy_static;
 use log::Level;
-use slog::{o, Drain};
+use slog::{o, Drain, Key, OwnedKVList, Record, Serializer, KV};
 
 lazy_static! {
-    static ref LOGGER: slog::Logger = slog::Logger::root(
-        Mutex::new(slog_json::Json::default(std::io::stderr())).map(slog::Fuse),
-        o!()
-    );
+    /// Root drain shared by all loggers.
+    static ref DRAIN: slog::Fuse<Mutex<OutputDrain>> = Mutex::new(OutputDrain::new()).fuse();
+
+    /// Current logging filter.
+    static ref FILTER: RwLock<Filter> = RwLock::new(Filter::default());
+
+    /// Current logging output format.
+    static ref FORMAT: RwLock<Format> = RwLock::new(Format::default());
 
     /// Initializes the global logger once.
     static ref INIT_GLOBAL_LOGGER: Once = Once::new();
@@ -18,15 +29,210 @@ lazy_static! {
     static ref GLOBAL_LOGGER_SCOPE_GUARD: Mutex<Option<slog_scope::GlobalLoggerGuard>> = Mutex::new(None);
 }
 
+/// Log output format.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    /// Structured JSON output, one object per line.
+    #[default]
+    Json,
+    /// Human-readable plaintext output, intended for local development.
+    Plain,
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "plain" | "text" => Ok(Self::Plain),
+            _ => Err(anyhow!("unsupported log format: {}", s)),
+        }
+    }
+}
+
+/// Log level filter, optionally configured per module.
+///
+/// Module filters apply to the given module and all of its submodules (e.g. a filter for
+/// `runtime/storage` also applies to `runtime/storage/mkvs`), with the most specific filter
+/// taking precedence.
+///
+/// Note that levels above the statically configured maximum level (see the `debug-logging`
+/// feature) are always discarded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Filter {
+    /// Default level for modules without a specific filter.
+    pub default: Level,
+    /// Per-module levels.
+    pub modules: BTreeMap<String, Level>,
+}
+
+impl Default for Filter {
+    fn default() -> Self {
+        Self {
+            default: Level::Trace,
+            modules: BTreeMap::new(),
+        }
+    }
+}
+
+impl Filter {
+    /// Create a new filter with the given default level.
+    pub fn new(default: Level) -> Self {
+        Self {
+            default,
+            modules: BTreeMap::new(),
+        }
+    }
+
+    /// Set the level for the given module and its submodules.
+    pub fn module(mut self, module: &str, level: Level) -> Self {
+        self.modules.insert(module.to_owned(), level);
+        self
+    }
+
+    /// Level that applies to the given module.
+    pub fn level_for(&self, module: &str) -> Level {
+        self.modules
+            .iter()
+            .filter(|(prefix, _)| {
+                module == prefix.as_str()
+                    || (module.starts_with(prefix.as_str())
+                        && module[prefix.len()..].starts_with('/'))
+            })
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, level)| *level)
+            .unwrap_or(self.default)
+    }
+}
+
+impl FromStr for Filter {
+    type Err = Error;
+
+    /// Parse a filter of the form `info,runtime/storage=debug,runtime/protocol=warn` where the
+    /// optional bare level specifies the default level.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut filter = Self::default();
+        for directive in s.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            match directive.split_once('=') {
+                Some((module, level)) => {
+                    let level = Level::from_str(level.trim())
+                        .map_err(|_| anyhow!("malformed log level: {}", level))?;
+                    filter.modules.insert(module.trim().to_owned(), level);
+                }
+                None => {
+                    filter.default = Level::from_str(directive)
+                        .map_err(|_| anyhow!("malformed log level: {}", directive))?;
+                }
+            }
+        }
+        Ok(filter)
+    }
+}
+
+/// Convert a `log` level to an `slog` level.
+fn to_slog_level(level: Level) -> slog::Level {
+    match level {
+        Level::Error => slog::Level::Error,
+        Level::Warn => slog::Level::Warning,
+        Level::Info => slog::Level::Info,
+        Level::Debug => slog::Level::Debug,
+        Level::Trace => slog::Level::Trace,
+    }
+}
+
+/// Drain that filters records based on the configured level for a given module.
+struct ModuleFilter {
+    module: &'static str,
+}
+
+impl Drain for ModuleFilter {
+    type Ok = ();
+    type Err = slog::Never;
+
+    fn log(&self, record: &Record<'_>, values: &OwnedKVList) -> Result<Self::Ok, Self::Err> {
+        let level = FILTER.read().unwrap().level_for(self.module);
+        if record.level().is_at_least(to_slog_level(level)) {
+            DRAIN.log(record, values)?;
+        }
+        Ok(())
+    }
+}
+
+/// Drain that writes records to stderr in the configured format.
+struct OutputDrain {
+    json: slog_json::Json<io::Stderr>,
+}
+
+impl OutputDrain {
+    fn new() -> Self {
+        Self {
+            json: slog_json::Json::default(io::stderr()),
+        }
+    }
+}
+
+impl Drain for OutputDrain {
+    type Ok = ();
+    type Err = io::Error;
+
+    fn log(&self, record: &Record<'_>, values: &OwnedKVList) -> Result<Self::Ok, Self::Err> {
+        let format = *FORMAT.read().unwrap();
+        match format {
+            Format::Json => self.json.log(record, values),
+            Format::Plain => {
+                let line = format_plain(record, values)?;
+                io::stderr().write_all(line.as_bytes())
+            }
+        }
+    }
+}
+
+/// Serializer that emits key-value pairs as ` key=value`.
+struct PlainSerializer<'a>(&'a mut String);
+
+impl Serializer for PlainSerializer<'_> {
+    fn emit_arguments(&mut self, key: Key, val: &fmt::Arguments<'_>) -> slog::Result {
+        write!(self.0, " {key}={val}")?;
+        Ok(())
+    }
+}
+
+/// Format a record as a single human-readable line.
+fn format_plain(record: &Record<'_>, values: &OwnedKVList) -> io::Result<String> {
+    let mut line = format!(
+        "{} {} {}",
+        chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ"),
+        record.level().as_short_str(),
+        record.msg(),
+    );
+    let mut serializer = PlainSerializer(&mut line);
+    record.kv().serialize(record, &mut serializer)?;
+    values.serialize(record, &mut serializer)?;
+    line.push('\n');
+
+    Ok(line)
+}
+
 /// Get the logger.
 pub fn get_logger(module: &'static str) -> slog::Logger {
-    LOGGER.new(o!("module" => module))
+    slog::Logger::root(ModuleFilter { module }, o!("module" => module))
+}
+
+/// Configure the log level filter.
+pub fn set_filter(filter: Filter) {
+    *FILTER.write().unwrap() = filter;
+}
+
+/// Configure the log output format.
+pub fn set_format(format: Format) {
+    *FORMAT.write().unwrap() = format;
 }
 
 /// Initialize the global slog_stdlog adapter to allow logging with the log crate (instead of slog).
 pub fn init_logger(level: Level) {
     INIT_GLOBAL_LOGGER.call_once(|| {
-        let global_logger = LOGGER.new(o!("module" => "global"));
+        let global_logger = get_logger("global");
         GLOBAL_LOGGER_SCOPE_GUARD
             .lock()
             .unwrap()
@@ -34,3 +240,68 @@ pub fn init_logger(level: Level) {
         slog_stdlog::init_with_level(level).unwrap();
     });
 }
+
+#[cfg(test)]
+mod test {
+    use slog::{info, o};
+
+    use super::*;
+
+    #[test]
+    fn test_filter_parse() {
+        let filter: Filter = "info,runtime/storage=debug, runtime/protocol = warn"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            filter,
+            Filter::new(Level::Info)
+                .module("runtime/storage", Level::Debug)
+                .module("runtime/protocol", Level::Warn)
+        );
+
+        let filter: Filter = "runtime=error".parse().unwrap();
+        assert_eq!(filter, Filter::default().module("runtime", Level::Error));
+
+        assert!("runtime=foo".parse::<Filter>().is_err());
+        assert!("foo".parse::<Filter>().is_err());
+    }
+
+    #[test]
+    fn test_filter_level_for() {
+        let filter = Filter::new(Level::Info)
+            .module("runtime", Level::Warn)
+            .module("runtime/storage", Level::Debug);
+
+        assert_eq!(filter.level_for("runtime"), Level::Warn);
+        assert_eq!(filter.level_for("runtime/protocol"), Level::Warn);
+        assert_eq!(filter.level_for("runtime/storage"), Level::Debug);
+        assert_eq!(filter.level_for("runtime/storage/mkvs"), Level::Debug);
+        assert_eq!(filter.level_for("runtimes"), Level::Info);
+        assert_eq!(filter.level_for("consensus/cometbft/verifier"), Level::Info);
+    }
+
+    #[test]
+    fn test_format_plain() {
+        // Drain that captures the formatted lines.
+        struct Capture(Mutex<Vec<String>>);
+        impl Drain for Capture {
+            type Ok = ();
+            type Err = slog::Never;
+
+            fn log(&self, record: &Record<'_>, values: &OwnedKVList) -> Result<(), slog::Never> {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push(format_plain(record, values).unwrap());
+                Ok(())
+            }
+        }
+        let capture = std::sync::Arc::new(Capture(Mutex::new(vec![])));
+        let logger = slog::Logger::root(capture.clone(), o!("module" => "test"));
+        info!(logger, "hello world"; "answer" => 42);
+
+        let lines = capture.0.lock().unwrap();
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].ends_with(" INFO hello world answer=42 module=test\n"));
+    }
+}
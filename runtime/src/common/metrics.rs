@@ -0,0 +1,355 @@
+//! Metrics subsystem for runtimes.
+//!
+//! Metrics are collected in a process-wide registry and can be scraped by the host via the
+//! `RuntimeMetricsRequest` runtime host protocol message.
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicI64, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use lazy_static::lazy_static;
+
+lazy_static! {
+    static ref REGISTRY: Registry = Registry::default();
+}
+
+/// Default histogram buckets suitable for measuring durations in milliseconds.
+pub const DEFAULT_DURATION_BUCKETS: &[u64] = &[1, 5, 10, 25, 50, 100, 250, 500, 1000, 2500, 10000];
+
+/// A monotonically increasing counter.
+#[derive(Clone, Debug, Default)]
+pub struct Counter(Arc<AtomicU64>);
+
+impl Counter {
+    /// Increment the counter by one.
+    pub fn inc(&self) {
+        self.add(1);
+    }
+
+    /// Increment the counter by the given amount.
+    pub fn add(&self, v: u64) {
+        self.0.fetch_add(v, Ordering::Relaxed);
+    }
+
+    /// Current value of the counter.
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// A gauge which can arbitrarily go up and down.
+#[derive(Clone, Debug, Default)]
+pub struct Gauge(Arc<AtomicI64>);
+
+impl Gauge {
+    /// Set the gauge to the given value.
+    pub fn set(&self, v: i64) {
+        self.0.store(v, Ordering::Relaxed);
+    }
+
+    /// Increment the gauge by one.
+    pub fn inc(&self) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Decrement the gauge by one.
+    pub fn dec(&self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    /// Current value of the gauge.
+    pub fn get(&self) -> i64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+#[derive(Debug)]
+struct HistogramInner {
+    bounds: Vec<u64>,
+    counts: Vec<u64>,
+    count: u64,
+    sum: u64,
+}
+
+/// A histogram of observed values, grouped into buckets.
+#[derive(Clone, Debug)]
+pub struct Histogram(Arc<Mutex<HistogramInner>>);
+
+impl Histogram {
+    fn new(bounds: &[u64]) -> Self {
+        let mut bounds = bounds.to_vec();
+        bounds.sort_unstable();
+        bounds.dedup();
+
+        Self(Arc::new(Mutex::new(HistogramInner {
+            counts: vec![0; bounds.len()],
+            bounds,
+            count: 0,
+            sum: 0,
+        })))
+    }
+
+    /// Record an observed value.
+    pub fn observe(&self, v: u64) {
+        let mut inner = self.0.lock().unwrap();
+        if let Some(idx) = inner.bounds.iter().position(|b| v <= *b) {
+            inner.counts[idx] += 1;
+        }
+        inner.count += 1;
+        inner.sum = inner.sum.saturating_add(v);
+    }
+
+    fn value(&self) -> MetricValue {
+        let inner = self.0.lock().unwrap();
+        let mut cumulative = 0;
+        let buckets = inner
+            .bounds
+            .iter()
+            .zip(inner.counts.iter())
+            .map(|(bound, count)| {
+                cumulative += count;
+                HistogramBucket {
+                    upper_bound: *bound,
+                    count: cumulative,
+                }
+            })
+            .collect();
+
+        MetricValue::Histogram {
+            buckets,
+            count: inner.count,
+            sum: inner.sum,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Collector {
+    Counter(Counter),
+    Gauge(Gauge),
+    Histogram(Histogram),
+}
+
+impl Collector {
+    fn value(&self) -> MetricValue {
+        match self {
+            Collector::Counter(c) => MetricValue::Counter(c.get()),
+            Collector::Gauge(g) => MetricValue::Gauge(g.get()),
+            Collector::Histogram(h) => h.value(),
+        }
+    }
+}
+
+/// A cumulative histogram bucket.
+#[derive(Clone, Debug, Default, PartialEq, Eq, cbor::Encode, cbor::Decode)]
+pub struct HistogramBucket {
+    /// Inclusive upper bound of the bucket.
+    pub upper_bound: u64,
+    /// Number of observations less than or equal to the upper bound.
+    pub count: u64,
+}
+
+/// Value of a metric at the time of collection.
+#[derive(Clone, Debug, PartialEq, Eq, cbor::Encode, cbor::Decode)]
+pub enum MetricValue {
+    #[cbor(rename = "counter")]
+    Counter(u64),
+
+    #[cbor(rename = "gauge")]
+    Gauge(i64),
+
+    #[cbor(rename = "histogram")]
+    Histogram {
+        buckets: Vec<HistogramBucket>,
+        count: u64,
+        sum: u64,
+    },
+}
+
+impl Default for MetricValue {
+    fn default() -> Self {
+        Self::Counter(0)
+    }
+}
+
+/// A collected metric.
+#[derive(Clone, Debug, Default, PartialEq, Eq, cbor::Encode, cbor::Decode)]
+pub struct Metric {
+    /// Metric name.
+    pub name: String,
+    /// Human-readable metric description.
+    pub help: String,
+    /// Metric value.
+    pub value: MetricValue,
+}
+
+struct Entry {
+    help: &'static str,
+    collector: Collector,
+}
+
+/// A registry of metrics.
+#[derive(Default)]
+pub struct Registry {
+    metrics: Mutex<BTreeMap<&'static str, Entry>>,
+}
+
+impl Registry {
+    fn get_or_register(
+        &self,
+        name: &'static str,
+        help: &'static str,
+        create: impl FnOnce() -> Collector,
+    ) -> Collector {
+        let mut metrics = self.metrics.lock().unwrap();
+        let entry = metrics.entry(name).or_insert_with(|| Entry {
+            help,
+            collector: create(),
+        });
+        entry.collector.clone()
+    }
+
+    /// Get or register a counter with the given name.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic in case a metric of a different type has already been registered
+    /// under the same name.
+    pub fn counter(&self, name: &'static str, help: &'static str) -> Counter {
+        match self.get_or_register(name, help, || Collector::Counter(Counter::default())) {
+            Collector::Counter(c) => c,
+            _ => panic!("metric '{}' already registered with a different type", name),
+        }
+    }
+
+    /// Get or register a gauge with the given name.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic in case a metric of a different type has already been registered
+    /// under the same name.
+    pub fn gauge(&self, name: &'static str, help: &'static str) -> Gauge {
+        match self.get_or_register(name, help, || Collector::Gauge(Gauge::default())) {
+            Collector::Gauge(g) => g,
+            _ => panic!("metric '{}' already registered with a different type", name),
+        }
+    }
+
+    /// Get or register a histogram with the given name and bucket upper bounds.
+    ///
+    /// In case the histogram has already been registered, the passed buckets are ignored.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic in case a metric of a different type has already been registered
+    /// under the same name.
+    pub fn histogram(&self, name: &'static str, help: &'static str, buckets: &[u64]) -> Histogram {
+        match self.get_or_register(name, help, || Collector::Histogram(Histogram::new(buckets))) {
+            Collector::Histogram(h) => h,
+            _ => panic!("metric '{}' already registered with a different type", name),
+        }
+    }
+
+    /// Collect the current values of all registered metrics.
+    pub fn collect(&self) -> Vec<Metric> {
+        let metrics = self.metrics.lock().unwrap();
+        metrics
+            .iter()
+            .map(|(name, entry)| Metric {
+                name: name.to_string(),
+                help: entry.help.to_string(),
+                value: entry.collector.value(),
+            })
+            .collect()
+    }
+}
+
+/// Get or register a counter in the global registry.
+pub fn counter(name: &'static str, help: &'static str) -> Counter {
+    REGISTRY.counter(name, help)
+}
+
+/// Get or register a gauge in the global registry.
+pub fn gauge(name: &'static str, help: &'static str) -> Gauge {
+    REGISTRY.gauge(name, help)
+}
+
+/// Get or register a histogram in the global registry.
+pub fn histogram(name: &'static str, help: &'static str, buckets: &[u64]) -> Histogram {
+    REGISTRY.histogram(name, help, buckets)
+}
+
+/// Collect the current values of all metrics in the global registry.
+pub fn collect() -> Vec<Metric> {
+    REGISTRY.collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_registry() {
+        let registry = Registry::default();
+
+        let counter = registry.counter("test_counter", "Test counter.");
+        counter.inc();
+        counter.add(2);
+        // Registering again should return the same counter.
+        registry.counter("test_counter", "Test counter.").inc();
+
+        let gauge = registry.gauge("test_gauge", "Test gauge.");
+        gauge.set(10);
+        gauge.dec();
+
+        let histogram = registry.histogram("test_histogram", "Test histogram.", &[10, 1, 100]);
+        histogram.observe(0);
+        histogram.observe(5);
+        histogram.observe(50);
+        histogram.observe(500);
+
+        let metrics = registry.collect();
+        assert_eq!(metrics.len(), 3);
+        assert_eq!(metrics[0].name, "test_counter");
+        assert_eq!(metrics[0].value, MetricValue::Counter(4));
+        assert_eq!(metrics[1].name, "test_gauge");
+        assert_eq!(metrics[1].value, MetricValue::Gauge(9));
+        assert_eq!(metrics[2].name, "test_histogram");
+        assert_eq!(
+            metrics[2].value,
+            MetricValue::Histogram {
+                buckets: vec![
+                    HistogramBucket {
+                        upper_bound: 1,
+                        count: 1
+                    },
+                    HistogramBucket {
+                        upper_bound: 10,
+                        count: 2
+                    },
+                    HistogramBucket {
+                        upper_bound: 100,
+                        count: 3
+                    },
+                ],
+                count: 4,
+                sum: 555,
+            }
+        );
+
+        // Round-trip through CBOR.
+        let dec: Vec<Metric> = cbor::from_slice(&cbor::to_vec(metrics.clone())).unwrap();
+        assert_eq!(dec, metrics);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_registry_type_mismatch() {
+        let registry = Registry::default();
+        registry.counter("test_metric", "Test metric.");
+        registry.gauge("test_metric", "Test metric.");
+    }
+}
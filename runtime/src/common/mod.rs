@@ -3,12 +3,16 @@
 #[macro_use]
 pub mod bytes;
 pub mod crypto;
+#[macro_use]
+pub mod errors;
 pub mod key_format;
 pub mod logger;
+pub mod metrics;
 pub mod namespace;
 pub mod process;
 pub mod quantity;
 pub mod sgx;
 pub mod time;
+pub mod trace;
 pub mod version;
 pub mod versioned;
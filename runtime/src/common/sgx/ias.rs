@@ -240,6 +240,13 @@ impl ParsedAVR {
     }
 }
 
+/// Time (POSIX) until which the timestamp of the already verified attestation report is
+/// considered fresh.
+pub fn valid_until(avr: &AVR) -> Result<i64> {
+    let timestamp = ParsedAVR::new(avr)?.timestamp()?;
+    Ok(timestamp.saturating_add(60 * 60 * 24 - 1))
+}
+
 /// Verify attestation report.
 pub fn verify(avr: &AVR, policy: &QuotePolicy) -> Result<VerifiedQuote> {
     if policy.disabled {
@@ -84,6 +84,14 @@ impl Quote {
         Ok(verified_quote)
     }
 
+    /// Time (POSIX) until which the time-dependent checks of the already verified quote pass.
+    pub fn valid_until(&self, policy: &QuotePolicy) -> Result<i64> {
+        match self {
+            Quote::Ias(avr) => ias::valid_until(avr),
+            Quote::Pcs(qb) => Ok(qb.valid_until(&policy.pcs.clone().unwrap_or_default())?),
+        }
+    }
+
     /// Whether the quote should be considered fresh.
     pub fn is_fresh(&self, now: i64, ts: i64, policy: &QuotePolicy) -> bool {
         // Check general freshness requirement.
@@ -382,6 +382,28 @@ impl quote::Quote3SignatureEcdsaP256Verifier for QeEcdsaP256Verifier {
     }
 }
 
+impl QuoteBundle {
+    /// Time (POSIX) until which the time-dependent checks of the already verified quote bundle
+    /// pass, based on the issue dates of the TCB info and QE identity.
+    pub fn valid_until(&self, policy: &QuotePolicy) -> Result<i64, Error> {
+        let ti: TCBInfo = serde_json::from_str(self.tcb.tcb_info.tcb_info.get())
+            .map_err(|err| Error::TCBParseError(err.into()))?;
+        let qe: QEIdentity = serde_json::from_str(self.tcb.qe_identity.enclave_identity.get())
+            .map_err(|err| Error::TCBParseError(err.into()))?;
+        let validity_period = Duration::try_days(policy.tcb_validity_period.into())
+            .unwrap_or(DEFAULT_TCB_VALIDITY_PERIOD);
+
+        let valid_until = |issue_date: &str| -> Result<i64, Error> {
+            let issue_date = NaiveDateTime::parse_from_str(issue_date, PCS_TS_FMT)
+                .map_err(|err| Error::TCBParseError(err.into()))?
+                .and_utc();
+            Ok((issue_date + validity_period).timestamp())
+        };
+
+        Ok(valid_until(&ti.issue_date)?.min(valid_until(&qe.issue_date)?))
+    }
+}
+
 /// The TCB bundle contains all the required components to verify a quote's TCB.
 #[derive(Clone, Debug, Default, PartialEq, Eq, cbor::Encode, cbor::Decode)]
 pub struct TCBBundle {
@@ -1016,4 +1038,19 @@ mod tests {
         qb.verify(policy, now)
             .expect_err("quote verification should fail for blacklisted FMSPCs");
     }
+
+    #[test]
+    fn test_quote_bundle_valid_until() {
+        // From Go implementation.
+        const RAW_QUOTE_BUNDLE: &[u8] = include_bytes!("../../../testdata/pcs_quote_bundle.cbor");
+
+        let qb: QuoteBundle = cbor::from_slice(RAW_QUOTE_BUNDLE).unwrap();
+        let policy = QuotePolicy::default();
+
+        let valid_until = qb.valid_until(&policy).unwrap();
+        qb.verify(&policy, Utc.timestamp_opt(valid_until, 0).unwrap())
+            .expect("quote verification should succeed until the quote expires");
+        qb.verify(&policy, Utc.timestamp_opt(valid_until + 1, 0).unwrap())
+            .expect_err("quote verification should fail after the quote expires");
+    }
 }
@@ -0,0 +1,167 @@
+//! Distributed tracing context propagation.
+//!
+//! The host may attach a span context to requests that it sends to the runtime. The runtime does
+//! not record any spans of its own. Instead, the context is made available while the request is
+//! being processed so that log records can be correlated with the host's spans and so that any
+//! requests made to the host on behalf of the request carry the same context.
+//!
+//! NOTE: The span context is only ever sent to the host when it was received from the host, so
+//! hosts that do not support span contexts never receive one.
+use std::{cell::RefCell, future::Future};
+
+use rustc_hex::ToHex;
+use slog::{o, Logger};
+
+tokio::task_local! {
+    static TASK_CONTEXT: Option<SpanContext>;
+}
+
+thread_local! {
+    static THREAD_CONTEXT: RefCell<Option<SpanContext>> = const { RefCell::new(None) };
+}
+
+/// Span context propagated over the runtime host protocol.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, cbor::Encode, cbor::Decode)]
+pub struct SpanContext {
+    /// Trace identifier.
+    pub trace_id: [u8; 16],
+    /// Identifier of the host span that issued the request.
+    pub span_id: [u8; 8],
+    /// Whether the trace has been sampled by the host. Contexts that are not sampled are still
+    /// propagated, but are not attached to log records.
+    #[cbor(optional)]
+    pub sampled: bool,
+}
+
+/// Span context of the request currently being processed, if any.
+pub fn current() -> Option<SpanContext> {
+    TASK_CONTEXT
+        .try_with(|ctx| *ctx)
+        .ok()
+        .flatten()
+        .or_else(|| THREAD_CONTEXT.with(|ctx| *ctx.borrow()))
+}
+
+/// Run the given future with the given span context as the current context.
+pub async fn scope<F: Future>(ctx: Option<SpanContext>, f: F) -> F::Output {
+    TASK_CONTEXT.scope(ctx, f).await
+}
+
+/// Set the given span context as the current context of this thread until the returned guard
+/// is dropped.
+pub fn enter(ctx: Option<SpanContext>) -> EnterGuard {
+    let previous = THREAD_CONTEXT.with(|current| current.replace(ctx));
+    EnterGuard { previous }
+}
+
+/// Guard that restores the previous thread span context when dropped.
+pub struct EnterGuard {
+    previous: Option<SpanContext>,
+}
+
+impl Drop for EnterGuard {
+    fn drop(&mut self) {
+        THREAD_CONTEXT.with(|current| current.replace(self.previous.take()));
+    }
+}
+
+/// Run a blocking closure on the Tokio blocking thread pool, propagating the current span
+/// context.
+pub fn spawn_blocking<F, R>(f: F) -> tokio::task::JoinHandle<R>
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    let ctx = current();
+    tokio::task::spawn_blocking(move || {
+        let _guard = enter(ctx);
+        f()
+    })
+}
+
+/// Spawn a future on the Tokio runtime, propagating the current span context.
+pub fn spawn<F>(f: F) -> tokio::task::JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    tokio::spawn(scope(current(), f))
+}
+
+/// Return a logger which includes the current span context in its key-values in case the
+/// current trace is sampled.
+pub fn logger(logger: &Logger) -> Logger {
+    match current() {
+        Some(ctx) if ctx.sampled => logger.new(o!(
+            "trace_id" => ctx.trace_id.to_hex::<String>(),
+            "span_id" => ctx.span_id.to_hex::<String>(),
+        )),
+        _ => logger.clone(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_context_propagation() {
+        let ctx = SpanContext {
+            trace_id: [1; 16],
+            span_id: [2; 8],
+            sampled: true,
+        };
+        assert_eq!(current(), None);
+
+        {
+            let _guard = enter(Some(ctx));
+            assert_eq!(current(), Some(ctx));
+
+            {
+                let _guard = enter(None);
+                assert_eq!(current(), None);
+            }
+            assert_eq!(current(), Some(ctx));
+        }
+        assert_eq!(current(), None);
+
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        rt.block_on(scope(Some(ctx), async move {
+            assert_eq!(current(), Some(ctx));
+
+            let inner = spawn_blocking(current).await.unwrap();
+            assert_eq!(inner, Some(ctx));
+
+            let inner = spawn(async { current() }).await.unwrap();
+            assert_eq!(inner, Some(ctx));
+        }));
+        assert_eq!(current(), None);
+    }
+
+    #[test]
+    fn test_message_span_context_serialization() {
+        use rustc_hex::FromHex;
+
+        use crate::types::{Body, Message, MessageType};
+
+        // NOTE: This comes from TestMessageSpanContextSerialization in
+        //       go/runtime/host/protocol/types_test.go.
+        let encoded: Vec<u8> = "a4626964182a64626f6479a165456d707479a06c6d6573736167655f74797065016c7370616e5f636f6e74657874a36773616d706c6564f5677370616e5f69644802020202020202026874726163655f69645001010101010101010101010101010101"
+            .from_hex()
+            .unwrap();
+
+        let msg: Message = cbor::from_slice(&encoded).unwrap();
+        assert_eq!(msg.id, 42);
+        assert!(matches!(msg.message_type, MessageType::Request));
+        assert!(matches!(msg.body, Body::Empty {}));
+        assert_eq!(
+            msg.span_context,
+            Some(SpanContext {
+                trace_id: [1; 16],
+                span_id: [2; 8],
+                sampled: true,
+            })
+        );
+        assert_eq!(cbor::to_vec(msg), encoded);
+    }
+}
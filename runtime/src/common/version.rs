@@ -64,7 +64,7 @@ impl From<u64> for Version {
 // the worker host.
 pub const PROTOCOL_VERSION: Version = Version {
     major: 5,
-    minor: 1,
+    minor: 2,
     patch: 0,
 };
 
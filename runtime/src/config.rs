@@ -1,13 +1,32 @@
 //! Runtime configuration.
-use crate::{common::version::Version, consensus::verifier::TrustRoot, types::Features};
+use std::sync::Arc;
+
+use crate::{
+    common::{logger, version::Version},
+    consensus::{tendermint::verifier::Witness, verifier::TrustRoot},
+    types::Features,
+};
 
 /// Global runtime configuration.
 #[derive(Clone, Debug, Default)]
 pub struct Config {
     /// Semantic runtime version.
     pub version: Version,
-    /// Optional trust root for consensus layer integrity verification.
-    pub trust_root: Option<TrustRoot>,
+    /// Trust roots (checkpoints) for consensus layer integrity verification, ordered from the
+    /// oldest to the newest chain context. Each chain context should appear at most once and
+    /// verification is disabled in case no trust roots are configured.
+    ///
+    /// On first start the verifier starts from the newest trust root. When the consensus chain
+    /// context changes, the verifier switches to the matching trust root if one is configured and
+    /// otherwise attempts to transition from its latest trusted state. Transitions to chain
+    /// contexts preceding the trusted one are rejected.
+    pub trust_roots: Vec<TrustRoot>,
+    /// Additional witnesses used to cross-check consensus layer light blocks provided by the host.
+    /// A fork detected using any of the witnesses causes verification to fail.
+    pub witnesses: Vec<Arc<dyn Witness>>,
+    /// Whether light blocks should also be cross-checked against blocks that the host fetches
+    /// directly from consensus peers instead of its local consensus node.
+    pub host_witness: bool,
     /// Storage configuration.
     pub storage: Storage,
     /// Advertised runtime features.
@@ -15,6 +34,8 @@ pub struct Config {
     /// Whether storage state should be persisted between transaction check invocations. The state
     /// is invalidated on the next round.
     pub persist_check_tx_state: bool,
+    /// Logging configuration.
+    pub logging: Logging,
 }
 
 /// Storage-related configuration.
@@ -36,3 +57,15 @@ impl Default for Storage {
         }
     }
 }
+
+/// Logging-related configuration.
+///
+/// The host may override these settings via the `log_level` and `log_format` local configuration
+/// keys during runtime initialization.
+#[derive(Clone, Debug, Default)]
+pub struct Logging {
+    /// Log level filter.
+    pub filter: logger::Filter,
+    /// Log output format.
+    pub format: logger::Format,
+}
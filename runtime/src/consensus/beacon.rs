@@ -1,3 +1,5 @@
+use crate::common::crypto::hash::Hash;
+
 /// The number of intervals (epochs) since a fixed instant in time/block height (epoch date/height).
 pub type EpochTime = u64;
 
@@ -10,3 +12,48 @@ pub struct EpochTimeState {
     pub epoch: EpochTime,
     pub height: i64,
 }
+
+/// Event emitted when a new epoch starts.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, cbor::Encode, cbor::Decode)]
+pub struct EpochEvent {
+    /// The new epoch.
+    #[cbor(optional)]
+    pub epoch: EpochTime,
+}
+
+/// Event emitted when a new beacon value is generated.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, cbor::Encode, cbor::Decode)]
+pub struct BeaconEvent {
+    /// The new beacon value.
+    #[cbor(optional)]
+    pub beacon: Vec<u8>,
+}
+
+/// Event emitted by the VRF beacon backend.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, cbor::Encode, cbor::Decode)]
+pub struct VRFEvent {
+    /// The epoch that alpha is valid for.
+    #[cbor(optional)]
+    pub epoch: EpochTime,
+    /// The active VRF alpha_string input.
+    #[cbor(optional)]
+    pub alpha: Vec<u8>,
+    /// The block height after which nodes may submit VRF proofs for the current epoch.
+    pub submit_after: i64,
+}
+
+/// A beacon-related event.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, cbor::Encode, cbor::Decode)]
+pub struct Event {
+    #[cbor(optional)]
+    pub height: i64,
+    #[cbor(optional)]
+    pub tx_hash: Hash,
+
+    #[cbor(optional)]
+    pub epoch: Option<EpochEvent>,
+    #[cbor(optional)]
+    pub beacon: Option<BeaconEvent>,
+    #[cbor(optional)]
+    pub vrf: Option<VRFEvent>,
+}
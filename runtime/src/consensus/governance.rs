@@ -7,8 +7,8 @@
 use std::collections::BTreeMap;
 
 use crate::{
-    common::{quantity::Quantity, version::ProtocolVersions},
-    consensus::beacon::EpochTime,
+    common::{crypto::hash::Hash, quantity::Quantity, version::ProtocolVersions},
+    consensus::{address::Address, beacon::EpochTime},
 };
 
 /// A governance vote.
@@ -60,9 +60,21 @@ pub struct ChangeParametersProposal {
     pub changes: Option<cbor::Value>,
 }
 
+/// Proposal metadata.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, cbor::Encode, cbor::Decode)]
+pub struct ProposalMetadata {
+    /// Human-readable proposal title.
+    pub title: String,
+    /// Human-readable description.
+    #[cbor(optional)]
+    pub description: String,
+}
+
 /// Consensus layer governance proposal content.
 #[derive(Clone, Debug, Default, PartialEq, Eq, cbor::Encode, cbor::Decode)]
 pub struct ProposalContent {
+    #[cbor(optional)]
+    pub metadata: Option<ProposalMetadata>,
     #[cbor(optional)]
     pub upgrade: Option<UpgradeProposal>,
     #[cbor(optional)]
@@ -90,6 +102,144 @@ pub struct ConsensusParameterChanges {
     pub enable_change_parameters_proposal: Option<bool>,
 }
 
+/// State of a governance proposal.
+#[derive(
+    Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord, cbor::Encode, cbor::Decode,
+)]
+#[repr(u8)]
+pub enum ProposalState {
+    /// Proposal is open for voting.
+    #[default]
+    Active = 1,
+    /// Proposal has been accepted.
+    Passed = 2,
+    /// Proposal has been rejected.
+    Rejected = 3,
+    /// Proposal has been accepted but could not be executed.
+    Failed = 4,
+}
+
+/// Consensus layer governance proposal.
+#[derive(Clone, Debug, Default, PartialEq, Eq, cbor::Encode, cbor::Decode)]
+pub struct Proposal {
+    /// Unique identifier of the proposal.
+    pub id: u64,
+    /// Address of the proposal submitter.
+    pub submitter: Address,
+    /// State of the proposal.
+    pub state: ProposalState,
+    /// Deposit attached to the proposal.
+    pub deposit: Quantity,
+    /// Content of the proposal.
+    pub content: ProposalContent,
+    /// Epoch at which the proposal was created.
+    pub created_at: EpochTime,
+    /// Epoch at which the proposal will close and votes will be tallied.
+    pub closes_at: EpochTime,
+    /// Final tallied results after the voting period has ended.
+    #[cbor(optional)]
+    pub results: BTreeMap<Vote, Quantity>,
+    /// Number of invalid votes after tallying.
+    #[cbor(optional)]
+    pub invalid_votes: u64,
+}
+
+/// A vote cast for a proposal.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, cbor::Encode, cbor::Decode)]
+pub struct VoteEntry {
+    /// Address of the voter.
+    pub voter: Address,
+    /// Cast vote.
+    pub vote: Vote,
+}
+
+/// Governance consensus parameters.
+#[derive(Clone, Debug, Default, PartialEq, Eq, cbor::Encode, cbor::Decode)]
+pub struct ConsensusParameters {
+    /// Governance transaction gas costs.
+    #[cbor(optional)]
+    pub gas_costs: BTreeMap<String, u64>,
+    /// Number of base units that are deposited when creating a new proposal.
+    #[cbor(optional)]
+    pub min_proposal_deposit: Quantity,
+    /// Number of epochs after which the voting for a proposal is closed.
+    #[cbor(optional)]
+    pub voting_period: EpochTime,
+    /// Minimum percentage of yes votes in terms of total voting power for a proposal to pass.
+    #[cbor(optional)]
+    pub stake_threshold: u8,
+    /// Minimum number of epochs between the current epoch and the proposed upgrade epoch.
+    #[cbor(optional)]
+    pub upgrade_min_epoch_diff: EpochTime,
+    /// Minimum number of epochs between the current epoch and the upgrade epoch for the upgrade
+    /// cancellation proposal to be valid.
+    #[cbor(optional)]
+    pub upgrade_cancel_min_epoch_diff: EpochTime,
+    /// Whether change parameters proposals are allowed.
+    #[cbor(optional)]
+    pub enable_change_parameters_proposal: bool,
+    /// Whether casting votes without a registered entity is allowed.
+    #[cbor(optional)]
+    pub allow_vote_without_entity: bool,
+    /// Whether proposals are allowed to contain metadata.
+    #[cbor(optional)]
+    pub allow_proposal_metadata: bool,
+}
+
+/// Event emitted when a new proposal is submitted.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, cbor::Encode, cbor::Decode)]
+pub struct ProposalSubmittedEvent {
+    /// Unique identifier of the proposal.
+    pub id: u64,
+    /// Address of the submitter.
+    pub submitter: Address,
+}
+
+/// Event emitted when a proposal is executed.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, cbor::Encode, cbor::Decode)]
+pub struct ProposalExecutedEvent {
+    /// Unique identifier of the proposal.
+    pub id: u64,
+}
+
+/// Event emitted when a proposal is finalized.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, cbor::Encode, cbor::Decode)]
+pub struct ProposalFinalizedEvent {
+    /// Unique identifier of the proposal.
+    pub id: u64,
+    /// New state of the proposal.
+    pub state: ProposalState,
+}
+
+/// Event emitted when a vote is cast.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, cbor::Encode, cbor::Decode)]
+pub struct VoteEvent {
+    /// Unique identifier of the proposal.
+    pub id: u64,
+    /// Address of the vote submitter.
+    pub submitter: Address,
+    /// Cast vote.
+    pub vote: Vote,
+}
+
+/// A governance-related event.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, cbor::Encode, cbor::Decode)]
+pub struct Event {
+    #[cbor(optional)]
+    pub height: i64,
+    #[cbor(optional)]
+    pub tx_hash: Hash,
+
+    #[cbor(optional)]
+    pub proposal_submitted: Option<ProposalSubmittedEvent>,
+    #[cbor(optional)]
+    pub proposal_executed: Option<ProposalExecutedEvent>,
+    #[cbor(optional)]
+    pub proposal_finalized: Option<ProposalFinalizedEvent>,
+    #[cbor(optional)]
+    pub vote: Option<VoteEvent>,
+}
+
 #[cfg(test)]
 mod tests {
     use base64::prelude::*;
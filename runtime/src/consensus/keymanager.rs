@@ -5,6 +5,7 @@ use thiserror::Error;
 
 use crate::common::{
     crypto::{
+        hash::Hash,
         signature::{Signature, SignatureBundle, Signer},
         x25519,
     },
@@ -12,7 +13,7 @@ use crate::common::{
     sgx::EnclaveIdentity,
 };
 
-use super::beacon::EpochTime;
+use super::{beacon::EpochTime, state::keymanager::Status};
 
 pub mod churp;
 
@@ -146,3 +147,43 @@ impl SignedEncryptedEphemeralSecret {
         Ok(Self { secret, signature })
     }
 }
+
+/// Event emitted when key manager statuses are updated.
+#[derive(Clone, Debug, Default, PartialEq, Eq, cbor::Encode, cbor::Decode)]
+pub struct StatusUpdateEvent {
+    /// Updated key manager statuses.
+    #[cbor(rename = "Statuses")]
+    pub statuses: Vec<Status>,
+}
+
+/// Event emitted when a new master secret is published.
+#[derive(Clone, Debug, Default, PartialEq, Eq, cbor::Encode, cbor::Decode)]
+pub struct MasterSecretPublishedEvent {
+    /// Published master secret.
+    #[cbor(rename = "Secret")]
+    pub secret: SignedEncryptedMasterSecret,
+}
+
+/// Event emitted when a new ephemeral secret is published.
+#[derive(Clone, Debug, Default, PartialEq, Eq, cbor::Encode, cbor::Decode)]
+pub struct EphemeralSecretPublishedEvent {
+    /// Published ephemeral secret.
+    #[cbor(rename = "Secret")]
+    pub secret: SignedEncryptedEphemeralSecret,
+}
+
+/// A key manager-related event.
+#[derive(Clone, Debug, Default, PartialEq, Eq, cbor::Encode, cbor::Decode)]
+pub struct Event {
+    #[cbor(optional)]
+    pub height: i64,
+    #[cbor(optional)]
+    pub tx_hash: Hash,
+
+    #[cbor(optional)]
+    pub status_update: Option<StatusUpdateEvent>,
+    #[cbor(optional)]
+    pub master_secret_published: Option<MasterSecretPublishedEvent>,
+    #[cbor(optional)]
+    pub ephemeral_secret_published: Option<EphemeralSecretPublishedEvent>,
+}
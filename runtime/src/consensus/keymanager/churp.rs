@@ -178,3 +178,33 @@ impl SignedPolicySGX {
         Ok(&self.policy)
     }
 }
+
+/// Event emitted when a new CHURP instance is created.
+#[derive(Clone, Debug, Default, PartialEq, Eq, cbor::Encode, cbor::Decode)]
+pub struct CreateEvent {
+    /// Status of the created instance.
+    #[cbor(rename = "Status")]
+    pub status: Status,
+}
+
+/// Event emitted when a CHURP instance is updated.
+#[derive(Clone, Debug, Default, PartialEq, Eq, cbor::Encode, cbor::Decode)]
+pub struct UpdateEvent {
+    /// Status of the updated instance.
+    #[cbor(rename = "Status")]
+    pub status: Status,
+}
+
+/// A key manager CHURP-related event.
+#[derive(Clone, Debug, Default, PartialEq, Eq, cbor::Encode, cbor::Decode)]
+pub struct Event {
+    #[cbor(optional)]
+    pub height: i64,
+    #[cbor(optional)]
+    pub tx_hash: Hash,
+
+    #[cbor(optional)]
+    pub create: Option<CreateEvent>,
+    #[cbor(optional)]
+    pub update: Option<UpdateEvent>,
+}
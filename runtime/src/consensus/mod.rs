@@ -1,6 +1,6 @@
 //! Consensus service interfaces.
 
-use crate::common::crypto::hash::Hash;
+use crate::{common::crypto::hash::Hash, types::EventKind};
 
 pub mod address;
 pub mod beacon;
@@ -36,17 +36,113 @@ pub struct LightBlock {
 pub enum Event {
     #[cbor(rename = "staking")]
     Staking(staking::Event),
-    // TODO: Add support for other kind of events.
+    #[cbor(rename = "registry")]
+    Registry(registry::Event),
+    #[cbor(rename = "roothash")]
+    RootHash(roothash::Event),
+    #[cbor(rename = "governance")]
+    Governance(governance::Event),
+    #[cbor(rename = "beacon")]
+    Beacon(beacon::Event),
+    #[cbor(rename = "keymanager")]
+    KeyManager(keymanager::Event),
+    #[cbor(rename = "churp")]
+    KeyManagerChurp(keymanager::churp::Event),
+}
+
+impl Event {
+    /// Kind of the event.
+    pub fn kind(&self) -> EventKind {
+        match self {
+            Event::Staking(_) => EventKind::Staking,
+            Event::Registry(_) => EventKind::Registry,
+            Event::RootHash(_) => EventKind::RootHash,
+            Event::Governance(_) => EventKind::Governance,
+            Event::Beacon(_) => EventKind::Beacon,
+            Event::KeyManager(_) => EventKind::KeyManager,
+            Event::KeyManagerChurp(_) => EventKind::KeyManagerChurp,
+        }
+    }
+
+    /// Height of the block in which the event was emitted.
+    pub fn height(&self) -> i64 {
+        match self {
+            Event::Staking(ev) => ev.height,
+            Event::Registry(ev) => ev.height,
+            Event::RootHash(ev) => ev.height,
+            Event::Governance(ev) => ev.height,
+            Event::Beacon(ev) => ev.height,
+            Event::KeyManager(ev) => ev.height,
+            Event::KeyManagerChurp(ev) => ev.height,
+        }
+    }
 }
 
 /// BlockMetadata contains additional metadata related to the executing block.
 ///
 /// The metadata is included in the form of a special transaction where this structure is the
-/// transaction body.
+/// transaction body. Unknown fields are ignored so that metadata produced by newer consensus
+/// versions can still be decoded.
 #[derive(Clone, Debug, Default, PartialEq, Eq, cbor::Encode, cbor::Decode)]
+#[cbor(allow_unknown)]
 pub struct BlockMetadata {
     /// State root after executing all logic in the block.
     pub state_root: Hash,
     // EventsRoot is the provable events root.
     pub events_root: Vec<u8>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_encoding() {
+        let tcs = vec![
+            (
+                Event::Registry(registry::Event {
+                    height: 42,
+                    node_unfrozen: Some(registry::NodeUnfrozenEvent::default()),
+                    ..Default::default()
+                }),
+                "registry",
+                EventKind::Registry,
+            ),
+            (
+                Event::RootHash(roothash::Event {
+                    height: 42,
+                    finalized: Some(roothash::FinalizedEvent { round: 10 }),
+                    ..Default::default()
+                }),
+                "roothash",
+                EventKind::RootHash,
+            ),
+            (
+                Event::KeyManagerChurp(keymanager::churp::Event {
+                    height: 42,
+                    create: Some(keymanager::churp::CreateEvent::default()),
+                    ..Default::default()
+                }),
+                "churp",
+                EventKind::KeyManagerChurp,
+            ),
+        ];
+        for (event, name, kind) in tcs {
+            assert_eq!(event.kind(), kind);
+            assert_eq!(event.height(), 42);
+
+            // Events are encoded as maps keyed by the module name, same as in Go.
+            let value = cbor::to_value(event.clone());
+            match &value {
+                cbor::Value::Map(fields) => {
+                    assert_eq!(fields.len(), 1);
+                    assert_eq!(fields[0].0, cbor::Value::TextString(name.to_string()));
+                }
+                _ => panic!("event should be encoded as a map"),
+            }
+
+            let dec: Event = cbor::from_value(value).expect("event should deserialize correctly");
+            assert_eq!(dec.kind(), kind);
+        }
+    }
+}
@@ -904,6 +904,133 @@ pub struct RuntimeGenesis {
     pub round: u64,
 }
 
+/// Entity registry descriptor.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, cbor::Encode, cbor::Decode)]
+pub struct Entity {
+    /// Structure version.
+    pub v: u16,
+
+    /// Public key identifying the entity.
+    pub id: signature::PublicKey,
+
+    /// Node identity keys owned by this entity.
+    #[cbor(optional)]
+    pub nodes: Vec<signature::PublicKey>,
+}
+
+/// Node status.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, cbor::Encode, cbor::Decode)]
+pub struct NodeStatus {
+    /// A flag specifying whether the node expiration has already been processed.
+    ///
+    /// If you want to check whether a node has expired, check the node descriptor directly
+    /// instead of this flag.
+    pub expiration_processed: bool,
+    /// The epoch when a frozen node can become unfrozen.
+    pub freeze_end_time: EpochTime,
+    /// The epoch after which a node is eligible to be included in non-validator committee
+    /// elections.
+    ///
+    /// Note: A value of 0 is treated unconditionally as "ineligible".
+    pub election_eligible_after: EpochTime,
+    /// A set of fault records for nodes that are experiencing liveness failures when
+    /// participating in specific committees.
+    #[cbor(optional)]
+    pub faults: BTreeMap<Namespace, Fault>,
+}
+
+impl NodeStatus {
+    /// Returns true if the node is currently frozen (prevented from being considered in
+    /// scheduling decisions).
+    pub fn is_frozen(&self) -> bool {
+        self.freeze_end_time > 0
+    }
+
+    /// Checks whether the node is suspended for the given runtime in the given epoch.
+    pub fn is_suspended(&self, runtime_id: &Namespace, epoch: EpochTime) -> bool {
+        // If a node is frozen it is also suspended.
+        if self.is_frozen() {
+            return true;
+        }
+
+        self.faults
+            .get(runtime_id)
+            .map(|fault| fault.is_suspended(epoch))
+            .unwrap_or(false)
+    }
+}
+
+/// Liveness failure tracking state of a node.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, cbor::Encode, cbor::Decode)]
+pub struct Fault {
+    /// Number of times a node has been declared faulty.
+    #[cbor(optional)]
+    pub failures: u8,
+    /// Epoch number until the node is not eligible for being scheduled into the committee for
+    /// which it is deemed faulty.
+    #[cbor(optional)]
+    pub suspended_until: EpochTime,
+}
+
+impl Fault {
+    /// Checks whether the node is suspended in the given epoch.
+    pub fn is_suspended(&self, epoch: EpochTime) -> bool {
+        self.suspended_until > 0 && epoch < self.suspended_until
+    }
+}
+
+/// Event emitted when an entity is registered, updated or deregistered.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, cbor::Encode, cbor::Decode)]
+pub struct EntityEvent {
+    pub entity: Entity,
+    pub is_registration: bool,
+}
+
+/// Event emitted when a node is registered, updated or deregistered.
+#[derive(Clone, Debug, Default, PartialEq, Eq, cbor::Encode, cbor::Decode)]
+pub struct NodeEvent {
+    pub node: Node,
+    pub is_registration: bool,
+}
+
+/// Event emitted when a new runtime is started or a previously suspended runtime is resumed.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, cbor::Encode, cbor::Decode)]
+pub struct RuntimeStartedEvent {
+    pub runtime: Runtime,
+}
+
+/// Event emitted when a runtime is suspended.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, cbor::Encode, cbor::Decode)]
+pub struct RuntimeSuspendedEvent {
+    pub runtime_id: Namespace,
+}
+
+/// Event emitted when a node becomes unfrozen.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, cbor::Encode, cbor::Decode)]
+pub struct NodeUnfrozenEvent {
+    pub node_id: signature::PublicKey,
+}
+
+/// A registry-related event.
+#[derive(Clone, Debug, Default, PartialEq, Eq, cbor::Encode, cbor::Decode)]
+pub struct Event {
+    #[cbor(optional)]
+    pub height: i64,
+    #[cbor(optional)]
+    pub tx_hash: Hash,
+
+    #[cbor(optional)]
+    pub runtime_started: Option<RuntimeStartedEvent>,
+    #[cbor(optional)]
+    pub runtime_suspended: Option<RuntimeSuspendedEvent>,
+    #[cbor(optional)]
+    pub entity: Option<EntityEvent>,
+    #[cbor(optional)]
+    pub node: Option<NodeEvent>,
+    #[cbor(optional)]
+    pub node_unfrozen: Option<NodeUnfrozenEvent>,
+}
+
 #[cfg(test)]
 mod tests {
     use std::{convert::TryInto, net::Ipv4Addr};
@@ -1,4 +1,4 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 
 use crate::{
     common::{crypto::hash::Hash, quantity::Quantity, versioned::Versioned},
@@ -60,6 +60,15 @@ pub enum StakingMessage {
 
     #[cbor(rename = "reclaim_escrow")]
     ReclaimEscrow(staking::ReclaimEscrow),
+
+    #[cbor(rename = "allow")]
+    Allow(staking::Allow),
+
+    #[cbor(rename = "burn")]
+    Burn(staking::Burn),
+
+    #[cbor(rename = "amend_commission_schedule")]
+    AmendCommissionSchedule(staking::AmendCommissionSchedule),
 }
 
 impl StakingMessage {
@@ -82,6 +91,20 @@ impl StakingMessage {
                 // No validation at this time.
                 Ok(())
             }
+            StakingMessage::Allow(_) => {
+                // No validation at this time.
+                Ok(())
+            }
+            StakingMessage::Burn(_) => {
+                // No validation at this time.
+                Ok(())
+            }
+            StakingMessage::AmendCommissionSchedule(acs) => {
+                if acs.amendment.rates.is_empty() && acs.amendment.bounds.is_empty() {
+                    return Err(anyhow!("commission schedule amendment is empty"));
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -169,6 +192,8 @@ impl IncomingMessage {
 mod tests {
     use std::collections::BTreeMap;
 
+    use rustc_hex::ToHex;
+
     use crate::{
         common::{crypto::signature::PublicKey, namespace::Namespace, quantity},
         consensus::scheduler,
@@ -346,4 +371,51 @@ mod tests {
             assert_eq!(Message::messages_hash(&msgs), Hash::from(expected_hash));
         }
     }
+
+    #[test]
+    fn test_staking_message_encoding() {
+        // NOTE: These encodings MUST be kept in sync with go/roothash/api/message.
+        let beneficiary = Address::from(&[1; 21]);
+        let tcs = vec![
+            (
+                StakingMessage::Allow(staking::Allow {
+                    beneficiary,
+                    negative: true,
+                    amount_change: 10u32.into(),
+                }),
+                "a165616c6c6f77a3686e65676174697665f56b62656e6566696369617279550101010101010101010101010101010101010101016d616d6f756e745f6368616e6765410a",
+            ),
+            (
+                StakingMessage::Burn(staking::Burn {
+                    amount: 1000u32.into(),
+                }),
+                "a1646275726ea166616d6f756e744203e8",
+            ),
+            (
+                StakingMessage::AmendCommissionSchedule(staking::AmendCommissionSchedule {
+                    amendment: staking::CommissionSchedule {
+                        rates: vec![staking::CommissionRateStep {
+                            start: 0,
+                            rate: 50_000u32.into(),
+                        }],
+                        ..Default::default()
+                    },
+                }),
+                "a17819616d656e645f636f6d6d697373696f6e5f7363686564756c65a169616d656e646d656e74a165726174657381a1647261746542c350",
+            ),
+        ];
+        for (msg, expected) in tcs {
+            let enc = cbor::to_vec(msg.clone());
+            assert_eq!(enc.to_hex::<String>(), expected, "encoding should match");
+            let dec: StakingMessage = cbor::from_slice(&enc).expect("decoding should succeed");
+            assert_eq!(dec, msg, "decoded message should match");
+            assert!(msg.validate_basic().is_ok(), "message should be valid");
+        }
+
+        let msg = StakingMessage::AmendCommissionSchedule(Default::default());
+        assert!(
+            msg.validate_basic().is_err(),
+            "empty commission schedule amendment should be invalid"
+        );
+    }
 }
@@ -11,7 +11,7 @@ use crate::{
         crypto::{hash::Hash, signature::PublicKey},
         namespace::Namespace,
     },
-    consensus::state::StateError,
+    consensus::{address::Address, state::StateError},
 };
 
 // Modules.
@@ -128,6 +128,66 @@ pub struct RoundResults {
     pub bad_compute_entities: Vec<PublicKey>,
 }
 
+/// Event emitted when an executor commitment is received.
+#[derive(Clone, Debug, Default, PartialEq, Eq, cbor::Encode, cbor::Decode)]
+pub struct ExecutorCommittedEvent {
+    /// Executor commitment.
+    pub commit: ExecutorCommitment,
+}
+
+/// Event emitted when an execution discrepancy is detected.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, cbor::Encode, cbor::Decode)]
+pub struct ExecutionDiscrepancyDetectedEvent {
+    /// Round in which the discrepancy was detected.
+    pub round: u64,
+    /// Rank of the transaction scheduler.
+    pub rank: u64,
+    /// Whether the discrepancy was due to a timeout.
+    pub timeout: bool,
+}
+
+/// Event emitted when a round is finalized.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, cbor::Encode, cbor::Decode)]
+pub struct FinalizedEvent {
+    /// Round that was finalized.
+    pub round: u64,
+}
+
+/// Event emitted when an incoming message is processed.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, cbor::Encode, cbor::Decode)]
+pub struct InMsgProcessedEvent {
+    /// Unique incoming message identifier.
+    pub id: u64,
+    /// Round in which the incoming message was processed.
+    pub round: u64,
+    /// Address of the incoming message submitter.
+    pub caller: Address,
+    /// Optional tag provided by the caller.
+    #[cbor(optional)]
+    pub tag: u64,
+}
+
+/// A roothash-related event.
+#[derive(Clone, Debug, Default, PartialEq, Eq, cbor::Encode, cbor::Decode)]
+pub struct Event {
+    #[cbor(optional)]
+    pub height: i64,
+    #[cbor(optional)]
+    pub tx_hash: Hash,
+
+    /// Runtime the event relates to.
+    pub runtime_id: Namespace,
+
+    #[cbor(optional)]
+    pub executor_committed: Option<ExecutorCommittedEvent>,
+    #[cbor(optional, rename = "execution_discrepancy")]
+    pub execution_discrepancy_detected: Option<ExecutionDiscrepancyDetectedEvent>,
+    #[cbor(optional)]
+    pub finalized: Option<FinalizedEvent>,
+    #[cbor(optional)]
+    pub in_msg_processed: Option<InMsgProcessedEvent>,
+}
+
 /// Per-round state and I/O roots that are stored in consensus state.
 #[derive(Clone, Debug, Default, Eq, PartialEq, Hash, cbor::Encode, cbor::Decode)]
 #[cbor(as_array)]
@@ -22,6 +22,7 @@ pub enum Role {
 }
 
 /// A node participating in a committee.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, cbor::Encode, cbor::Decode)]
 pub struct CommitteeNode {
     /// The node's role in a committee.
     pub role: Role,
@@ -44,6 +45,7 @@ pub enum CommitteeKind {
 }
 
 /// A per-runtime (instance) committee.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, cbor::Encode, cbor::Decode)]
 pub struct Committee {
     /// The functionality a committee exists to provide.
     pub kind: CommitteeKind,
@@ -59,6 +61,18 @@ pub struct Committee {
 }
 
 impl Committee {
+    /// Returns true iff the given node is a member of the committee.
+    pub fn is_member(&self, id: &PublicKey) -> bool {
+        self.members.iter().any(|member| &member.public_key == id)
+    }
+
+    /// Returns true iff the given node is a worker in the committee.
+    pub fn is_worker(&self, id: &PublicKey) -> bool {
+        self.members
+            .iter()
+            .any(|member| member.role == Role::Worker && &member.public_key == id)
+    }
+
     /// Returns committee nodes with Worker role.
     pub fn workers(&self) -> Vec<&CommitteeNode> {
         self.members
@@ -79,3 +93,16 @@ impl Committee {
         Ok(scheduler)
     }
 }
+
+/// A consensus validator.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, cbor::Encode, cbor::Decode)]
+pub struct Validator {
+    /// The validator node identifier.
+    pub id: PublicKey,
+
+    /// The validator entity identifier.
+    pub entity_id: PublicKey,
+
+    /// The validator's consensus voting power.
+    pub voting_power: i64,
+}
@@ -39,6 +39,27 @@ pub struct ReclaimEscrow {
     pub shares: Quantity,
 }
 
+/// A stake burn.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, cbor::Encode, cbor::Decode)]
+pub struct Burn {
+    pub amount: Quantity,
+}
+
+/// An amendment to a commission schedule.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, cbor::Encode, cbor::Decode)]
+pub struct AmendCommissionSchedule {
+    pub amendment: CommissionSchedule,
+}
+
+/// A beneficiary allowance configuration.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, cbor::Encode, cbor::Decode)]
+pub struct Allow {
+    pub beneficiary: Address,
+    #[cbor(optional)]
+    pub negative: bool,
+    pub amount_change: Quantity,
+}
+
 /// Kind of staking threshold.
 #[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, cbor::Encode, cbor::Decode)]
 #[repr(i32)]
@@ -119,7 +140,10 @@ pub struct CommissionSchedule {
 /// Commission rate and its starting time.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Hash, cbor::Encode, cbor::Decode)]
 pub struct CommissionRateStep {
+    #[cbor(optional)]
     pub start: EpochTime,
+
+    #[cbor(optional)]
     pub rate: Quantity,
 }
 
@@ -247,6 +271,31 @@ pub struct Event {
     pub allowance_change: Option<AllowanceChangeEvent>,
 }
 
+impl Event {
+    /// Provable representation of the event, as committed to in the block's provable events root.
+    ///
+    /// Returns `None` in case the event is empty.
+    pub fn provable_representation(&self) -> Option<cbor::Value> {
+        if let Some(ev) = &self.transfer {
+            return Some(cbor::to_value(ev.clone()));
+        }
+        if let Some(ev) = &self.burn {
+            return Some(cbor::to_value(ev.clone()));
+        }
+        if let Some(ev) = &self.escrow {
+            // Escrow events are committed to without the enclosing escrow event kind.
+            return match cbor::to_value(ev.clone()) {
+                cbor::Value::Map(mut fields) if fields.len() == 1 => fields.pop().map(|(_, v)| v),
+                _ => None,
+            };
+        }
+        if let Some(ev) = &self.allowance_change {
+            return Some(cbor::to_value(ev.clone()));
+        }
+        None
+    }
+}
+
 /// Event emitted when stake is transferred, either by a call to Transfer or Withdraw.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Hash, cbor::Encode, cbor::Decode)]
 pub struct TransferEvent {
@@ -723,4 +772,43 @@ mod tests {
             assert_eq!(dec, ev, "decoded event should match the expected value");
         }
     }
+
+    #[test]
+    fn test_event_provable_representation() {
+        let transfer = TransferEvent {
+            amount: 100u32.into(),
+            ..Default::default()
+        };
+        let ev = Event {
+            height: 42,
+            transfer: Some(transfer.clone()),
+            ..Default::default()
+        };
+        assert_eq!(ev.provable_representation(), Some(cbor::to_value(transfer)));
+
+        // Escrow events are represented without the escrow event kind.
+        let ev = Event {
+            height: 42,
+            escrow: Some(EscrowEvent::Add {
+                owner: Address::default(),
+                escrow: Address::default(),
+                amount: 100u32.into(),
+                new_shares: 10u32.into(),
+            }),
+            ..Default::default()
+        };
+        let keys: Vec<_> = match ev.provable_representation() {
+            Some(cbor::Value::Map(fields)) => fields.into_iter().map(|(k, _)| k).collect(),
+            _ => panic!("escrow event should be represented as a map"),
+        };
+        assert_eq!(
+            keys,
+            ["owner", "escrow", "amount", "new_shares"]
+                .iter()
+                .map(|k| cbor::Value::TextString(k.to_string()))
+                .collect::<Vec<_>>()
+        );
+
+        assert_eq!(Event::default().provable_representation(), None);
+    }
 }
@@ -0,0 +1,553 @@
+//! Governance state in the consensus layer.
+use anyhow::anyhow;
+
+use crate::{
+    common::key_format::{KeyFormat, KeyFormatAtom},
+    consensus::{
+        address::Address,
+        beacon::EpochTime,
+        governance::{
+            ConsensusParameters, Proposal, ProposalState, UpgradeProposal, Vote, VoteEntry,
+        },
+        state::StateError,
+    },
+    key_format,
+    storage::mkvs::{FallibleMKVS, ImmutableMKVS},
+};
+
+/// Consensus governance state wrapper.
+pub struct ImmutableState<'a, T: ImmutableMKVS> {
+    mkvs: &'a T,
+}
+
+impl<'a, T: ImmutableMKVS> ImmutableState<'a, T> {
+    /// Constructs a new ImmutableMKVS.
+    pub fn new(mkvs: &'a T) -> ImmutableState<'a, T> {
+        ImmutableState { mkvs }
+    }
+}
+
+key_format!(NextProposalIdentifierKeyFmt, 0x80, ());
+key_format!(ProposalsKeyFmt, 0x81, u64);
+key_format!(ActiveProposalsKeyFmt, 0x82, (EpochTime, u64));
+key_format!(VotesKeyFmt, 0x83, (u64, Address));
+key_format!(PendingUpgradesKeyFmt, 0x84, (EpochTime, u64));
+key_format!(ParametersKeyFmt, 0x85, ());
+
+impl<'a, T: ImmutableMKVS> ImmutableState<'a, T> {
+    /// Returns the identifier of the next proposal.
+    pub fn next_proposal_identifier(&self) -> Result<u64, StateError> {
+        match self.mkvs.get(&NextProposalIdentifierKeyFmt(()).encode()) {
+            Ok(Some(b)) => {
+                cbor::from_slice(&b).map_err(|err| StateError::Unavailable(anyhow!(err)))
+            }
+            Ok(None) => Ok(0),
+            Err(err) => Err(StateError::Unavailable(anyhow!(err))),
+        }
+    }
+
+    /// Looks up a specific proposal by its identifier.
+    pub fn proposal(&self, id: u64) -> Result<Option<Proposal>, StateError> {
+        match self.mkvs.get(&ProposalsKeyFmt(id).encode()) {
+            Ok(Some(b)) => Ok(Some(
+                cbor::from_slice(&b).map_err(|err| StateError::Unavailable(anyhow!(err)))?,
+            )),
+            Ok(None) => Ok(None),
+            Err(err) => Err(StateError::Unavailable(anyhow!(err))),
+        }
+    }
+
+    /// Returns all proposals, ordered by their identifiers.
+    pub fn proposals(&self) -> Result<Vec<Proposal>, StateError> {
+        let mut it = self.mkvs.iter();
+        it.seek(&ProposalsKeyFmt::default().encode_partial(0));
+
+        let mut result: Vec<Proposal> = Vec::new();
+
+        for value in it.map_while(|(key, value)| ProposalsKeyFmt::decode(&key).map(|_| value)) {
+            result.push(
+                cbor::from_slice(&value).map_err(|err| StateError::Unavailable(anyhow!(err)))?,
+            );
+        }
+
+        Ok(result)
+    }
+
+    /// Returns all active proposals, ordered by the epoch at which they close.
+    pub fn active_proposals(&self) -> Result<Vec<Proposal>, StateError> {
+        let mut it = self.mkvs.iter();
+        it.seek(&ActiveProposalsKeyFmt::default().encode_partial(0));
+
+        let ids: Vec<u64> = it
+            .map_while(|(key, _)| ActiveProposalsKeyFmt::decode(&key))
+            .map(|ActiveProposalsKeyFmt((_, id))| id)
+            .collect();
+
+        ids.into_iter()
+            .map(|id| self.indexed_proposal(id))
+            .collect()
+    }
+
+    /// Returns all votes cast for the given proposal.
+    pub fn votes(&self, id: u64) -> Result<Vec<VoteEntry>, StateError> {
+        let mut it = self.mkvs.iter();
+        it.seek(&VotesKeyFmt((id, Default::default())).encode_partial(1));
+
+        let mut result: Vec<VoteEntry> = Vec::new();
+
+        for (voter, value) in it.map_while(|(key, value)| {
+            VotesKeyFmt::decode(&key)
+                .filter(|VotesKeyFmt((proposal_id, _))| *proposal_id == id)
+                .map(|VotesKeyFmt((_, voter))| (voter, value))
+        }) {
+            let vote: Vote =
+                cbor::from_slice(&value).map_err(|err| StateError::Unavailable(anyhow!(err)))?;
+            result.push(VoteEntry { voter, vote });
+        }
+
+        Ok(result)
+    }
+
+    /// Returns all pending upgrades, ordered by their upgrade epoch.
+    pub fn pending_upgrades(&self) -> Result<Vec<UpgradeProposal>, StateError> {
+        let mut it = self.mkvs.iter();
+        it.seek(&PendingUpgradesKeyFmt::default().encode_partial(0));
+
+        let ids: Vec<u64> = it
+            .map_while(|(key, _)| PendingUpgradesKeyFmt::decode(&key))
+            .map(|PendingUpgradesKeyFmt((_, id))| id)
+            .collect();
+
+        ids.into_iter()
+            .map(|id| {
+                self.indexed_proposal(id)?.content.upgrade.ok_or_else(|| {
+                    StateError::Unavailable(anyhow!(
+                        "pending upgrade {} with missing upgrade descriptor",
+                        id
+                    ))
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the governance consensus parameters.
+    pub fn consensus_parameters(&self) -> Result<ConsensusParameters, StateError> {
+        match self.mkvs.get(&ParametersKeyFmt(()).encode()) {
+            Ok(Some(b)) => {
+                cbor::from_slice(&b).map_err(|err| StateError::Unavailable(anyhow!(err)))
+            }
+            Ok(None) => Err(StateError::Unavailable(anyhow!(
+                "expected consensus parameters to be present in app state"
+            ))),
+            Err(err) => Err(StateError::Unavailable(anyhow!(err))),
+        }
+    }
+
+    /// Looks up a proposal referenced by one of the indices, which must exist.
+    fn indexed_proposal(&self, id: u64) -> Result<Proposal, StateError> {
+        self.proposal(id)?.ok_or_else(|| {
+            StateError::Unavailable(anyhow!("indexed proposal {} does not exist", id))
+        })
+    }
+}
+
+/// Mutable consensus governance state wrapper.
+pub struct MutableState;
+
+impl MutableState {
+    /// Set the next proposal identifier.
+    pub fn set_next_proposal_identifier<S: FallibleMKVS>(
+        mkvs: &mut S,
+        id: u64,
+    ) -> Result<(), StateError> {
+        mkvs.insert(
+            &NextProposalIdentifierKeyFmt(()).encode(),
+            &cbor::to_vec(id),
+        )?;
+        Ok(())
+    }
+
+    /// Set a proposal and add it to the active proposals in case it is active.
+    pub fn set_proposal<S: FallibleMKVS>(
+        mkvs: &mut S,
+        proposal: &Proposal,
+    ) -> Result<(), StateError> {
+        mkvs.insert(
+            &ProposalsKeyFmt(proposal.id).encode(),
+            &cbor::to_vec(proposal.clone()),
+        )?;
+        let key = ActiveProposalsKeyFmt((proposal.closes_at, proposal.id)).encode();
+        if proposal.state == ProposalState::Active {
+            mkvs.insert(&key, &[])?;
+        } else {
+            mkvs.remove(&key)?;
+        }
+        Ok(())
+    }
+
+    /// Set a vote for a proposal.
+    pub fn set_vote<S: FallibleMKVS>(
+        mkvs: &mut S,
+        id: u64,
+        voter: Address,
+        vote: Vote,
+    ) -> Result<(), StateError> {
+        mkvs.insert(&VotesKeyFmt((id, voter)).encode(), &cbor::to_vec(vote))?;
+        Ok(())
+    }
+
+    /// Set a pending upgrade for an upgrade proposal.
+    pub fn set_pending_upgrade<S: FallibleMKVS>(
+        mkvs: &mut S,
+        id: u64,
+        upgrade: &UpgradeProposal,
+    ) -> Result<(), StateError> {
+        mkvs.insert(&PendingUpgradesKeyFmt((upgrade.epoch, id)).encode(), &[])?;
+        Ok(())
+    }
+
+    /// Set governance consensus parameters.
+    pub fn set_consensus_parameters<S: FallibleMKVS>(
+        mkvs: &mut S,
+        params: ConsensusParameters,
+    ) -> Result<(), StateError> {
+        mkvs.insert(&ParametersKeyFmt(()).encode(), &cbor::to_vec(params))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+
+    use crate::{
+        common::{
+            crypto::{hash::Hash, signature::PublicKey},
+            quantity::Quantity,
+            version::{ProtocolVersions, Version},
+        },
+        consensus::governance::{CancelUpgradeProposal, ProposalContent},
+        storage::mkvs::{
+            interop::{Fixture, ProtocolServer},
+            sync::NoopReadSyncer,
+            Root, RootType, Tree,
+        },
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_mutable_state() {
+        let mut mkvs = Tree::builder()
+            .with_root_type(RootType::State)
+            .build(Box::new(NoopReadSyncer));
+
+        let upgrade = UpgradeProposal {
+            v: 1,
+            handler: "test-handler".into(),
+            epoch: 42,
+            ..Default::default()
+        };
+        let proposals = vec![
+            Proposal {
+                id: 1,
+                state: ProposalState::Passed,
+                content: ProposalContent {
+                    upgrade: Some(upgrade.clone()),
+                    ..Default::default()
+                },
+                closes_at: 20,
+                ..Default::default()
+            },
+            Proposal {
+                id: 2,
+                closes_at: 30,
+                ..Default::default()
+            },
+            Proposal {
+                id: 3,
+                closes_at: 25,
+                ..Default::default()
+            },
+        ];
+        for proposal in &proposals {
+            MutableState::set_proposal(&mut mkvs, proposal).unwrap();
+        }
+        MutableState::set_next_proposal_identifier(&mut mkvs, 4).unwrap();
+        MutableState::set_pending_upgrade(&mut mkvs, 1, &upgrade).unwrap();
+
+        let voter1 = Address::from(&[1; 21]);
+        let voter2 = Address::from(&[2; 21]);
+        MutableState::set_vote(&mut mkvs, 2, voter2.clone(), Vote::No).unwrap();
+        MutableState::set_vote(&mut mkvs, 2, voter1.clone(), Vote::Yes).unwrap();
+        MutableState::set_vote(&mut mkvs, 3, voter1.clone(), Vote::Abstain).unwrap();
+        MutableState::set_consensus_parameters(
+            &mut mkvs,
+            ConsensusParameters {
+                voting_period: 10,
+                stake_threshold: 68,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let governance_state = ImmutableState::new(&mkvs);
+
+        // Test proposals.
+        let next_id = governance_state
+            .next_proposal_identifier()
+            .expect("next proposal identifier query should work");
+        assert_eq!(4, next_id, "expected next proposal identifier should match");
+        let proposal = governance_state
+            .proposal(2)
+            .expect("proposal query should work");
+        assert_eq!(Some(proposals[1].clone()), proposal);
+        let proposal = governance_state
+            .proposal(4)
+            .expect("proposal query should work");
+        assert_eq!(None, proposal, "proposal should be missing");
+        let all = governance_state
+            .proposals()
+            .expect("proposals query should work");
+        assert_eq!(proposals, all, "expected proposals should match");
+
+        // Test active proposals.
+        let active: Vec<_> = governance_state
+            .active_proposals()
+            .expect("active proposals query should work")
+            .into_iter()
+            .map(|p| p.id)
+            .collect();
+        assert_eq!(vec![3, 2], active, "expected active proposals should match");
+
+        // Test votes.
+        let votes = governance_state.votes(2).expect("votes query should work");
+        assert_eq!(
+            vec![
+                VoteEntry {
+                    voter: voter1.clone(),
+                    vote: Vote::Yes,
+                },
+                VoteEntry {
+                    voter: voter2,
+                    vote: Vote::No,
+                },
+            ],
+            votes,
+            "expected votes should match"
+        );
+        let votes = governance_state.votes(1).expect("votes query should work");
+        assert!(votes.is_empty(), "there should be no votes");
+
+        // Test pending upgrades.
+        let upgrades = governance_state
+            .pending_upgrades()
+            .expect("pending upgrades query should work");
+        assert_eq!(vec![upgrade], upgrades, "expected upgrades should match");
+
+        // Test consensus parameters.
+        let params = governance_state
+            .consensus_parameters()
+            .expect("consensus parameters query should work");
+        assert_eq!(10, params.voting_period);
+        assert_eq!(68, params.stake_threshold);
+    }
+
+    #[test]
+    fn test_governance_state_interop() {
+        // Keep in sync with go/consensus/cometbft/apps/governance/state/interop/interop.go.
+        // If mock consensus state changes, update the root hash bellow.
+        // See protocol server stdout for hash.
+        // To make the hash show up during tests, run "cargo test" as
+        // "cargo test -- --nocapture".
+
+        // Setup protocol server with initialized mock consensus state.
+        let server = ProtocolServer::new(Fixture::ConsensusGovernanceMock.into());
+        let mock_consensus_root = Root {
+            version: 1,
+            root_type: RootType::State,
+            hash: Hash::from("cd4bcd3df950cf62b9cdfffa301e3839bdfc02ba324382bd1722aac8ad188d2d"),
+            ..Default::default()
+        };
+        let mkvs = Tree::builder()
+            .with_capacity(100_000, 10_000_000)
+            .with_root(mock_consensus_root)
+            .build(server.read_sync());
+        let governance_state = ImmutableState::new(&mkvs);
+
+        let pk =
+            PublicKey::from("7e57baaad01fffffffffffffffffffffffffffffffffffffffffffffffffffff");
+        let pk2 =
+            PublicKey::from("7e57baaad02fffffffffffffffffffffffffffffffffffffffffffffffffffff");
+        let pk3 =
+            PublicKey::from("7e57baaad03fffffffffffffffffffffffffffffffffffffffffffffffffffff");
+        let addrs = [
+            Address::from_pk(&pk),
+            Address::from_pk(&pk2),
+            Address::from_pk(&pk3),
+        ];
+
+        // Test proposals.
+        let next_id = governance_state
+            .next_proposal_identifier()
+            .expect("next proposal identifier query should work");
+        assert_eq!(4, next_id, "expected next proposal identifier should match");
+
+        let passed_upgrade = UpgradeProposal {
+            v: 1,
+            handler: "test-handler".into(),
+            target: ProtocolVersions {
+                consensus_protocol: Version::new(7, 1, 0),
+                runtime_host_protocol: Version::new(5, 0, 0),
+                runtime_committee_protocol: Version::new(4, 0, 2),
+            },
+            epoch: 42,
+        };
+        let expected_proposals = vec![
+            Proposal {
+                id: 1,
+                submitter: addrs[0].clone(),
+                state: ProposalState::Passed,
+                deposit: Quantity::from(100u32),
+                content: ProposalContent {
+                    upgrade: Some(passed_upgrade.clone()),
+                    ..Default::default()
+                },
+                created_at: 10,
+                closes_at: 20,
+                results: BTreeMap::from([
+                    (Vote::Yes, Quantity::from(300u32)),
+                    (Vote::No, Quantity::from(100u32)),
+                ]),
+                invalid_votes: 1,
+            },
+            Proposal {
+                id: 2,
+                submitter: addrs[1].clone(),
+                state: ProposalState::Active,
+                deposit: Quantity::from(200u32),
+                content: ProposalContent {
+                    cancel_upgrade: Some(CancelUpgradeProposal { proposal_id: 1 }),
+                    ..Default::default()
+                },
+                created_at: 25,
+                closes_at: 35,
+                ..Default::default()
+            },
+            Proposal {
+                id: 3,
+                submitter: addrs[0].clone(),
+                state: ProposalState::Active,
+                deposit: Quantity::from(150u32),
+                content: ProposalContent {
+                    upgrade: Some(UpgradeProposal {
+                        v: 1,
+                        handler: "test-handler-2".into(),
+                        target: ProtocolVersions {
+                            consensus_protocol: Version::new(8, 0, 0),
+                            runtime_host_protocol: Version::new(5, 1, 0),
+                            runtime_committee_protocol: Version::new(4, 1, 0),
+                        },
+                        epoch: 50,
+                    }),
+                    ..Default::default()
+                },
+                created_at: 26,
+                closes_at: 30,
+                ..Default::default()
+            },
+        ];
+        let proposals = governance_state
+            .proposals()
+            .expect("proposals query should work");
+        assert_eq!(
+            expected_proposals, proposals,
+            "expected proposals should match"
+        );
+        for expected in &expected_proposals {
+            let proposal = governance_state
+                .proposal(expected.id)
+                .expect("proposal query should work");
+            assert_eq!(
+                Some(expected.clone()),
+                proposal,
+                "expected proposal should match"
+            );
+        }
+        let proposal = governance_state
+            .proposal(4)
+            .expect("proposal query should work");
+        assert_eq!(None, proposal, "proposal should be missing");
+
+        // Test active proposals.
+        let active = governance_state
+            .active_proposals()
+            .expect("active proposals query should work");
+        assert_eq!(
+            vec![expected_proposals[2].clone(), expected_proposals[1].clone()],
+            active,
+            "expected active proposals should match"
+        );
+
+        // Test votes.
+        let votes = governance_state.votes(2).expect("votes query should work");
+        assert_eq!(
+            vec![
+                VoteEntry {
+                    voter: addrs[1].clone(),
+                    vote: Vote::Yes,
+                },
+                VoteEntry {
+                    voter: addrs[2].clone(),
+                    vote: Vote::No,
+                },
+            ],
+            votes,
+            "expected votes should match"
+        );
+        let votes = governance_state.votes(3).expect("votes query should work");
+        assert_eq!(
+            vec![VoteEntry {
+                voter: addrs[0].clone(),
+                vote: Vote::Abstain,
+            }],
+            votes,
+            "expected votes should match"
+        );
+        let votes = governance_state.votes(1).expect("votes query should work");
+        assert!(votes.is_empty(), "there should be no votes");
+
+        // Test pending upgrades.
+        let upgrades = governance_state
+            .pending_upgrades()
+            .expect("pending upgrades query should work");
+        assert_eq!(
+            vec![passed_upgrade],
+            upgrades,
+            "expected pending upgrades should match"
+        );
+
+        // Test consensus parameters.
+        let params = governance_state
+            .consensus_parameters()
+            .expect("consensus parameters query should work");
+        assert_eq!(
+            ConsensusParameters {
+                gas_costs: BTreeMap::from([
+                    ("cast_vote".to_string(), 500),
+                    ("submit_proposal".to_string(), 1000),
+                ]),
+                min_proposal_deposit: Quantity::from(100u32),
+                voting_period: 10,
+                stake_threshold: 68,
+                upgrade_min_epoch_diff: 40,
+                upgrade_cancel_min_epoch_diff: 20,
+                enable_change_parameters_proposal: true,
+                allow_vote_without_entity: true,
+                allow_proposal_metadata: true,
+            },
+            params,
+            "expected consensus parameters should match"
+        );
+    }
+}
@@ -7,13 +7,15 @@ use thiserror::Error;
 use crate::{
     protocol::Protocol,
     storage::mkvs::{sync::HostReadSyncer, ImmutableMKVS, Root, Tree},
-    types::{self, HostStorageEndpoint},
+    types::HostStorageEndpoint,
 };
 
 pub mod beacon;
+pub mod governance;
 pub mod keymanager;
 pub mod registry;
 pub mod roothash;
+pub mod scheduler;
 pub mod staking;
 
 #[derive(Error, Debug)]
@@ -22,15 +24,9 @@ pub enum StateError {
     Unavailable(#[from] Error),
 }
 
-impl From<StateError> for types::Error {
-    fn from(e: StateError) -> Self {
-        Self {
-            module: "consensus".to_string(),
-            code: 1,
-            message: e.to_string(),
-        }
-    }
-}
+impl_error_codes!(StateError, "consensus", {
+    StateError::Unavailable(_) => 1,
+});
 
 /// Provides consensus state tree from the host.
 pub struct ConsensusState {
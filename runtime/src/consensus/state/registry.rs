@@ -5,13 +5,13 @@ use crate::{
     common::{
         crypto::{
             hash::Hash,
-            signature::{MultiSigned, PublicKey},
+            signature::{MultiSigned, PublicKey, Signed},
         },
         key_format::{KeyFormat, KeyFormatAtom},
         namespace::Namespace,
     },
     consensus::{
-        registry::{Node, Runtime},
+        registry::{Entity, Node, NodeStatus, Runtime},
         state::StateError,
     },
     key_format,
@@ -30,11 +30,48 @@ impl<'a, T: ImmutableMKVS> ImmutableState<'a, T> {
     }
 }
 
+key_format!(SignedEntityKeyFmt, 0x10, Hash);
 key_format!(SignedNodeKeyFmt, 0x11, Hash);
 key_format!(RuntimeKeyFmt, 0x13, Hash);
+key_format!(NodeStatusKeyFmt, 0x15, Hash);
 key_format!(SuspendedRuntimeKeyFmt, 0x18, Hash);
 
 impl<'a, T: ImmutableMKVS> ImmutableState<'a, T> {
+    fn decode_entity(&self, data: &[u8]) -> Result<Entity, StateError> {
+        let signed: Signed =
+            cbor::from_slice(data).map_err(|err| StateError::Unavailable(anyhow!(err)))?;
+        // The signed blob is transported as-is so we need to use non-strict decoding.
+        cbor::from_slice_non_strict(&signed.blob)
+            .map_err(|err| StateError::Unavailable(anyhow!(err)))
+    }
+
+    /// Looks up a specific entity by its identifier.
+    pub fn entity(&self, id: &PublicKey) -> Result<Option<Entity>, StateError> {
+        let h = Hash::digest_bytes(id.as_ref());
+        match self.mkvs.get(&SignedEntityKeyFmt(h).encode()) {
+            Ok(Some(b)) => Ok(Some(self.decode_entity(&b)?)),
+            Ok(None) => Ok(None),
+            Err(err) => Err(StateError::Unavailable(anyhow!(err))),
+        }
+    }
+
+    /// Returns the list of all registered entities.
+    pub fn entities(&self) -> Result<Vec<Entity>, StateError> {
+        let mut it = self.mkvs.iter();
+        it.seek(&SignedEntityKeyFmt::default().encode_partial(0));
+
+        let mut result: Vec<Entity> = Vec::new();
+
+        while let Some(value) = it
+            .next()
+            .and_then(|(key, value)| SignedEntityKeyFmt::decode(&key).map(|_| value))
+        {
+            result.push(self.decode_entity(&value)?)
+        }
+
+        Ok(result)
+    }
+
     fn decode_node(&self, data: &[u8]) -> Result<Node, StateError> {
         let signed: MultiSigned =
             cbor::from_slice(data).map_err(|err| StateError::Unavailable(anyhow!(err)))?;
@@ -70,6 +107,18 @@ impl<'a, T: ImmutableMKVS> ImmutableState<'a, T> {
         Ok(result)
     }
 
+    /// Looks up the status of a specific node by its identifier.
+    pub fn node_status(&self, id: &PublicKey) -> Result<Option<NodeStatus>, StateError> {
+        let h = Hash::digest_bytes(id.as_ref());
+        match self.mkvs.get(&NodeStatusKeyFmt(h).encode()) {
+            Ok(Some(b)) => Ok(Some(
+                cbor::from_slice(&b).map_err(|err| StateError::Unavailable(anyhow!(err)))?,
+            )),
+            Ok(None) => Ok(None),
+            Err(err) => Err(StateError::Unavailable(anyhow!(err))),
+        }
+    }
+
     fn decode_runtime(&self, data: &[u8]) -> Result<Runtime, StateError> {
         cbor::from_slice(data).map_err(|err| StateError::Unavailable(anyhow!(err)))
     }
@@ -96,6 +145,32 @@ impl<'a, T: ImmutableMKVS> ImmutableState<'a, T> {
             Err(err) => Err(StateError::Unavailable(anyhow!(err))),
         }
     }
+
+    fn runtimes_by_key<K: KeyFormat + Default>(&self) -> Result<Vec<Runtime>, StateError> {
+        let mut it = self.mkvs.iter();
+        it.seek(&K::default().encode_partial(0));
+
+        let mut result: Vec<Runtime> = Vec::new();
+
+        while let Some(value) = it
+            .next()
+            .and_then(|(key, value)| K::decode(&key).map(|_| value))
+        {
+            result.push(self.decode_runtime(&value)?)
+        }
+
+        Ok(result)
+    }
+
+    /// Returns the list of all registered non-suspended runtimes.
+    pub fn runtimes(&self) -> Result<Vec<Runtime>, StateError> {
+        self.runtimes_by_key::<RuntimeKeyFmt>()
+    }
+
+    /// Returns the list of all suspended runtimes.
+    pub fn suspended_runtimes(&self) -> Result<Vec<Runtime>, StateError> {
+        self.runtimes_by_key::<SuspendedRuntimeKeyFmt>()
+    }
 }
 
 #[cfg(test)]
@@ -106,12 +181,13 @@ mod test {
         common::crypto::signature,
         consensus::registry::{
             AnyNodeRuntimeAdmissionPolicy, Capabilities, CapabilityTEE, ConsensusInfo,
-            EntityWhitelistRoleAdmissionPolicy, NodeRuntime, P2PInfo, PerRoleAdmissionPolicy,
-            RolesMask, RuntimeAdmissionPolicy, RuntimeKind, TEEHardware, TLSInfo, VRFInfo,
-            VersionInfo,
+            EntityWhitelistRoleAdmissionPolicy, Fault, NodeRuntime, P2PInfo,
+            PerRoleAdmissionPolicy, RolesMask, RuntimeAdmissionPolicy, RuntimeKind, TEEHardware,
+            TLSInfo, VRFInfo, VersionInfo,
         },
         storage::mkvs::{
             interop::{Fixture, ProtocolServer},
+            sync::NoopReadSyncer,
             Root, RootType, Tree,
         },
         Version,
@@ -304,11 +380,97 @@ mod test {
             },
         ];
 
-        for rt in expected_runtimes {
+        for rt in &expected_runtimes {
             let ext_rt = registry_state
                 .runtime(&rt.id)
                 .expect("runtime query should work");
-            assert_eq!(ext_rt, Some(rt));
+            assert_eq!(ext_rt, Some(rt.clone()));
         }
+
+        let mut runtimes = registry_state
+            .runtimes()
+            .expect("runtimes query should work");
+        runtimes.sort_by_key(|rt| rt.id);
+        assert_eq!(
+            runtimes,
+            vec![expected_runtimes[0].clone(), expected_runtimes[2].clone()]
+        );
+
+        let suspended = registry_state
+            .suspended_runtimes()
+            .expect("suspended runtimes query should work");
+        assert_eq!(suspended, vec![expected_runtimes[1].clone()]);
+    }
+
+    #[test]
+    fn test_registry_state() {
+        let mut mkvs = Tree::builder()
+            .with_root_type(RootType::State)
+            .build(Box::new(NoopReadSyncer));
+
+        let entity = Entity {
+            v: 1,
+            id: PublicKey::from(vec![1; 32]),
+            nodes: vec![PublicKey::from(vec![2; 32])],
+        };
+        let signed = Signed {
+            blob: cbor::to_vec(entity.clone()),
+            ..Default::default()
+        };
+        mkvs.insert(
+            &SignedEntityKeyFmt(Hash::digest_bytes(entity.id.as_ref())).encode(),
+            &cbor::to_vec(signed),
+        )
+        .unwrap();
+
+        let runtime_id =
+            Namespace::from("8000000000000000000000000000000000000000000000000000000000000010");
+        let status = NodeStatus {
+            election_eligible_after: 5,
+            faults: BTreeMap::from([(
+                runtime_id,
+                Fault {
+                    failures: 2,
+                    suspended_until: 14,
+                },
+            )]),
+            ..Default::default()
+        };
+        mkvs.insert(
+            &NodeStatusKeyFmt(Hash::digest_bytes(entity.nodes[0].as_ref())).encode(),
+            &cbor::to_vec(status.clone()),
+        )
+        .unwrap();
+
+        let registry_state = ImmutableState::new(&mkvs);
+
+        // Test entities.
+        let result = registry_state
+            .entity(&entity.id)
+            .expect("entity query should work");
+        assert_eq!(Some(entity.clone()), result, "entity should match");
+        let result = registry_state
+            .entity(&entity.nodes[0])
+            .expect("entity query should work");
+        assert_eq!(None, result, "entity should be missing");
+        let result = registry_state
+            .entities()
+            .expect("entities query should work");
+        assert_eq!(vec![entity.clone()], result, "entities should match");
+
+        // Test node status.
+        let result = registry_state
+            .node_status(&entity.nodes[0])
+            .expect("node status query should work")
+            .expect("node status should exist");
+        assert_eq!(status, result, "node status should match");
+        assert!(!result.is_frozen());
+        assert!(result.is_suspended(&runtime_id, 13));
+        assert!(!result.is_suspended(&runtime_id, 14));
+        assert!(!result.is_suspended(&Namespace::default(), 13));
+        let result = registry_state
+            .node_status(&entity.id)
+            .expect("node status query should work");
+        assert_eq!(None, result, "node status should be missing");
     }
 }
@@ -0,0 +1,373 @@
+//! Scheduler state in the consensus layer.
+use std::collections::BTreeMap;
+
+use anyhow::anyhow;
+
+use crate::{
+    common::{
+        crypto::{hash::Hash, signature::PublicKey},
+        key_format::{KeyFormat, KeyFormatAtom},
+        namespace::Namespace,
+    },
+    consensus::{
+        scheduler::{Committee, CommitteeKind, Validator},
+        state::StateError,
+    },
+    key_format,
+    storage::mkvs::{FallibleMKVS, ImmutableMKVS},
+};
+
+/// Consensus scheduler state wrapper.
+pub struct ImmutableState<'a, T: ImmutableMKVS> {
+    mkvs: &'a T,
+}
+
+impl<'a, T: ImmutableMKVS> ImmutableState<'a, T> {
+    /// Constructs a new ImmutableMKVS.
+    pub fn new(mkvs: &'a T) -> ImmutableState<'a, T> {
+        ImmutableState { mkvs }
+    }
+}
+
+key_format!(CommitteeKeyFmt, 0x60, (u8, Hash));
+key_format!(ValidatorsCurrentKeyFmt, 0x61, ());
+key_format!(ValidatorsPendingKeyFmt, 0x62, ());
+
+impl<'a, T: ImmutableMKVS> ImmutableState<'a, T> {
+    /// Returns the currently elected committee of the given kind for the given runtime.
+    pub fn committee(
+        &self,
+        kind: CommitteeKind,
+        runtime_id: &Namespace,
+    ) -> Result<Option<Committee>, StateError> {
+        let h = Hash::digest_bytes(runtime_id.as_ref());
+        match self.mkvs.get(&CommitteeKeyFmt((kind as u8, h)).encode()) {
+            Ok(Some(b)) => Ok(Some(
+                cbor::from_slice(&b).map_err(|err| StateError::Unavailable(anyhow!(err)))?,
+            )),
+            Ok(None) => Ok(None),
+            Err(err) => Err(StateError::Unavailable(anyhow!(err))),
+        }
+    }
+
+    /// Returns all currently elected committees of the given kind.
+    pub fn kind_committees(&self, kind: CommitteeKind) -> Result<Vec<Committee>, StateError> {
+        let kind = kind as u8;
+        let mut it = self.mkvs.iter();
+        it.seek(&CommitteeKeyFmt((kind, Default::default())).encode_partial(1));
+
+        let mut result: Vec<Committee> = Vec::new();
+
+        for value in it.map_while(|(key, value)| {
+            CommitteeKeyFmt::decode(&key)
+                .filter(|CommitteeKeyFmt((k, _))| *k == kind)
+                .map(|_| value)
+        }) {
+            result.push(
+                cbor::from_slice(&value).map_err(|err| StateError::Unavailable(anyhow!(err)))?,
+            );
+        }
+
+        Ok(result)
+    }
+
+    /// Returns all currently elected committees.
+    pub fn committees(&self) -> Result<Vec<Committee>, StateError> {
+        let mut it = self.mkvs.iter();
+        it.seek(&CommitteeKeyFmt::default().encode_partial(0));
+
+        let mut result: Vec<Committee> = Vec::new();
+
+        for value in it.map_while(|(key, value)| CommitteeKeyFmt::decode(&key).map(|_| value)) {
+            result.push(
+                cbor::from_slice(&value).map_err(|err| StateError::Unavailable(anyhow!(err)))?,
+            );
+        }
+
+        Ok(result)
+    }
+
+    fn validators<K: KeyFormat>(
+        &self,
+        key_format: K,
+    ) -> Result<BTreeMap<PublicKey, Validator>, StateError> {
+        match self.mkvs.get(&key_format.encode()) {
+            Ok(Some(b)) => {
+                cbor::from_slice(&b).map_err(|err| StateError::Unavailable(anyhow!(err)))
+            }
+            Ok(None) => Ok(BTreeMap::new()),
+            Err(err) => Err(StateError::Unavailable(anyhow!(err))),
+        }
+    }
+
+    /// Returns the current validator set.
+    pub fn current_validators(&self) -> Result<BTreeMap<PublicKey, Validator>, StateError> {
+        self.validators(ValidatorsCurrentKeyFmt(()))
+    }
+
+    /// Returns the pending validator set which will become the current validator set at the
+    /// next epoch transition, if any.
+    pub fn pending_validators(&self) -> Result<BTreeMap<PublicKey, Validator>, StateError> {
+        self.validators(ValidatorsPendingKeyFmt(()))
+    }
+}
+
+/// Mutable consensus scheduler state wrapper.
+pub struct MutableState;
+
+impl MutableState {
+    /// Set an elected committee for a specific runtime.
+    pub fn put_committee<S: FallibleMKVS>(
+        mkvs: &mut S,
+        committee: &Committee,
+    ) -> Result<(), StateError> {
+        let h = Hash::digest_bytes(committee.runtime_id.as_ref());
+        mkvs.insert(
+            &CommitteeKeyFmt((committee.kind.clone() as u8, h)).encode(),
+            &cbor::to_vec(committee.clone()),
+        )?;
+        Ok(())
+    }
+
+    /// Set the current validator set.
+    pub fn put_current_validators<S: FallibleMKVS>(
+        mkvs: &mut S,
+        validators: BTreeMap<PublicKey, Validator>,
+    ) -> Result<(), StateError> {
+        mkvs.insert(
+            &ValidatorsCurrentKeyFmt(()).encode(),
+            &cbor::to_vec(validators),
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        consensus::scheduler::{CommitteeNode, Role},
+        storage::mkvs::{
+            interop::{Fixture, ProtocolServer},
+            sync::NoopReadSyncer,
+            Root, RootType, Tree,
+        },
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_mutable_state() {
+        let mut mkvs = Tree::builder()
+            .with_root_type(RootType::State)
+            .build(Box::new(NoopReadSyncer));
+
+        let runtime_id =
+            Namespace::from("8000000000000000000000000000000000000000000000000000000000000010");
+        let committee = Committee {
+            kind: CommitteeKind::ComputeExecutor,
+            members: vec![
+                CommitteeNode {
+                    role: Role::Worker,
+                    public_key: PublicKey::from(vec![1; 32]),
+                },
+                CommitteeNode {
+                    role: Role::BackupWorker,
+                    public_key: PublicKey::from(vec![2; 32]),
+                },
+            ],
+            runtime_id,
+            valid_for: 42,
+        };
+        MutableState::put_committee(&mut mkvs, &committee).unwrap();
+
+        let validator = Validator {
+            id: PublicKey::from(vec![3; 32]),
+            entity_id: PublicKey::from(vec![4; 32]),
+            voting_power: 10,
+        };
+        let validators: BTreeMap<_, _> = vec![(validator.id, validator)].into_iter().collect();
+        MutableState::put_current_validators(&mut mkvs, validators.clone()).unwrap();
+
+        let scheduler_state = ImmutableState::new(&mkvs);
+
+        // Test committees.
+        let result = scheduler_state
+            .committee(CommitteeKind::ComputeExecutor, &runtime_id)
+            .expect("committee query should work");
+        assert_eq!(Some(committee.clone()), result, "committee should match");
+        let result = scheduler_state
+            .committee(CommitteeKind::ComputeExecutor, &Namespace::default())
+            .expect("committee query should work");
+        assert_eq!(None, result, "committee should be missing");
+        let result = scheduler_state
+            .kind_committees(CommitteeKind::ComputeExecutor)
+            .expect("kind committees query should work");
+        assert_eq!(vec![committee.clone()], result, "committees should match");
+        let result = scheduler_state
+            .committees()
+            .expect("committees query should work");
+        assert_eq!(vec![committee], result, "committees should match");
+
+        // Test validators.
+        let result = scheduler_state
+            .current_validators()
+            .expect("current validators query should work");
+        assert_eq!(validators, result, "validators should match");
+        let result = scheduler_state
+            .pending_validators()
+            .expect("pending validators query should work");
+        assert!(result.is_empty(), "there should be no pending validators");
+    }
+
+    #[test]
+    fn test_scheduler_state_interop() {
+        // Keep in sync with go/consensus/cometbft/apps/scheduler/state/interop/interop.go.
+        // If mock consensus state changes, update the root hash bellow.
+        // See protocol server stdout for hash.
+        // To make the hash show up during tests, run "cargo test" as
+        // "cargo test -- --nocapture".
+
+        // Setup protocol server with initialized mock consensus state.
+        let server = ProtocolServer::new(Fixture::ConsensusSchedulerMock.into());
+        let mock_consensus_root = Root {
+            version: 1,
+            root_type: RootType::State,
+            hash: Hash::from("c1b09a1a83134e21588e431d616cd3709b20d67e475f0d5b833e4e04d5255796"),
+            ..Default::default()
+        };
+        let mkvs = Tree::builder()
+            .with_capacity(100_000, 10_000_000)
+            .with_root(mock_consensus_root)
+            .build(server.read_sync());
+        let scheduler_state = ImmutableState::new(&mkvs);
+
+        let node1 =
+            PublicKey::from("7e57baaad01fffffffffffffffffffffffffffffffffffffffffffffffffffff");
+        let node2 =
+            PublicKey::from("7e57baaad02fffffffffffffffffffffffffffffffffffffffffffffffffffff");
+        let node3 =
+            PublicKey::from("7e57baaad03fffffffffffffffffffffffffffffffffffffffffffffffffffff");
+        let entity1 =
+            PublicKey::from("7e57baaade1fffffffffffffffffffffffffffffffffffffffffffffffffffff");
+        let entity2 =
+            PublicKey::from("7e57baaade2fffffffffffffffffffffffffffffffffffffffffffffffffffff");
+        let runtime_id =
+            Namespace::from("8000000000000000000000000000000000000000000000000000000000000010");
+        let runtime_id2 =
+            Namespace::from("8000000000000000000000000000000000000000000000000000000000000011");
+
+        // Test committees.
+        let expected_committees = vec![
+            Committee {
+                kind: CommitteeKind::ComputeExecutor,
+                members: vec![
+                    CommitteeNode {
+                        role: Role::Worker,
+                        public_key: node1,
+                    },
+                    CommitteeNode {
+                        role: Role::Worker,
+                        public_key: node2,
+                    },
+                    CommitteeNode {
+                        role: Role::BackupWorker,
+                        public_key: node3,
+                    },
+                ],
+                runtime_id,
+                valid_for: 42,
+            },
+            Committee {
+                kind: CommitteeKind::ComputeExecutor,
+                members: vec![
+                    CommitteeNode {
+                        role: Role::Worker,
+                        public_key: node2,
+                    },
+                    CommitteeNode {
+                        role: Role::BackupWorker,
+                        public_key: node1,
+                    },
+                ],
+                runtime_id: runtime_id2,
+                valid_for: 43,
+            },
+        ];
+        for expected in &expected_committees {
+            let committee = scheduler_state
+                .committee(CommitteeKind::ComputeExecutor, &expected.runtime_id)
+                .expect("committee query should work");
+            assert_eq!(
+                Some(expected.clone()),
+                committee,
+                "expected committee should match"
+            );
+        }
+        let committee = scheduler_state
+            .committee(CommitteeKind::ComputeExecutor, &Namespace::default())
+            .expect("committee query should work");
+        assert_eq!(None, committee, "committee should be missing");
+
+        // Committees are ordered by the hash of their runtime identifier.
+        let committees = scheduler_state
+            .kind_committees(CommitteeKind::ComputeExecutor)
+            .expect("kind committees query should work");
+        assert_eq!(
+            expected_committees, committees,
+            "expected committees should match"
+        );
+        let committees = scheduler_state
+            .kind_committees(CommitteeKind::Invalid)
+            .expect("kind committees query should work");
+        assert!(committees.is_empty(), "there should be no committees");
+        let committees = scheduler_state
+            .committees()
+            .expect("committees query should work");
+        assert_eq!(
+            expected_committees, committees,
+            "expected committees should match"
+        );
+
+        // Test validators.
+        let validators = scheduler_state
+            .current_validators()
+            .expect("current validators query should work");
+        let expected_validators = BTreeMap::from([
+            (
+                node1,
+                Validator {
+                    id: node1,
+                    entity_id: entity1,
+                    voting_power: 10,
+                },
+            ),
+            (
+                node2,
+                Validator {
+                    id: node2,
+                    entity_id: entity2,
+                    voting_power: 20,
+                },
+            ),
+        ]);
+        assert_eq!(
+            expected_validators, validators,
+            "expected current validators should match"
+        );
+        let validators = scheduler_state
+            .pending_validators()
+            .expect("pending validators query should work");
+        let expected_validators = BTreeMap::from([(
+            node3,
+            Validator {
+                id: node3,
+                entity_id: entity1,
+                voting_power: 5,
+            },
+        )]);
+        assert_eq!(
+            expected_validators, validators,
+            "expected pending validators should match"
+        );
+    }
+}
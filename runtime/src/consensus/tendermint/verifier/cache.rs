@@ -12,6 +12,8 @@ pub struct Cache {
     pub last_verified_epoch: u64,
     pub last_verified_block: Option<TMLightBlock>,
     pub verified_state_roots: lru::LruCache<u64, (Hash, u64)>,
+    pub verified_historical_blocks: lru::LruCache<u64, TMLightBlock>,
+    pub cross_checked_blocks: lru::LruCache<u64, tendermint::Hash>,
     pub host_node_id: PublicKey,
 }
 
@@ -23,6 +25,15 @@ impl Cache {
             .map(|b| b.signed_header.header.height.value())
     }
 
+    /// Closest verified historical block above the given height, if any.
+    pub fn historical_block_above(&self, height: u64) -> Option<TMLightBlock> {
+        self.verified_historical_blocks
+            .iter()
+            .filter(|(&h, _)| h > height)
+            .min_by_key(|(&h, _)| h)
+            .map(|(_, b)| b.clone())
+    }
+
     /// Process a new verified consensus layer block and update the cache if needed.
     pub fn update_verified_block(&mut self, verified_block: &TMLightBlock) {
         let h = |b: &TMLightBlock| -> Height { b.signed_header.header.height };
@@ -44,6 +55,8 @@ impl Cache {
             last_verified_epoch: 0,
             last_verified_block: None,
             verified_state_roots: lru::LruCache::new(NonZeroUsize::new(128).unwrap()),
+            verified_historical_blocks: lru::LruCache::new(NonZeroUsize::new(128).unwrap()),
+            cross_checked_blocks: lru::LruCache::new(NonZeroUsize::new(128).unwrap()),
             host_node_id,
         }
     }
@@ -10,8 +10,9 @@ use crate::{
         roothash::Header,
         state::ConsensusState,
         tendermint::decode_light_block,
-        verifier::{self, Error},
-        Event, LightBlock,
+        transaction::SignedTransactionWithProof,
+        verifier::{self, Error, TransactionInclusion},
+        BlockMetadata, Event, LightBlock,
     },
     protocol::Protocol,
     types::EventKind,
@@ -114,6 +115,27 @@ impl verifier::Verifier for Handle {
         receiver.await.map_err(|_| Error::Internal)?
     }
 
+    async fn block_metadata_at(&self, height: u64) -> Result<BlockMetadata, Error> {
+        let (sender, receiver) = oneshot::channel();
+        self.command_sender
+            .send(Command::BlockMetadataAt(height, sender))
+            .map_err(|_| Error::Internal)?;
+
+        receiver.await.map_err(|_| Error::Internal)?
+    }
+
+    async fn transaction_inclusion(
+        &self,
+        tx: SignedTransactionWithProof,
+    ) -> Result<TransactionInclusion, Error> {
+        let (sender, receiver) = oneshot::channel();
+        self.command_sender
+            .send(Command::TransactionInclusion(tx, sender))
+            .map_err(|_| Error::Internal)?;
+
+        receiver.await.map_err(|_| Error::Internal)?
+    }
+
     async fn latest_height(&self) -> Result<u64, Error> {
         let (sender, receiver) = oneshot::channel();
         self.command_sender
@@ -13,7 +13,7 @@ use crate::{
     consensus::{
         tendermint::{decode_light_block, LightBlockMeta},
         transaction::SignedTransactionWithProof,
-        HEIGHT_LATEST,
+        LightBlock, HEIGHT_LATEST,
     },
     protocol::Protocol,
     types::Body,
@@ -32,7 +32,7 @@ impl Io {
         }
     }
 
-    fn fetch_light_block(&self, height: u64) -> Result<LightBlockMeta, IoError> {
+    pub fn fetch_light_block(&self, height: u64) -> Result<LightBlockMeta, IoError> {
         let result = self
             .protocol
             .call_host(Body::HostFetchConsensusBlockRequest { height })
@@ -51,6 +51,19 @@ impl Io {
         Ok(block)
     }
 
+    pub fn fetch_witness_light_block(&self, height: u64) -> Result<LightBlock, IoError> {
+        let result = self
+            .protocol
+            .call_host(Body::HostFetchWitnessBlockRequest { height })
+            .map_err(|err| IoError::rpc(RpcError::server(err.to_string())))?;
+
+        // Extract generic light block from response.
+        match result {
+            Body::HostFetchWitnessBlockResponse { block } => Ok(block),
+            _ => Err(IoError::rpc(RpcError::server("bad response".to_string()))),
+        }
+    }
+
     pub fn fetch_genesis_height(&self) -> Result<u64, IoError> {
         let result = self
             .protocol
@@ -0,0 +1,261 @@
+//! Verification of consensus transactions and block metadata against verified block headers.
+use anyhow::anyhow;
+use sha2::{Digest, Sha256};
+use tendermint::{block::Header as TMHeader, merkle::HASH_SIZE, Hash as TMHash};
+
+use crate::{
+    common::crypto::hash::Hash,
+    consensus::{
+        tendermint::merkle,
+        transaction::{Proof, SignedTransaction, Transaction},
+        verifier::Error,
+        BlockMetadata, Event, METHOD_META,
+    },
+};
+
+/// Verify that the given signed transaction was included in the block with the given header.
+pub(super) fn verify_transaction(
+    header: &TMHeader,
+    chain_context: &str,
+    signed_tx: &SignedTransaction,
+    proof: &Proof,
+) -> Result<Transaction, Error> {
+    // Verify the signature.
+    if !signed_tx.verify(&chain_context.to_string()) {
+        return Err(Error::TransactionVerificationFailed(anyhow!(
+            "failed to verify the signature"
+        )));
+    }
+
+    if header.height.value() != proof.height {
+        return Err(Error::TransactionVerificationFailed(anyhow!(
+            "invalid block"
+        )));
+    }
+
+    let root_hash = header
+        .data_hash
+        .ok_or_else(|| Error::TransactionVerificationFailed(anyhow!("root hash not found")))?;
+    let root_hash = match root_hash {
+        TMHash::Sha256(hash) => hash,
+        TMHash::None => {
+            return Err(Error::TransactionVerificationFailed(anyhow!(
+                "root hash not found"
+            )));
+        }
+    };
+
+    // Compute hash of the transaction.
+    let digest = Sha256::digest(cbor::to_vec(signed_tx.clone()));
+    let mut tx_hash = [0u8; HASH_SIZE];
+    tx_hash.copy_from_slice(&digest);
+
+    // Decode raw proof as a CometBFT Merkle proof of inclusion.
+    let merkle_proof: merkle::Proof = cbor::from_slice(&proof.raw_proof).map_err(|err| {
+        Error::TransactionVerificationFailed(anyhow!("failed to decode Merkle proof: {}", err))
+    })?;
+
+    merkle_proof.verify(root_hash, tx_hash).map_err(|err| {
+        Error::TransactionVerificationFailed(anyhow!("failed to verify Merkle proof: {}", err))
+    })?;
+
+    // Decode transaction.
+    let tx: Transaction = cbor::from_slice(signed_tx.blob.as_slice()).map_err(|err| {
+        Error::TransactionVerificationFailed(anyhow!("failed to decode transaction: {}", err))
+    })?;
+
+    Ok(tx)
+}
+
+/// Decode block metadata from the given verified block metadata transaction.
+pub(super) fn decode_block_metadata(tx: Transaction) -> Result<BlockMetadata, Error> {
+    if tx.method != METHOD_META {
+        return Err(Error::StateRoot(anyhow!("invalid method name")));
+    }
+
+    cbor::from_value(tx.body).map_err(|err| {
+        Error::StateRoot(anyhow!(
+            "failed to decode block metadata transaction: {}",
+            err
+        ))
+    })
+}
+
+/// Return the provable events emitted by the given signed transaction.
+pub(super) fn transaction_events(events: Vec<Event>, signed_tx: &SignedTransaction) -> Vec<Event> {
+    let tx_hash = Hash::digest_bytes(&cbor::to_vec(signed_tx.clone()));
+
+    events
+        .into_iter()
+        .filter(|ev| matches!(ev, Event::Staking(ev) if ev.tx_hash == tx_hash))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use tendermint_testgen::{Generator, Header, LightBlock as TestgenLightBlock, Validator};
+
+    use crate::{
+        common::crypto::signature::{
+            signature_context_with_chain_separation, PrivateKey, SignatureBundle, Signer,
+        },
+        consensus::{
+            registry, staking,
+            tendermint::chain_id,
+            transaction::{SignedTransactionWithProof, SIGNATURE_CONTEXT},
+        },
+    };
+
+    use super::*;
+
+    const CHAIN_CONTEXT: &str = "test-chain-0000000000000000000000000000000000000000000000000000";
+
+    fn sign_transaction(tx: Transaction) -> SignedTransaction {
+        let signer = PrivateKey::from_test_seed("metadata test".to_string());
+        let blob = cbor::to_vec(tx);
+        let context = signature_context_with_chain_separation(
+            SIGNATURE_CONTEXT.to_vec(),
+            &CHAIN_CONTEXT.to_string(),
+        );
+        let signature = signer.sign(&context, &blob).unwrap();
+
+        SignedTransaction {
+            blob,
+            signature: SignatureBundle {
+                public_key: signer.public(),
+                signature,
+            },
+        }
+    }
+
+    /// Generate a block header at the given height for a block containing only the given
+    /// transaction together with the transaction's inclusion proof.
+    fn generate_block(
+        height: u64,
+        signed_tx: SignedTransaction,
+    ) -> (TMHeader, SignedTransactionWithProof) {
+        let tx_hash = Sha256::digest(cbor::to_vec(signed_tx.clone()));
+        let mut leaf_hash = [0u8; HASH_SIZE];
+        leaf_hash.copy_from_slice(&Sha256::digest([&[0x00], tx_hash.as_slice()].concat()));
+
+        let validators = [Validator::new("1"), Validator::new("2")];
+        let header = Header::new(&validators)
+            .height(height)
+            .chain_id(chain_id(CHAIN_CONTEXT).as_str())
+            .next_validators(&validators);
+        let mut lb = TestgenLightBlock::new_default_with_header(header)
+            .generate()
+            .unwrap();
+        lb.signed_header.header.data_hash = Some(TMHash::Sha256(leaf_hash));
+
+        let proof = merkle::Proof {
+            total: 1,
+            index: 0,
+            leaf_hash,
+            aunts: vec![],
+        };
+        let stwp = SignedTransactionWithProof {
+            signed_tx,
+            proof: Proof {
+                height,
+                raw_proof: cbor::to_vec(proof),
+            },
+        };
+
+        (lb.signed_header.header, stwp)
+    }
+
+    fn verify_block_metadata(
+        header: &TMHeader,
+        chain_context: &str,
+        stwp: &SignedTransactionWithProof,
+    ) -> Result<BlockMetadata, Error> {
+        let tx = verify_transaction(header, chain_context, &stwp.signed_tx, &stwp.proof)?;
+        decode_block_metadata(tx)
+    }
+
+    #[test]
+    fn test_verify_block_metadata() {
+        let meta = BlockMetadata {
+            state_root: Hash::digest_bytes(b"state"),
+            events_root: b"events".to_vec(),
+        };
+        let signed_tx = sign_transaction(Transaction {
+            nonce: 0,
+            fee: None,
+            method: METHOD_META.to_string(),
+            body: cbor::to_value(meta.clone()),
+        });
+        let (header, stwp) = generate_block(10, signed_tx);
+
+        // Valid metadata.
+        let verified =
+            verify_block_metadata(&header, CHAIN_CONTEXT, &stwp).expect("metadata should verify");
+        assert_eq!(verified, meta);
+
+        // Wrong chain context.
+        let other_context = "other-chain-000000000000000000000000000000000000000000000000000";
+        let result = verify_block_metadata(&header, other_context, &stwp);
+        assert!(result.is_err(), "wrong chain context should fail");
+
+        // Proof for a different block.
+        let (other_header, _) = generate_block(11, stwp.signed_tx.clone());
+        let result = verify_block_metadata(&other_header, CHAIN_CONTEXT, &stwp);
+        assert!(result.is_err(), "proof for a different block should fail");
+
+        // Transaction not included in the block.
+        let signed_tx = sign_transaction(Transaction {
+            nonce: 1,
+            fee: None,
+            method: METHOD_META.to_string(),
+            body: cbor::to_value(meta.clone()),
+        });
+        let (_, other_stwp) = generate_block(10, signed_tx);
+        let result = verify_block_metadata(&header, CHAIN_CONTEXT, &other_stwp);
+        assert!(result.is_err(), "transaction not in block should fail");
+
+        // Regular transactions can be verified but are not block metadata.
+        let signed_tx = sign_transaction(Transaction {
+            nonce: 0,
+            fee: None,
+            method: "staking.Transfer".to_string(),
+            body: cbor::Value::Simple(cbor::SimpleValue::NullValue),
+        });
+        let (header, stwp) = generate_block(10, signed_tx);
+        let tx = verify_transaction(&header, CHAIN_CONTEXT, &stwp.signed_tx, &stwp.proof)
+            .expect("transaction should verify");
+        assert_eq!(tx.method, "staking.Transfer");
+        let result = verify_block_metadata(&header, CHAIN_CONTEXT, &stwp);
+        assert!(result.is_err(), "non-metadata transaction should fail");
+    }
+
+    #[test]
+    fn test_transaction_events() {
+        let signed_tx = sign_transaction(Transaction {
+            nonce: 0,
+            fee: None,
+            method: "staking.Burn".to_string(),
+            body: cbor::Value::Simple(cbor::SimpleValue::NullValue),
+        });
+        let tx_hash = Hash::digest_bytes(&cbor::to_vec(signed_tx.clone()));
+        let event = |tx_hash: Hash| {
+            Event::Staking(staking::Event {
+                tx_hash,
+                burn: Some(staking::BurnEvent::default()),
+                ..Default::default()
+            })
+        };
+        let events = vec![
+            event(Hash::empty_hash()),
+            event(tx_hash),
+            Event::Registry(registry::Event::default()),
+            event(tx_hash),
+        ];
+
+        let events = transaction_events(events, &signed_tx);
+        assert_eq!(events.len(), 2);
+        assert!(events
+            .iter()
+            .all(|ev| matches!(ev, Event::Staking(ev) if ev.tx_hash == tx_hash)));
+    }
+}
@@ -3,10 +3,9 @@ use std::{convert::TryInto, str::FromStr, sync::Arc, time::Duration};
 
 use anyhow::anyhow;
 use crossbeam::channel;
+use lazy_static::lazy_static;
 use rand::{rngs::OsRng, Rng};
-use sha2::{Digest, Sha256};
 use slog::{debug, error, info};
-use tendermint::merkle::HASH_SIZE;
 use tendermint_light_client::{
     builder::LightClientBuilder,
     components::{self, io::AtHeight, verifier::PredicateVerifier},
@@ -22,14 +21,20 @@ use tendermint_light_client::{
 };
 
 use crate::{
-    common::{logger::get_logger, namespace::Namespace, process, time, version::Version},
+    common::{
+        logger::get_logger,
+        metrics::{self, Counter, Gauge},
+        namespace::Namespace,
+        process, time,
+        version::Version,
+    },
     consensus::{
         beacon::EpochTime,
         registry::METHOD_PROVE_FRESHNESS,
         roothash::Header,
         state::ConsensusState,
         tendermint::{
-            chain_id, decode_light_block, merkle, state_root_from_header,
+            chain_id, decode_light_block, state_root_from_header,
             verifier::{
                 clock::InsecureClock,
                 io::Io,
@@ -38,9 +43,9 @@ use crate::{
             },
             LightBlockMeta,
         },
-        transaction::{Proof, SignedTransaction, Transaction},
-        verifier::{self, verify_state_freshness, Error, TrustRoot},
-        BlockMetadata, Event, LightBlock, HEIGHT_LATEST, METHOD_META,
+        transaction::{Proof, SignedTransaction, SignedTransactionWithProof, Transaction},
+        verifier::{self, verify_state_freshness, Error, TransactionInclusion, TrustRoot},
+        BlockMetadata, Event, LightBlock, HEIGHT_LATEST,
     },
     future::block_on,
     host::Host,
@@ -60,14 +65,19 @@ mod cache;
 mod clock;
 mod handle;
 mod io;
+mod metadata;
 mod noop;
 mod predicates;
+mod proof;
 mod signature;
 mod store;
 mod types;
+mod witness;
 
 // Re-exports.
 pub use noop::NopVerifier;
+pub use proof::{verify_consensus_state_proof, ConsensusStateProof};
+pub use witness::{EvidenceWitness, HostWitness, Witness};
 
 /// Maximum number of times to retry initialization.
 const MAX_INITIALIZATION_RETRIES: usize = 3;
@@ -75,6 +85,23 @@ const MAX_INITIALIZATION_RETRIES: usize = 3;
 /// Trusted state save interval (in consensus blocks).
 const TRUSTED_STATE_SAVE_INTERVAL: u64 = 128;
 
+/// Interval (in consensus blocks) at which blocks verified backwards are cached as checkpoints.
+const BACKWARD_CHECKPOINT_INTERVAL: u64 = 100;
+
+/// Maximum number of blocks verified backwards when serving a single request.
+const MAX_BACKWARD_STEPS: u64 = 10_000;
+
+lazy_static! {
+    static ref VERIFIED_HEIGHT: Gauge = metrics::gauge(
+        "runtime_consensus_verifier_height",
+        "Height of the last verified consensus block.",
+    );
+    static ref VERIFICATION_FAILURES: Counter = metrics::counter(
+        "runtime_consensus_verifier_failures",
+        "Number of failed consensus light block verifications.",
+    );
+}
+
 /// Tendermint consensus layer verifier.
 pub struct Verifier {
     logger: slog::Logger,
@@ -83,7 +110,8 @@ pub struct Verifier {
     runtime_version: Version,
     runtime_id: Namespace,
     chain_context: String,
-    trust_root: TrustRoot,
+    trust_roots: Vec<TrustRoot>,
+    witnesses: Vec<Arc<dyn Witness>>,
     command_sender: channel::Sender<Command>,
     command_receiver: channel::Receiver<Command>,
     trusted_state_store: TrustedStateStore,
@@ -94,33 +122,35 @@ impl Verifier {
     pub fn new(
         protocol: Arc<Protocol>,
         tokio_runtime: tokio::runtime::Handle,
-        trust_root: TrustRoot,
+        trust_roots: Vec<TrustRoot>,
         runtime_id: Namespace,
         chain_context: String,
-    ) -> Self {
+    ) -> Result<Self, Error> {
+        validate_trust_roots(&trust_roots, &runtime_id)?;
+
         let logger = get_logger("consensus/cometbft/verifier");
         let (command_sender, command_receiver) = channel::unbounded();
         let runtime_version = protocol.get_config().version;
+        let mut witnesses = protocol.get_config().witnesses.clone();
+        if protocol.get_config().host_witness {
+            witnesses.push(Arc::new(HostWitness::new(protocol.clone())));
+        }
         let trusted_state_store =
             TrustedStateStore::new(runtime_id, chain_context.clone(), protocol.clone());
 
-        assert_eq!(
-            trust_root.runtime_id, runtime_id,
-            "trust root must have the same runtime id"
-        );
-
-        Self {
+        Ok(Self {
             logger,
             protocol,
             tokio_runtime,
             runtime_version,
             runtime_id,
             chain_context,
-            trust_root,
+            trust_roots,
+            witnesses,
             command_sender,
             command_receiver,
             trusted_state_store,
-        }
+        })
     }
 
     /// Return a handle to interact with the verifier.
@@ -137,23 +167,79 @@ impl Verifier {
         cache: &mut Cache,
         instance: &mut Instance,
     ) -> Result<TMLightBlock, Error> {
+        // Blocks below the lowest trusted block can only be verified backwards.
+        if let Some(lowest) = instance.state.light_store.lowest(Status::Trusted) {
+            if height != HEIGHT_LATEST && height < lowest.height().value() {
+                return self.verify_backward(height, cache, lowest);
+            }
+        }
+
         let verified_block = match height {
             HEIGHT_LATEST => instance.light_client.verify_to_highest(&mut instance.state),
             _ => instance
                 .light_client
                 .verify_to_target(height.try_into().unwrap(), &mut instance.state),
         }
-        .map_err(|err| Error::VerificationFailed(err.into()))?;
+        .map_err(|err| {
+            VERIFICATION_FAILURES.inc();
+            Error::VerificationFailed(err.into())
+        })?;
 
         // Clear verification trace as it could otherwise lead to infinite memory growth.
         instance.state.verification_trace.clear();
 
+        // Cross-check verified blocks with witnesses, if any.
+        witness::cross_check_once(
+            &self.logger,
+            &self.witnesses,
+            &mut cache.cross_checked_blocks,
+            &verified_block,
+        )?;
+
         cache.update_verified_block(&verified_block);
+        VERIFIED_HEIGHT.set(verified_block.signed_header.header.height.value() as i64);
         self.update_insecure_posix_time(&verified_block);
 
         Ok(verified_block)
     }
 
+    /// Verify the block at the given height by following the hash chain backwards from the
+    /// closest verified block above it.
+    ///
+    /// See [`verify_backward_chain`] for how the walk is bounded and cached.
+    fn verify_backward(
+        &self,
+        height: u64,
+        cache: &mut Cache,
+        lowest_trusted: TMLightBlock,
+    ) -> Result<TMLightBlock, Error> {
+        let io = Io::new(&self.protocol);
+        let verified_block = verify_backward_chain(
+            cache,
+            height,
+            lowest_trusted,
+            BACKWARD_CHECKPOINT_INTERVAL,
+            MAX_BACKWARD_STEPS,
+            |height| {
+                io.fetch_light_block(height)
+                    .map_err(|err| Error::VerificationFailed(err.into()))
+            },
+        )
+        .map_err(|err| {
+            VERIFICATION_FAILURES.inc();
+            err
+        })?;
+
+        witness::cross_check_once(
+            &self.logger,
+            &self.witnesses,
+            &mut cache.cross_checked_blocks,
+            &verified_block,
+        )?;
+
+        Ok(verified_block)
+    }
+
     fn sync(&self, cache: &mut Cache, instance: &mut Instance, height: u64) -> Result<(), Error> {
         if height < cache.last_verified_height || height < cache.latest_known_height().unwrap_or(0)
         {
@@ -318,13 +404,6 @@ impl Verifier {
         signed_tx: &SignedTransaction,
         proof: &Proof,
     ) -> Result<Transaction, Error> {
-        // Verify the signature.
-        if !signed_tx.verify(&self.chain_context) {
-            return Err(Error::TransactionVerificationFailed(anyhow!(
-                "failed to verify the signature"
-            )));
-        }
-
         // Fetch the root hash of a block in which the transaction was published.
         let verified_block = self
             .verify_to_target(proof.height, cache, instance)
@@ -332,45 +411,12 @@ impl Verifier {
                 Error::TransactionVerificationFailed(anyhow!("failed to fetch the block: {}", err))
             })?;
 
-        let header = verified_block.signed_header.header;
-        if header.height.value() != proof.height {
-            return Err(Error::TransactionVerificationFailed(anyhow!(
-                "invalid block"
-            )));
-        }
-
-        let root_hash = header
-            .data_hash
-            .ok_or_else(|| Error::TransactionVerificationFailed(anyhow!("root hash not found")))?;
-        let root_hash = match root_hash {
-            TMHash::Sha256(hash) => hash,
-            TMHash::None => {
-                return Err(Error::TransactionVerificationFailed(anyhow!(
-                    "root hash not found"
-                )));
-            }
-        };
-
-        // Compute hash of the transaction.
-        let digest = Sha256::digest(cbor::to_vec(signed_tx.clone()));
-        let mut tx_hash = [0u8; HASH_SIZE];
-        tx_hash.copy_from_slice(&digest);
-
-        // Decode raw proof as a CometBFT Merkle proof of inclusion.
-        let merkle_proof: merkle::Proof = cbor::from_slice(&proof.raw_proof).map_err(|err| {
-            Error::TransactionVerificationFailed(anyhow!("failed to decode Merkle proof: {}", err))
-        })?;
-
-        merkle_proof.verify(root_hash, tx_hash).map_err(|err| {
-            Error::TransactionVerificationFailed(anyhow!("failed to verify Merkle proof: {}", err))
-        })?;
-
-        // Decode transaction.
-        let tx: Transaction = cbor::from_slice(signed_tx.blob.as_slice()).map_err(|err| {
-            Error::TransactionVerificationFailed(anyhow!("failed to decode transaction: {}", err))
-        })?;
-
-        Ok(tx)
+        metadata::verify_transaction(
+            &verified_block.signed_header.header,
+            &self.chain_context,
+            signed_tx,
+            proof,
+        )
     }
 
     /// Fetch state root from block metadata transaction.
@@ -385,6 +431,23 @@ impl Verifier {
             "Fetching state root from block metadata transaction"
         );
 
+        let meta = self.block_metadata_at(cache, instance, height)?;
+
+        Ok(Root {
+            namespace: Namespace::default(),
+            version: height,
+            root_type: RootType::State,
+            hash: meta.state_root,
+        })
+    }
+
+    /// Fetch and verify block metadata of the block at the given height.
+    fn block_metadata_at(
+        &self,
+        cache: &mut Cache,
+        instance: &mut Instance,
+        height: u64,
+    ) -> Result<BlockMetadata, Error> {
         // Ask the host for block metadata transaction.
         let io = Io::new(&self.protocol);
         let stwp = io.fetch_block_metadata(height).map_err(|err| {
@@ -397,23 +460,7 @@ impl Verifier {
         // Verify the transaction and the proof.
         let tx = self.verify_transaction(cache, instance, &stwp.signed_tx, &stwp.proof)?;
 
-        if tx.method != METHOD_META {
-            return Err(Error::StateRoot(anyhow!("invalid method name")));
-        }
-
-        let meta: BlockMetadata = cbor::from_value(tx.body).map_err(|err| {
-            Error::StateRoot(anyhow!(
-                "failed to decode block metadata transaction: {}",
-                err
-            ))
-        })?;
-
-        Ok(Root {
-            namespace: Namespace::default(),
-            version: height,
-            root_type: RootType::State,
-            hash: meta.state_root,
-        })
+        metadata::decode_block_metadata(tx)
     }
 
     fn verify(
@@ -538,21 +585,55 @@ impl Verifier {
         Ok(state)
     }
 
-    fn events_at(&self, height: u64, kind: EventKind) -> Result<Vec<Event>, Error> {
+    fn events_at(
+        &self,
+        cache: &mut Cache,
+        instance: &mut Instance,
+        height: u64,
+        kind: EventKind,
+    ) -> Result<Vec<Event>, Error> {
+        // Make sure the block at the given height has been finalized and verified so that the host
+        // cannot make up events for heights that do not exist.
+        let verified_block = self.verify_to_target(height, cache, instance)?;
+        let height = verified_block.signed_header.header.height.value();
+
         let result = self
             .protocol
             .call_host(Body::HostFetchConsensusEventsRequest(
                 HostFetchConsensusEventsRequest { height, kind },
             ))
             .map_err(|err| Error::VerificationFailed(err.into()))?;
-        // TODO: Perform event verification once this becomes possible.
 
-        match result {
+        let events = match result {
             Body::HostFetchConsensusEventsResponse(HostFetchConsensusEventsResponse { events }) => {
-                Ok(events)
+                events
             }
-            _ => Err(Error::VerificationFailed(anyhow!("bad response from host"))),
+            _ => return Err(Error::VerificationFailed(anyhow!("bad response from host"))),
+        };
+
+        predicates::verify_events(&events, height, kind)?;
+
+        // Only staking events are provable. Events of other kinds are bound to a verified height
+        // but their contents are not authenticated.
+        if kind == EventKind::Staking {
+            let meta = self.block_metadata_at(cache, instance, height)?;
+            predicates::verify_events_root(&events, &meta.events_root)?;
         }
+
+        Ok(events)
+    }
+
+    fn transaction_inclusion(
+        &self,
+        cache: &mut Cache,
+        instance: &mut Instance,
+        stwp: SignedTransactionWithProof,
+    ) -> Result<TransactionInclusion, Error> {
+        let tx = self.verify_transaction(cache, instance, &stwp.signed_tx, &stwp.proof)?;
+        let events = self.events_at(cache, instance, stwp.proof.height, EventKind::Staking)?;
+        let events = metadata::transaction_events(events, &stwp.signed_tx);
+
+        Ok(TransactionInclusion { tx, events })
     }
 
     fn update_insecure_posix_time(&self, verified_block: &TMLightBlock) {
@@ -645,11 +726,15 @@ impl Verifier {
         let io = Box::new(Io::new(&self.protocol));
 
         // Build a light client using the embedded trust root or trust root
-        // stored in the local store.
+        // stored in the local store. On first start always use the newest
+        // embedded trust root, as the host could otherwise pick an older one
+        // by reporting an older chain context. Any chain context change is
+        // then only allowed to move forward.
         info!(self.logger, "Loading trusted state");
+        let trust_root = self.trust_roots.last().ok_or(Error::Internal)?;
         let trusted_state = self
             .trusted_state_store
-            .load(self.runtime_version, &self.trust_root);
+            .load(self.runtime_version, trust_root);
 
         let trusted_state: TrustedState = match trusted_state {
             Ok(state) => state,
@@ -778,7 +863,17 @@ impl Verifier {
                 }
                 Command::EventsAt(height, kind, sender) => {
                     sender
-                        .send(self.events_at(height, kind))
+                        .send(self.events_at(&mut cache, &mut instance, height, kind))
+                        .map_err(|_| Error::Internal)?;
+                }
+                Command::BlockMetadataAt(height, sender) => {
+                    sender
+                        .send(self.block_metadata_at(&mut cache, &mut instance, height))
+                        .map_err(|_| Error::Internal)?;
+                }
+                Command::TransactionInclusion(stwp, sender) => {
+                    sender
+                        .send(self.transaction_inclusion(&mut cache, &mut instance, stwp))
                         .map_err(|_| Error::Internal)?;
                 }
             }
@@ -811,6 +906,22 @@ impl Verifier {
         }
         info!(self.logger, "Consensus chain context has changed");
 
+        // Switch to the embedded trust root for the new chain context, if any.
+        if let Some(trust_root) = select_trust_root(
+            &self.trust_roots,
+            &trusted_state.trust_root,
+            &host_info.consensus_chain_context,
+        )? {
+            info!(self.logger, "Using embedded trust root for the new chain context";
+                "trust_root_height" => trust_root.height,
+                "trust_root_chain_context" => ?trust_root.chain_context,
+            );
+            return Ok(TrustedState {
+                trust_root,
+                trusted_blocks: vec![],
+            });
+        }
+
         // Chain context transition cannot be done directly from the embedded
         // trust root as we don't have access to the matching trusted light
         // block which validator set we need to verify blocks from the new chain.
@@ -819,7 +930,8 @@ impl Verifier {
             .pop()
             .ok_or_else(|| {
                 Error::ChainContextTransitionFailed(anyhow!(
-                    "cannot transition from embedded trust root"
+                    "no trust root for chain context {} and cannot transition from embedded trust root",
+                    host_info.consensus_chain_context,
                 ))
             })?
             .into();
@@ -923,3 +1035,308 @@ impl Verifier {
         })
     }
 }
+
+/// Validate the embedded trust roots.
+///
+/// There must be at least one trust root, all trust roots must be for the given runtime and each
+/// must be bound to a distinct chain context, ordered by strictly increasing heights.
+fn validate_trust_roots(trust_roots: &[TrustRoot], runtime_id: &Namespace) -> Result<(), Error> {
+    if trust_roots.is_empty() {
+        return Err(Error::Builder(anyhow!(
+            "at least one trust root is required"
+        )));
+    }
+
+    for (i, trust_root) in trust_roots.iter().enumerate() {
+        if &trust_root.runtime_id != runtime_id {
+            return Err(Error::Builder(anyhow!(
+                "trust root for chain context {} has runtime id {:?} instead of {:?}",
+                trust_root.chain_context,
+                trust_root.runtime_id,
+                runtime_id,
+            )));
+        }
+        if trust_roots[..i]
+            .iter()
+            .any(|root| root.chain_context == trust_root.chain_context)
+        {
+            return Err(Error::Builder(anyhow!(
+                "duplicate trust root for chain context {}",
+                trust_root.chain_context,
+            )));
+        }
+        if i > 0 && trust_root.height <= trust_roots[i - 1].height {
+            return Err(Error::Builder(anyhow!(
+                "trust root heights must be strictly increasing ({} after {})",
+                trust_root.height,
+                trust_roots[i - 1].height,
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Select the embedded trust root to switch to when the trusted state is for a different chain
+/// context than the given one.
+///
+/// Returns `None` in case no trust root is bound to the given chain context, in which case the
+/// trusted state must be transitioned to the new chain instead. Fails in case the given chain
+/// context precedes the chain context of the trusted state or, when the trusted chain context is
+/// not bound to any trust root, in case the selected trust root is below the trusted height, as
+/// that would move the verifier back to an older chain.
+fn select_trust_root(
+    trust_roots: &[TrustRoot],
+    trusted: &TrustRoot,
+    chain_context: &str,
+) -> Result<Option<TrustRoot>, Error> {
+    let position = |chain_context: &str| {
+        trust_roots
+            .iter()
+            .position(|root| root.chain_context == chain_context)
+    };
+
+    let precedes = || {
+        Err(Error::ChainContextTransitionFailed(anyhow!(
+            "chain context {} precedes trusted chain context {}",
+            chain_context,
+            trusted.chain_context,
+        )))
+    };
+
+    match (position(&trusted.chain_context), position(chain_context)) {
+        (Some(current), Some(target)) if target < current => precedes(),
+        (None, Some(target)) if trust_roots[target].height < trusted.height => precedes(),
+        (_, Some(target)) => Ok(Some(trust_roots[target].clone())),
+        (_, None) => Ok(None),
+    }
+}
+
+/// Verify the block at the given height by following the hash chain backwards from the closest
+/// verified historical block above it or, if there is none, from the lowest trusted block.
+///
+/// Every `checkpoint_interval` blocks the verified block is cached so that subsequent requests
+/// for nearby heights, as well as retries after a failed fetch, can resume from there. At most
+/// `max_steps` blocks are verified per call. In case the target is further away, the block where
+/// the walk stopped is cached and an error is returned, so that a retry continues from there.
+fn verify_backward_chain<F>(
+    cache: &mut Cache,
+    height: u64,
+    lowest_trusted: TMLightBlock,
+    checkpoint_interval: u64,
+    max_steps: u64,
+    mut fetch_light_block: F,
+) -> Result<TMLightBlock, Error>
+where
+    F: FnMut(u64) -> Result<LightBlockMeta, Error>,
+{
+    if let Some(block) = cache.verified_historical_blocks.get(&height) {
+        return Ok(block.clone());
+    }
+
+    let mut verified_block = cache
+        .historical_block_above(height)
+        .unwrap_or(lowest_trusted);
+    let start = verified_block.height().value();
+    let target = height.max(start.saturating_sub(max_steps));
+
+    for height in (target..start).rev() {
+        let untrusted_block = fetch_light_block(height)?;
+        verified_block = predicates::verify_backward_link(&verified_block, untrusted_block)?;
+
+        if height % checkpoint_interval == 0 {
+            cache
+                .verified_historical_blocks
+                .put(height, verified_block.clone());
+        }
+    }
+
+    cache
+        .verified_historical_blocks
+        .put(target, verified_block.clone());
+
+    if target != height {
+        return Err(Error::VerificationFailed(anyhow!(
+            "block at height {} is too far below verified height {}, stopped at height {}",
+            height,
+            start,
+            target,
+        )));
+    }
+
+    Ok(verified_block)
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+
+    use tendermint_testgen::{Generator, LightChain};
+
+    use crate::common::crypto::signature::PublicKey;
+
+    use super::*;
+
+    fn trust_root(height: u64, chain_context: &str) -> TrustRoot {
+        TrustRoot {
+            height,
+            chain_context: chain_context.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_validate_trust_roots() {
+        let runtime_id = Namespace::default();
+        let trust_roots = vec![
+            trust_root(10, "chain-a"),
+            trust_root(20, "chain-b"),
+            trust_root(30, "chain-c"),
+        ];
+        validate_trust_roots(&trust_roots, &runtime_id).expect("trust roots should be valid");
+
+        // No trust roots.
+        let result = validate_trust_roots(&[], &runtime_id);
+        assert!(
+            matches!(result, Err(Error::Builder(_))),
+            "empty trust roots should fail"
+        );
+
+        // Trust root for a different runtime.
+        let mut other = trust_root(40, "chain-d");
+        other.runtime_id =
+            Namespace::from("8000000000000000000000000000000000000000000000000000000000000010");
+        let result = validate_trust_roots(&[trust_root(10, "chain-a"), other], &runtime_id);
+        assert!(
+            matches!(result, Err(Error::Builder(_))),
+            "trust root with a different runtime id should fail"
+        );
+
+        // Duplicate chain contexts.
+        let result = validate_trust_roots(
+            &[trust_root(10, "chain-a"), trust_root(20, "chain-a")],
+            &runtime_id,
+        );
+        assert!(
+            matches!(result, Err(Error::Builder(_))),
+            "duplicate chain contexts should fail"
+        );
+
+        // Heights not strictly increasing.
+        let result = validate_trust_roots(
+            &[trust_root(20, "chain-a"), trust_root(20, "chain-b")],
+            &runtime_id,
+        );
+        assert!(
+            matches!(result, Err(Error::Builder(_))),
+            "equal heights should fail"
+        );
+        let result = validate_trust_roots(
+            &[trust_root(20, "chain-a"), trust_root(10, "chain-b")],
+            &runtime_id,
+        );
+        assert!(
+            matches!(result, Err(Error::Builder(_))),
+            "decreasing heights should fail"
+        );
+    }
+
+    #[test]
+    fn test_select_trust_root() {
+        let trust_roots = vec![
+            trust_root(10, "chain-a"),
+            trust_root(20, "chain-b"),
+            trust_root(30, "chain-c"),
+        ];
+
+        // Trusted state for a known older chain context.
+        let selected = select_trust_root(&trust_roots, &trust_root(15, "chain-a"), "chain-c")
+            .expect("selection should succeed");
+        assert_eq!(selected, Some(trust_roots[2].clone()));
+
+        // Trusted state for an unknown chain context (e.g. transitioned via genesis).
+        let selected = select_trust_root(&trust_roots, &trust_root(15, "chain-x"), "chain-b")
+            .expect("selection should succeed");
+        assert_eq!(selected, Some(trust_roots[1].clone()));
+
+        // Trusted state for an unknown chain context newer than the target trust root.
+        let result = select_trust_root(&trust_roots, &trust_root(25, "chain-x"), "chain-b");
+        assert!(
+            matches!(result, Err(Error::ChainContextTransitionFailed(_))),
+            "trust root below the trusted height should fail"
+        );
+
+        // No trust root for the new chain context.
+        let selected = select_trust_root(&trust_roots, &trust_root(35, "chain-c"), "chain-d")
+            .expect("selection should succeed");
+        assert_eq!(selected, None);
+
+        // Chain context moving backwards.
+        let result = select_trust_root(&trust_roots, &trust_root(35, "chain-c"), "chain-a");
+        assert!(
+            matches!(result, Err(Error::ChainContextTransitionFailed(_))),
+            "chain context moving backwards should fail"
+        );
+    }
+
+    #[test]
+    fn test_verify_backward_chain() {
+        let blocks: BTreeMap<u64, TMLightBlock> = LightChain::default_with_length(30)
+            .light_blocks
+            .into_iter()
+            .map(|lb| lb.generate().unwrap())
+            .map(|lb| TMLightBlock {
+                signed_header: lb.signed_header,
+                validators: lb.validators,
+                next_validators: lb.next_validators,
+                provider: lb.provider,
+            })
+            .map(|lb| (lb.height().value(), lb))
+            .collect();
+        let lowest_trusted = blocks[&30].clone();
+
+        let mut cache = Cache::new(PublicKey::default());
+        let mut fetched = Vec::new();
+        let verify = |cache: &mut Cache, fetched: &mut Vec<u64>, height: u64| {
+            fetched.clear();
+            verify_backward_chain(cache, height, lowest_trusted.clone(), 4, 10, |height| {
+                fetched.push(height);
+                if height == 12 {
+                    return Err(Error::VerificationFailed(anyhow!("unavailable")));
+                }
+                let block = &blocks[&height];
+                Ok(LightBlockMeta {
+                    signed_header: Some(block.signed_header.clone()),
+                    validators: block.validators.clone(),
+                })
+            })
+        };
+
+        // Nearby blocks should be verified directly.
+        let verified = verify(&mut cache, &mut fetched, 27).expect("verification should succeed");
+        assert_eq!(verified.signed_header, blocks[&27].signed_header);
+        assert_eq!(fetched, vec![29, 28, 27]);
+
+        // Walks should be capped and resume from where they stopped.
+        let result = verify(&mut cache, &mut fetched, 5);
+        assert!(result.is_err(), "verification should stop after max steps");
+        assert_eq!(fetched, (17..27).rev().collect::<Vec<_>>());
+        assert!(cache.verified_historical_blocks.contains(&17));
+
+        // Walks should resume from the last checkpoint after a failed fetch.
+        let result = verify(&mut cache, &mut fetched, 5);
+        assert!(result.is_err(), "verification should fail on fetch failure");
+        assert_eq!(fetched, vec![16, 15, 14, 13, 12]);
+        assert!(cache.verified_historical_blocks.contains(&16));
+
+        // Blocks between checkpoints should only require a short walk.
+        let verified = verify(&mut cache, &mut fetched, 22).expect("verification should succeed");
+        assert_eq!(verified.signed_header, blocks[&22].signed_header);
+        assert_eq!(fetched, vec![23, 22]);
+
+        // Cached blocks should not be fetched again.
+        let verified = verify(&mut cache, &mut fetched, 27).expect("verification should succeed");
+        assert_eq!(verified.signed_header, blocks[&27].signed_header);
+        assert!(fetched.is_empty());
+    }
+}
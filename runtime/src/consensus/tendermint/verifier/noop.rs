@@ -11,8 +11,8 @@ use crate::{
         roothash::Header,
         state::ConsensusState,
         tendermint::decode_light_block,
-        transaction::Transaction,
-        verifier::{self, Error},
+        transaction::{SignedTransaction, SignedTransactionWithProof, Transaction},
+        verifier::{self, Error, TransactionInclusion},
         BlockMetadata, Event, LightBlock, HEIGHT_LATEST, METHOD_META,
     },
     protocol::Protocol,
@@ -20,6 +20,8 @@ use crate::{
     types::{Body, EventKind, HostFetchConsensusEventsRequest, HostFetchConsensusEventsResponse},
 };
 
+use super::metadata;
+
 struct Inner {
     latest_height: Option<u64>,
 }
@@ -110,32 +112,7 @@ impl verifier::Verifier for NopVerifier {
         let height = self.latest_height().await?;
 
         // When latest state is requested we always perform same-block execution verification.
-        let result = self
-            .protocol
-            .call_host_async(Body::HostFetchBlockMetadataTxRequest { height })
-            .await
-            .map_err(|err| Error::StateRoot(err.into()))?;
-
-        // NOTE: This is a noop verifier so we do not verify the Merkle proof.
-        let signed_tx = match result {
-            Body::HostFetchBlockMetadataTxResponse { signed_tx, .. } => signed_tx,
-            _ => return Err(Error::StateRoot(anyhow!("bad response from host"))),
-        };
-
-        let tx: Transaction = cbor::from_slice(signed_tx.blob.as_slice()).map_err(|err| {
-            Error::TransactionVerificationFailed(anyhow!("failed to decode transaction: {}", err))
-        })?;
-
-        if tx.method != METHOD_META {
-            return Err(Error::StateRoot(anyhow!("invalid method name")));
-        }
-
-        let meta: BlockMetadata = cbor::from_value(tx.body).map_err(|err| {
-            Error::StateRoot(anyhow!(
-                "failed to decode block metadata transaction: {}",
-                err
-            ))
-        })?;
+        let meta = self.block_metadata_at(height).await?;
 
         let state_root = Root {
             namespace: Namespace::default(),
@@ -173,6 +150,47 @@ impl verifier::Verifier for NopVerifier {
         }
     }
 
+    async fn block_metadata_at(&self, height: u64) -> Result<BlockMetadata, Error> {
+        let result = self
+            .protocol
+            .call_host_async(Body::HostFetchBlockMetadataTxRequest { height })
+            .await
+            .map_err(|err| Error::StateRoot(err.into()))?;
+
+        // NOTE: This is a noop verifier so we do not verify the Merkle proof.
+        let signed_tx = match result {
+            Body::HostFetchBlockMetadataTxResponse { signed_tx, .. } => signed_tx,
+            _ => return Err(Error::StateRoot(anyhow!("bad response from host"))),
+        };
+
+        let tx = decode_transaction(&signed_tx)?;
+
+        if tx.method != METHOD_META {
+            return Err(Error::StateRoot(anyhow!("invalid method name")));
+        }
+
+        cbor::from_value(tx.body).map_err(|err| {
+            Error::StateRoot(anyhow!(
+                "failed to decode block metadata transaction: {}",
+                err
+            ))
+        })
+    }
+
+    async fn transaction_inclusion(
+        &self,
+        stwp: SignedTransactionWithProof,
+    ) -> Result<TransactionInclusion, Error> {
+        // NOTE: This is a noop verifier so we do not verify the Merkle proof.
+        let tx = decode_transaction(&stwp.signed_tx)?;
+        let events = self
+            .events_at(stwp.proof.height, EventKind::Staking)
+            .await?;
+        let events = metadata::transaction_events(events, &stwp.signed_tx);
+
+        Ok(TransactionInclusion { tx, events })
+    }
+
     async fn latest_height(&self) -> Result<u64, Error> {
         {
             let inner = self.inner.lock().unwrap();
@@ -186,3 +204,9 @@ impl verifier::Verifier for NopVerifier {
         Ok(latest_height)
     }
 }
+
+fn decode_transaction(signed_tx: &SignedTransaction) -> Result<Transaction, Error> {
+    cbor::from_slice(signed_tx.blob.as_slice()).map_err(|err| {
+        Error::TransactionVerificationFailed(anyhow!("failed to decode transaction: {}", err))
+    })
+}
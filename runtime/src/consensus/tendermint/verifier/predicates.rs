@@ -1,4 +1,8 @@
 use anyhow::anyhow;
+use rustc_hex::ToHex;
+use sha2::Sha256;
+use tendermint::merkle;
+use tendermint_light_client::types::{LightBlock as TMLightBlock, PeerId};
 
 use crate::{
     common::namespace::Namespace,
@@ -11,8 +15,9 @@ use crate::{
         },
         tendermint::{verifier::Cache, LightBlockMeta},
         verifier::Error,
-        LightBlock,
+        Event, LightBlock,
     },
+    types::EventKind,
 };
 
 /// Verifies that the namespace in the runtime header matches the trusted namespace.
@@ -127,3 +132,233 @@ pub fn verify_epoch(state: &ConsensusState, epoch: EpochTime) -> Result<(), Erro
 
     Ok(())
 }
+
+/// Verifies that the events returned by the host are consistent with the request.
+pub fn verify_events(events: &[Event], height: u64, kind: EventKind) -> Result<(), Error> {
+    for event in events {
+        if event.kind() != kind {
+            return Err(Error::VerificationFailed(anyhow!(
+                "event kind mismatch (expected: {:?} got: {:?})",
+                kind,
+                event.kind(),
+            )));
+        }
+        if event.height() != height as i64 {
+            return Err(Error::VerificationFailed(anyhow!(
+                "event height mismatch (expected: {} got: {})",
+                height,
+                event.height(),
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Verifies that the given staking events are exactly the provable events committed to by the
+/// given provable events root, in the order in which they were emitted.
+pub fn verify_events_root(events: &[Event], events_root: &[u8]) -> Result<(), Error> {
+    let leaves = events
+        .iter()
+        .map(|event| match event {
+            Event::Staking(ev) => ev.provable_representation().map(cbor::to_vec),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(|| Error::VerificationFailed(anyhow!("event is not provable")))?;
+
+    let root = merkle::simple_hash_from_byte_vectors::<Sha256>(&leaves);
+    if root[..] != events_root[..] {
+        return Err(Error::VerificationFailed(anyhow!(
+            "events root mismatch (expected: {} got: {})",
+            events_root.to_hex::<String>(),
+            root.to_hex::<String>(),
+        )));
+    }
+
+    Ok(())
+}
+
+/// Verifies that the untrusted block directly precedes the trusted block by checking that the
+/// trusted header links to it via its last block identifier and returns the verified block.
+pub fn verify_backward_link(
+    trusted: &TMLightBlock,
+    untrusted: LightBlockMeta,
+) -> Result<TMLightBlock, Error> {
+    let trusted_header = &trusted.signed_header.header;
+    let signed_header = untrusted
+        .signed_header
+        .ok_or_else(|| Error::VerificationFailed(anyhow!("missing signed header")))?;
+    let header = &signed_header.header;
+
+    if header.height.increment() != trusted_header.height {
+        return Err(Error::VerificationFailed(anyhow!(
+            "non-adjacent headers (trusted: {} untrusted: {})",
+            trusted_header.height,
+            header.height,
+        )));
+    }
+    let last_block_id = trusted_header.last_block_id.ok_or_else(|| {
+        Error::VerificationFailed(anyhow!(
+            "missing last block id at height {}",
+            trusted_header.height
+        ))
+    })?;
+    if header.hash() != last_block_id.hash {
+        return Err(Error::VerificationFailed(anyhow!(
+            "header hash mismatch (expected: {} got: {})",
+            last_block_id.hash,
+            header.hash(),
+        )));
+    }
+    // The hash chain only authenticates the header, make sure the commit is for the same block.
+    let commit = &signed_header.commit;
+    if commit.height != header.height || commit.block_id.hash != header.hash() {
+        return Err(Error::VerificationFailed(anyhow!(
+            "commit does not match header at height {}",
+            header.height,
+        )));
+    }
+    if untrusted.validators.hash() != header.validators_hash {
+        return Err(Error::VerificationFailed(anyhow!(
+            "validator set hash mismatch"
+        )));
+    }
+    if trusted.validators.hash() != header.next_validators_hash {
+        return Err(Error::VerificationFailed(anyhow!(
+            "next validator set hash mismatch"
+        )));
+    }
+
+    Ok(TMLightBlock {
+        signed_header,
+        validators: untrusted.validators,
+        next_validators: trusted.validators.clone(),
+        provider: PeerId::new([0; 20]),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use tendermint_testgen::{Generator, LightBlock as TestgenLightBlock, LightChain};
+
+    use super::*;
+
+    fn generate_blocks(count: u64) -> Vec<TMLightBlock> {
+        LightChain::default_with_length(count)
+            .light_blocks
+            .into_iter()
+            .map(|lb| lb.generate().unwrap())
+            .map(|lb| TMLightBlock {
+                signed_header: lb.signed_header,
+                validators: lb.validators,
+                next_validators: lb.next_validators,
+                provider: lb.provider,
+            })
+            .collect()
+    }
+
+    fn meta(block: &TMLightBlock) -> LightBlockMeta {
+        LightBlockMeta {
+            signed_header: Some(block.signed_header.clone()),
+            validators: block.validators.clone(),
+        }
+    }
+
+    #[test]
+    fn test_verify_events_root() {
+        use sha2::Digest;
+
+        use crate::consensus::{registry, staking};
+
+        // Empty blocks commit to the hash of an empty input.
+        let empty_root = Sha256::digest([]);
+        verify_events_root(&[], &empty_root).expect("empty events should verify");
+
+        let events: Vec<_> = (1u32..=2)
+            .map(|amount| {
+                Event::Staking(staking::Event {
+                    height: 42,
+                    burn: Some(staking::BurnEvent {
+                        amount: amount.into(),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                })
+            })
+            .collect();
+        let leaf = |event: &Event| match event {
+            Event::Staking(ev) => Sha256::digest(
+                [
+                    &[0x00][..],
+                    &cbor::to_vec(ev.provable_representation().unwrap()),
+                ]
+                .concat(),
+            ),
+            _ => unreachable!(),
+        };
+        let root = Sha256::digest(
+            [
+                &[0x01][..],
+                leaf(&events[0]).as_slice(),
+                leaf(&events[1]).as_slice(),
+            ]
+            .concat(),
+        );
+        verify_events_root(&events, &root).expect("events should verify");
+
+        // Missing, reordered or unprovable events should fail.
+        verify_events_root(&events[..1], &root).expect_err("missing events should fail");
+        let reordered = vec![events[1].clone(), events[0].clone()];
+        verify_events_root(&reordered, &root).expect_err("reordered events should fail");
+        let mut unprovable = events.clone();
+        unprovable.push(Event::Registry(registry::Event::default()));
+        verify_events_root(&unprovable, &root).expect_err("unprovable events should fail");
+    }
+
+    #[test]
+    fn test_verify_backward_link() {
+        let blocks = generate_blocks(3);
+
+        // Linked blocks should verify.
+        let verified =
+            verify_backward_link(&blocks[2], meta(&blocks[1])).expect("link should verify");
+        assert_eq!(verified.signed_header, blocks[1].signed_header);
+        let verified =
+            verify_backward_link(&verified, meta(&blocks[0])).expect("link should verify");
+        assert_eq!(verified.signed_header, blocks[0].signed_header);
+
+        // Non-adjacent blocks should fail.
+        verify_backward_link(&blocks[2], meta(&blocks[0]))
+            .expect_err("non-adjacent blocks should fail");
+        verify_backward_link(&blocks[1], meta(&blocks[2]))
+            .expect_err("non-adjacent blocks should fail");
+
+        // Blocks from a different chain should fail.
+        let other = TestgenLightBlock::new_default_with_time_and_chain_id(
+            "other-chain".to_string(),
+            tendermint::Time::from_unix_timestamp(1000, 0).unwrap(),
+            blocks[0].height().value(),
+        )
+        .generate()
+        .unwrap();
+        let untrusted = LightBlockMeta {
+            signed_header: Some(other.signed_header),
+            validators: other.validators,
+        };
+        verify_backward_link(&blocks[1], untrusted).expect_err("unlinked blocks should fail");
+
+        // Commit for a different block should fail.
+        let mut untrusted = meta(&blocks[0]);
+        untrusted.signed_header.as_mut().unwrap().commit.block_id =
+            blocks[1].signed_header.commit.block_id;
+        verify_backward_link(&blocks[1], untrusted).expect_err("mismatched commit should fail");
+
+        // Missing signed header should fail.
+        let untrusted = LightBlockMeta {
+            signed_header: None,
+            validators: blocks[0].validators.clone(),
+        };
+        verify_backward_link(&blocks[1], untrusted).expect_err("missing header should fail");
+    }
+}
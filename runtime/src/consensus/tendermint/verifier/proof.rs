@@ -0,0 +1,321 @@
+//! Exportable consensus state proofs.
+use std::{any::Any, convert::TryInto};
+
+use anyhow::anyhow;
+use tendermint::{trust_threshold::TrustThreshold as _, Hash as TMHash};
+use tendermint_light_client::{
+    operations::{ProvidedVotingPowerCalculator, VotingPowerCalculator},
+    types::TrustThreshold,
+};
+
+use crate::{
+    common::{crypto::hash::Hash, namespace::Namespace},
+    consensus::{
+        state::ConsensusState,
+        tendermint::{chain_id, decode_light_block},
+        verifier::Error,
+        LightBlock,
+    },
+    storage::mkvs::{
+        sync::{GetPrefixesRequest, GetRequest, IterateRequest, Proof, ProofResponse, ReadSync},
+        ImmutableMKVS, Root, RootType, Tree,
+    },
+};
+
+use super::signature::DomSepVerifier;
+
+/// A self-contained proof of a key/value pair in the consensus layer state that can be
+/// verified independently of the host, e.g. by external clients and bridges.
+#[derive(Clone, Debug, Default, cbor::Encode, cbor::Decode)]
+pub struct ConsensusStateProof {
+    /// Consensus layer light block whose application hash commits to the state.
+    pub light_block: LightBlock,
+    /// Consensus state root (application hash) the proof is for.
+    pub state_root: Hash,
+    /// Proven key.
+    pub key: Vec<u8>,
+    /// Merkle proof of the key/value pair against the state root.
+    pub proof: Proof,
+}
+
+impl ConsensusStateProof {
+    /// Create a new proof of the given key in the given verified consensus state.
+    ///
+    /// The passed light block must be the block whose application hash commits to the consensus
+    /// state. For states obtained for a specific height this is the block at that height, as the
+    /// header at height H commits to the state after executing block H-1. The latest consensus
+    /// state is instead derived from the block metadata of the latest block and is only committed
+    /// to by the header of the next block, so it can only be proven once that block exists.
+    ///
+    /// Fails in case the light block does not commit to the consensus state.
+    pub fn new(light_block: LightBlock, state: &ConsensusState, key: &[u8]) -> Result<Self, Error> {
+        let state_root = state_root(&light_block)?;
+        let proof = state
+            .get_proof(key)
+            .map_err(Error::StateRoot)?
+            .ok_or_else(|| Error::StateRoot(anyhow!("key does not exist")))?;
+        if proof.untrusted_root != state_root.hash {
+            return Err(Error::StateRoot(anyhow!(
+                "light block does not commit to the consensus state"
+            )));
+        }
+
+        Ok(Self {
+            light_block,
+            state_root: state_root.hash,
+            key: key.to_vec(),
+            proof,
+        })
+    }
+}
+
+/// Verify the given consensus state proof and return the proven value.
+///
+/// The light block is verified to be signed by more than 2/3 of the voting power of a validator
+/// set with the given trusted hash, which the caller must have obtained independently (e.g. by
+/// tracking validator set changes of the consensus layer).
+pub fn verify_consensus_state_proof(
+    proof: &ConsensusStateProof,
+    chain_context: &str,
+    trusted_validators_hash: TMHash,
+) -> Result<Vec<u8>, Error> {
+    let block = decode_light_block(proof.light_block.clone()).map_err(Error::VerificationFailed)?;
+    let signed_header = block
+        .signed_header
+        .as_ref()
+        .ok_or_else(|| Error::VerificationFailed(anyhow!("missing signed header")))?;
+    let header = signed_header.header();
+
+    // Verify the light block.
+    if header.height.value() != proof.light_block.height {
+        return Err(Error::VerificationFailed(anyhow!(
+            "inconsistent light block/header height"
+        )));
+    }
+    if header.chain_id != chain_id(chain_context) {
+        return Err(Error::VerificationFailed(anyhow!("chain id mismatch")));
+    }
+    if header.validators_hash != trusted_validators_hash
+        || block.validators.hash() != trusted_validators_hash
+    {
+        return Err(Error::VerificationFailed(anyhow!(
+            "validator set hash mismatch"
+        )));
+    }
+    if signed_header.commit().block_id.hash != header.hash() {
+        return Err(Error::VerificationFailed(anyhow!(
+            "commit does not match header"
+        )));
+    }
+    let tally = ProvidedVotingPowerCalculator::<DomSepVerifier>::default()
+        .voting_power_in(signed_header, &block.validators, TrustThreshold::TWO_THIRDS)
+        .map_err(|err| Error::VerificationFailed(err.into()))?;
+    if !tally
+        .trust_threshold
+        .is_enough_power(tally.tallied, tally.total)
+    {
+        return Err(Error::VerificationFailed(anyhow!(
+            "not enough voting power: {}",
+            tally
+        )));
+    }
+
+    // Verify the state proof.
+    let root = state_root(&proof.light_block)?;
+    if root.hash != proof.state_root {
+        return Err(Error::StateRoot(anyhow!("state root mismatch")));
+    }
+    let tree = Tree::builder()
+        .with_root(root)
+        .build(Box::new(ProofReadSyncer {
+            proof: Some(proof.proof.clone()),
+        }));
+
+    tree.get(&proof.key)
+        .map_err(Error::StateRoot)?
+        .ok_or_else(|| Error::StateRoot(anyhow!("key does not exist")))
+}
+
+/// Extract the state root from the given light block without panicking on malformed input.
+fn state_root(light_block: &LightBlock) -> Result<Root, Error> {
+    let block = decode_light_block(light_block.clone()).map_err(Error::StateRoot)?;
+    let header = block
+        .signed_header
+        .as_ref()
+        .ok_or_else(|| Error::StateRoot(anyhow!("missing signed header")))?
+        .header();
+    let hash: [u8; 32] = header
+        .app_hash
+        .as_bytes()
+        .try_into()
+        .map_err(|_| Error::StateRoot(anyhow!("invalid app hash")))?;
+    let version = header
+        .height
+        .value()
+        .checked_sub(1)
+        .ok_or_else(|| Error::StateRoot(anyhow!("invalid height")))?;
+
+    Ok(Root {
+        namespace: Namespace::default(),
+        version,
+        root_type: RootType::State,
+        hash: Hash(hash),
+    })
+}
+
+/// A read syncer that serves a single proof.
+struct ProofReadSyncer {
+    proof: Option<Proof>,
+}
+
+impl ReadSync for ProofReadSyncer {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn sync_get(&mut self, _request: GetRequest) -> anyhow::Result<ProofResponse> {
+        let proof = self
+            .proof
+            .take()
+            .ok_or_else(|| anyhow!("key not covered by proof"))?;
+        Ok(ProofResponse { proof })
+    }
+
+    fn sync_get_prefixes(&mut self, _request: GetPrefixesRequest) -> anyhow::Result<ProofResponse> {
+        Err(anyhow!("unsupported operation"))
+    }
+
+    fn sync_iterate(&mut self, _request: IterateRequest) -> anyhow::Result<ProofResponse> {
+        Err(anyhow!("unsupported operation"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::convert::TryFrom;
+
+    use tendermint::AppHash;
+    use tendermint_light_client::types::LightBlock as TMLightBlock;
+    use tendermint_testgen::{Generator, Header, LightBlock as TestgenLightBlock, Validator};
+
+    use crate::{
+        consensus::tendermint::{encode_light_block, LightBlockMeta},
+        storage::mkvs::sync::NoopReadSyncer,
+    };
+
+    use super::{super::signature::sign_test_commit, *};
+
+    const CHAIN_CONTEXT: &str = "test-chain-0000000000000000000000000000000000000000000000000000";
+
+    fn generate_light_block(height: u64, app_hash: Hash) -> (LightBlock, TMHash) {
+        let validators = [
+            Validator::new("1").voting_power(50),
+            Validator::new("2").voting_power(50),
+        ];
+        let header = Header::new(&validators)
+            .height(height)
+            .chain_id(chain_id(CHAIN_CONTEXT).as_str())
+            .next_validators(&validators)
+            .time(tendermint::Time::from_unix_timestamp(height as i64, 0).unwrap())
+            .app_hash(AppHash::try_from(app_hash.as_ref().to_vec()).unwrap());
+        let lb = TestgenLightBlock::new_default_with_header(header)
+            .generate()
+            .unwrap();
+        let mut block = TMLightBlock {
+            signed_header: lb.signed_header,
+            validators: lb.validators,
+            next_validators: lb.next_validators,
+            provider: lb.provider,
+        };
+        sign_test_commit(&mut block, &validators);
+        let validators_hash = block.validators.hash();
+
+        let light_block = encode_light_block(LightBlockMeta {
+            signed_header: Some(block.signed_header),
+            validators: block.validators,
+        })
+        .unwrap();
+
+        (light_block, validators_hash)
+    }
+
+    fn generate_state(version: u64) -> (Hash, Tree) {
+        let mut mkvs = Tree::builder()
+            .with_root_type(RootType::State)
+            .build(Box::new(NoopReadSyncer));
+        mkvs.insert(b"foo", b"bar").unwrap();
+        mkvs.insert(b"moo", b"boo").unwrap();
+        let state_root = mkvs.commit(Default::default(), version).unwrap();
+
+        (state_root, mkvs)
+    }
+
+    #[test]
+    fn test_consensus_state_proof() {
+        let height = 10;
+        let (state_root, mkvs) = generate_state(height - 1);
+        let state = ConsensusState::new(height, mkvs);
+
+        let (light_block, validators_hash) = generate_light_block(height, state_root);
+        let proof = ConsensusStateProof::new(light_block.clone(), &state, b"foo")
+            .expect("proof creation should succeed");
+        assert_eq!(proof.state_root, state_root);
+
+        // Valid proof.
+        let proof: ConsensusStateProof = cbor::from_slice(&cbor::to_vec(proof)).unwrap();
+        let value = verify_consensus_state_proof(&proof, CHAIN_CONTEXT, validators_hash)
+            .expect("proof should verify");
+        assert_eq!(value, b"bar".to_vec());
+
+        // Untrusted validator set.
+        let result = verify_consensus_state_proof(&proof, CHAIN_CONTEXT, TMHash::None);
+        assert!(result.is_err(), "untrusted validator set should fail");
+
+        // Wrong chain.
+        let other_context = "other-chain-000000000000000000000000000000000000000000000000000";
+        let result = verify_consensus_state_proof(&proof, other_context, validators_hash);
+        assert!(result.is_err(), "wrong chain should fail");
+
+        // Different key.
+        let mut tampered = proof.clone();
+        tampered.key = b"moo".to_vec();
+        let result = verify_consensus_state_proof(&tampered, CHAIN_CONTEXT, validators_hash);
+        assert!(result.is_err(), "key not covered by proof should fail");
+
+        // Light block committing to a different state.
+        let (light_block, validators_hash) = generate_light_block(height, Hash::empty_hash());
+        let mut tampered = proof.clone();
+        tampered.light_block = light_block;
+        let result = verify_consensus_state_proof(&tampered, CHAIN_CONTEXT, validators_hash);
+        assert!(result.is_err(), "state root mismatch should fail");
+
+        // Missing key.
+        let (light_block, _) = generate_light_block(height, state_root);
+        let result = ConsensusStateProof::new(light_block, &state, b"missing");
+        assert!(result.is_err(), "proof of missing key should fail");
+
+        // Light block not committing to the consensus state.
+        let (light_block, _) = generate_light_block(height, Hash::empty_hash());
+        let result = ConsensusStateProof::new(light_block, &state, b"foo");
+        assert!(
+            result.is_err(),
+            "light block for a different state should fail"
+        );
+
+        // State derived from block metadata is committed to by the next block.
+        let (_, mkvs) = generate_state(height - 1);
+        let state = ConsensusState::new(height - 1, mkvs);
+        let (light_block, _) = generate_light_block(height - 1, Hash::empty_hash());
+        let result = ConsensusStateProof::new(light_block, &state, b"foo");
+        assert!(
+            result.is_err(),
+            "light block at the state height should fail"
+        );
+        let (light_block, validators_hash) = generate_light_block(height, state_root);
+        let proof = ConsensusStateProof::new(light_block, &state, b"foo")
+            .expect("proof creation with the next block should succeed");
+        let value = verify_consensus_state_proof(&proof, CHAIN_CONTEXT, validators_hash)
+            .expect("proof should verify");
+        assert_eq!(value, b"bar".to_vec());
+    }
+}
@@ -19,3 +19,65 @@ impl signature::Verifier for DomSepVerifier {
         tendermint::crypto::default::signature::Verifier::verify(pubkey, msg.as_ref(), signature)
     }
 }
+
+/// Re-sign the commit of a light block generated by `tendermint_testgen` using the Oasis Core
+/// domain separation scheme. The given validators must be the ones that signed the block.
+#[cfg(test)]
+pub(super) fn sign_test_commit(
+    block: &mut tendermint_light_client::types::LightBlock,
+    validators: &[tendermint_testgen::Validator],
+) {
+    use std::convert::TryFrom;
+
+    use ed25519_dalek::Signer as _;
+    use tendermint::{
+        block::{signed_header::SignedHeader, CommitSig},
+        vote::{SignedVote, Type, ValidatorIndex, Vote},
+    };
+
+    let keys: Vec<_> = validators
+        .iter()
+        .map(|v| {
+            let key = v.get_private_key().unwrap();
+            ed25519_dalek::SigningKey::try_from(key.as_bytes()).unwrap()
+        })
+        .collect();
+
+    let header = block.signed_header.header.clone();
+    let mut commit = block.signed_header.commit.clone();
+    let validators = block.validators.validators().clone();
+    for (idx, commit_sig) in commit.signatures.iter_mut().enumerate() {
+        let (validator_address, timestamp, signature) = match commit_sig {
+            CommitSig::BlockIdFlagCommit {
+                validator_address,
+                timestamp,
+                signature,
+            } => (*validator_address, *timestamp, signature),
+            _ => continue,
+        };
+        let vote = Vote {
+            vote_type: Type::Precommit,
+            height: header.height,
+            round: block.signed_header.commit.round,
+            block_id: Some(block.signed_header.commit.block_id),
+            timestamp: Some(timestamp),
+            validator_address,
+            validator_index: ValidatorIndex::try_from(idx).unwrap(),
+            signature: signature.clone(),
+            extension: Default::default(),
+            extension_signature: None,
+        };
+        let sign_bytes = SignedVote::from_vote(vote, header.chain_id.clone())
+            .unwrap()
+            .sign_bytes();
+        let msg = Hash::digest_bytes_list(&[TENDERMINT_CONTEXT, &sign_bytes]);
+        let key = keys
+            .iter()
+            .find(|key| {
+                key.verifying_key().as_bytes()[..] == validators[idx].pub_key.to_bytes()[..]
+            })
+            .unwrap();
+        *signature = Some(Signature::try_from(key.sign(msg.as_ref()).to_bytes().as_ref()).unwrap());
+    }
+    block.signed_header = SignedHeader::new(header, commit).unwrap();
+}
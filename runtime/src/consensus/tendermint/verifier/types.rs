@@ -2,8 +2,12 @@ use tokio::sync::oneshot;
 
 use crate::{
     consensus::{
-        beacon::EpochTime, roothash::Header, state::ConsensusState, verifier::Error, Event,
-        LightBlock,
+        beacon::EpochTime,
+        roothash::Header,
+        state::ConsensusState,
+        transaction::SignedTransactionWithProof,
+        verifier::{Error, TransactionInclusion},
+        BlockMetadata, Event, LightBlock,
     },
     types::EventKind,
 };
@@ -28,4 +32,9 @@ pub enum Command {
     LatestHeight(oneshot::Sender<Result<u64, Error>>),
     StateAt(u64, oneshot::Sender<Result<ConsensusState, Error>>),
     EventsAt(u64, EventKind, oneshot::Sender<Result<Vec<Event>, Error>>),
+    BlockMetadataAt(u64, oneshot::Sender<Result<BlockMetadata, Error>>),
+    TransactionInclusion(
+        SignedTransactionWithProof,
+        oneshot::Sender<Result<TransactionInclusion, Error>>,
+    ),
 }
@@ -0,0 +1,339 @@
+//! Cross-checking of verified light blocks against additional witnesses.
+use std::{collections::BTreeMap, fmt, sync::Arc};
+
+use anyhow::{anyhow, Result};
+use slog::warn;
+use tendermint::trust_threshold::TrustThreshold as _;
+use tendermint_light_client::{
+    operations::{ProvidedVotingPowerCalculator, VotingPowerCalculator},
+    types::{LightBlock as TMLightBlock, TrustThreshold},
+};
+
+use crate::{
+    consensus::{tendermint::decode_light_block, verifier::Error, LightBlock},
+    protocol::Protocol,
+    types::Body,
+};
+
+use super::{io::Io, signature::DomSepVerifier};
+
+/// A source of consensus layer light blocks that is independent from the host.
+///
+/// Witnesses are used to cross-check light blocks verified using blocks provided by the host so
+/// that a host feeding the verifier a fork can be detected.
+pub trait Witness: fmt::Debug + Send + Sync {
+    /// Fetch the light block at the given height, if the witness has it.
+    fn fetch_light_block(&self, height: u64) -> Result<Option<LightBlock>>;
+}
+
+/// A witness backed by a provided list of light blocks (e.g., an evidence list).
+#[derive(Clone, Debug, Default)]
+pub struct EvidenceWitness {
+    blocks: BTreeMap<u64, LightBlock>,
+}
+
+impl EvidenceWitness {
+    /// Create a new witness from the given light blocks.
+    pub fn new(blocks: Vec<LightBlock>) -> Self {
+        Self {
+            blocks: blocks.into_iter().map(|lb| (lb.height, lb)).collect(),
+        }
+    }
+}
+
+impl Witness for EvidenceWitness {
+    fn fetch_light_block(&self, height: u64) -> Result<Option<LightBlock>> {
+        Ok(self.blocks.get(&height).cloned())
+    }
+}
+
+/// A witness backed by light blocks that the host fetches directly from consensus peers,
+/// bypassing its local consensus node.
+///
+/// As the blocks are still relayed by the host, this only detects a fork served by the host's
+/// local node (e.g., because the node has been eclipsed) and not a host that is itself malicious.
+/// Hosts that do not support the witness channel are treated as unavailable witnesses.
+pub struct HostWitness {
+    protocol: Arc<Protocol>,
+}
+
+impl HostWitness {
+    /// Create a new witness relayed by the host.
+    pub fn new(protocol: Arc<Protocol>) -> Self {
+        Self { protocol }
+    }
+}
+
+impl fmt::Debug for HostWitness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HostWitness").finish_non_exhaustive()
+    }
+}
+
+impl Witness for HostWitness {
+    fn fetch_light_block(&self, height: u64) -> Result<Option<LightBlock>> {
+        let request = Body::HostFetchWitnessBlockRequest { height };
+        if !self.protocol.is_supported_by_host(&request) {
+            return Ok(None);
+        }
+
+        let block = Io::new(&self.protocol).fetch_witness_light_block(height)?;
+        Ok(Some(block))
+    }
+}
+
+/// Cross-check the given verified light block against all witnesses unless the same block has
+/// already been cross-checked, as recorded in the given cache of checked block hashes.
+///
+/// Every verified height is cross-checked, not only heights that advance the latest known
+/// height, so that a fork is detected even if the host only serves it for historic heights.
+pub(super) fn cross_check_once(
+    logger: &slog::Logger,
+    witnesses: &[Arc<dyn Witness>],
+    checked: &mut lru::LruCache<u64, tendermint::Hash>,
+    verified_block: &TMLightBlock,
+) -> Result<(), Error> {
+    if witnesses.is_empty() {
+        return Ok(());
+    }
+
+    let header = &verified_block.signed_header.header;
+    let height = header.height.value();
+    let hash = header.hash();
+    if checked.get(&height) == Some(&hash) {
+        return Ok(());
+    }
+
+    cross_check(logger, witnesses, verified_block)?;
+    checked.put(height, hash);
+
+    Ok(())
+}
+
+/// Cross-check the given verified light block against all witnesses.
+///
+/// A witness providing a conflicting header is only treated as evidence of a fork in case the
+/// header is signed by more than 1/3 of the voting power of the verified validator set, as such
+/// a header can only exist if validators equivocated or the verified block is not canonical.
+/// Witnesses that are unavailable or provide headers without enough signatures are ignored.
+pub(super) fn cross_check(
+    logger: &slog::Logger,
+    witnesses: &[Arc<dyn Witness>],
+    verified_block: &TMLightBlock,
+) -> Result<(), Error> {
+    let header = &verified_block.signed_header.header;
+    let height = header.height.value();
+    let hash = header.hash();
+
+    for (index, witness) in witnesses.iter().enumerate() {
+        let conflicting = match fetch_conflicting_block(witness.as_ref(), verified_block) {
+            Ok(Some(conflicting)) => conflicting,
+            Ok(None) => continue,
+            Err(err) => {
+                warn!(logger, "Failed to cross-check light block with witness";
+                    "witness" => index,
+                    "height" => height,
+                    "err" => %err,
+                );
+                continue;
+            }
+        };
+
+        return Err(Error::ForkDetected(anyhow!(
+            "conflicting header at height {} (verified: {}, witness: {})",
+            height,
+            hash,
+            conflicting,
+        )));
+    }
+
+    Ok(())
+}
+
+/// Fetch the block at the height of the verified block from the witness and return the hash of
+/// its header in case it is a valid conflicting header.
+fn fetch_conflicting_block(
+    witness: &dyn Witness,
+    verified_block: &TMLightBlock,
+) -> Result<Option<tendermint::Hash>> {
+    let header = &verified_block.signed_header.header;
+    let height = header.height.value();
+
+    let block = match witness.fetch_light_block(height)? {
+        Some(block) => block,
+        None => return Ok(None),
+    };
+    let signed_header = decode_light_block(block)?
+        .signed_header
+        .ok_or_else(|| anyhow!("missing signed header"))?;
+    let witness_header = signed_header.header();
+
+    if witness_header.height != header.height || witness_header.chain_id != header.chain_id {
+        return Err(anyhow!("witness header for a different block"));
+    }
+    let witness_hash = witness_header.hash();
+    if witness_hash == header.hash() {
+        return Ok(None);
+    }
+
+    // Make sure the conflicting header is backed by enough trusted voting power.
+    if signed_header.commit().block_id.hash != witness_hash {
+        return Err(anyhow!("witness commit does not match header"));
+    }
+    let tally = ProvidedVotingPowerCalculator::<DomSepVerifier>::default().voting_power_in(
+        &signed_header,
+        &verified_block.validators,
+        TrustThreshold::ONE_THIRD,
+    )?;
+    if !tally
+        .trust_threshold
+        .is_enough_power(tally.tallied, tally.total)
+    {
+        return Err(anyhow!("not enough voting power: {}", tally));
+    }
+
+    Ok(Some(witness_hash))
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        num::NonZeroUsize,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use tendermint_testgen::{Generator, LightBlock as TestgenLightBlock, Validator};
+
+    use crate::{
+        common::logger::get_logger,
+        consensus::tendermint::{encode_light_block, LightBlockMeta},
+    };
+
+    use super::{super::signature::sign_test_commit, *};
+
+    /// Generate a light block at the given height and time. In case `dom_sep` is set, the commit
+    /// is signed using the Oasis Core domain separation scheme.
+    fn generate_block(height: u64, time: i64, dom_sep: bool) -> TMLightBlock {
+        let lb = TestgenLightBlock::new_default_with_time_and_chain_id(
+            "test-chain".to_string(),
+            tendermint::Time::from_unix_timestamp(time, 0).unwrap(),
+            height,
+        )
+        .generate()
+        .unwrap();
+        let mut block = TMLightBlock {
+            signed_header: lb.signed_header,
+            validators: lb.validators,
+            next_validators: lb.next_validators,
+            provider: lb.provider,
+        };
+        if !dom_sep {
+            return block;
+        }
+
+        sign_test_commit(&mut block, &[Validator::new("1"), Validator::new("2")]);
+
+        block
+    }
+
+    fn encode_block(block: &TMLightBlock) -> LightBlock {
+        encode_light_block(LightBlockMeta {
+            signed_header: Some(block.signed_header.clone()),
+            validators: block.validators.clone(),
+        })
+        .unwrap()
+    }
+
+    fn witness_for(block: &TMLightBlock) -> Arc<dyn Witness> {
+        Arc::new(EvidenceWitness::new(vec![encode_block(block)]))
+    }
+
+    /// A witness that counts the number of fetched light blocks.
+    #[derive(Debug, Default)]
+    struct CountingWitness {
+        inner: EvidenceWitness,
+        fetches: AtomicUsize,
+    }
+
+    impl Witness for CountingWitness {
+        fn fetch_light_block(&self, height: u64) -> Result<Option<LightBlock>> {
+            self.fetches.fetch_add(1, Ordering::SeqCst);
+            self.inner.fetch_light_block(height)
+        }
+    }
+
+    #[test]
+    fn test_cross_check() {
+        let logger = get_logger("consensus/cometbft/verifier/witness/test");
+        let verified = generate_block(10, 10, true);
+
+        // No witnesses.
+        cross_check(&logger, &[], &verified).expect("cross-check should succeed");
+
+        // Witness without the block.
+        let witnesses: Vec<Arc<dyn Witness>> = vec![Arc::new(EvidenceWitness::default())];
+        cross_check(&logger, &witnesses, &verified).expect("cross-check should succeed");
+
+        // Witness with the same block.
+        let witnesses = vec![witness_for(&verified)];
+        cross_check(&logger, &witnesses, &verified).expect("cross-check should succeed");
+
+        // Witness with a conflicting block without valid signatures should be ignored.
+        let witnesses = vec![witness_for(&generate_block(10, 20, false))];
+        cross_check(&logger, &witnesses, &verified).expect("cross-check should succeed");
+
+        // Witness with a valid conflicting block.
+        let witnesses = vec![
+            Arc::new(EvidenceWitness::default()) as Arc<dyn Witness>,
+            witness_for(&generate_block(10, 20, true)),
+        ];
+        let result = cross_check(&logger, &witnesses, &verified);
+        assert!(
+            matches!(result, Err(Error::ForkDetected(_))),
+            "cross-check should detect a fork"
+        );
+    }
+
+    #[test]
+    fn test_cross_check_once() {
+        let logger = get_logger("consensus/cometbft/verifier/witness/test");
+        let mut checked = lru::LruCache::new(NonZeroUsize::new(16).unwrap());
+        let latest = generate_block(20, 10, true);
+        let historic = generate_block(10, 10, true);
+
+        // Without witnesses nothing is recorded.
+        cross_check_once(&logger, &[], &mut checked, &latest).expect("cross-check should succeed");
+        assert!(checked.is_empty());
+
+        // The witness has the same latest block and a conflicting historic block.
+        let witness = Arc::new(CountingWitness {
+            inner: EvidenceWitness::new(vec![
+                encode_block(&latest),
+                encode_block(&generate_block(10, 20, true)),
+            ]),
+            ..Default::default()
+        });
+        let witnesses = vec![witness.clone() as Arc<dyn Witness>];
+
+        cross_check_once(&logger, &witnesses, &mut checked, &latest)
+            .expect("cross-check should succeed");
+        assert_eq!(witness.fetches.load(Ordering::SeqCst), 1);
+
+        // Blocks that were already cross-checked should not be fetched again.
+        cross_check_once(&logger, &witnesses, &mut checked, &latest)
+            .expect("cross-check should succeed");
+        assert_eq!(witness.fetches.load(Ordering::SeqCst), 1);
+
+        // Blocks below the latest verified height should still be cross-checked.
+        let result = cross_check_once(&logger, &witnesses, &mut checked, &historic);
+        assert!(
+            matches!(result, Err(Error::ForkDetected(_))),
+            "cross-check should detect a fork at a historic height"
+        );
+        assert_eq!(witness.fetches.load(Ordering::SeqCst), 2);
+        assert!(
+            !checked.contains(&10),
+            "forked block should not be recorded"
+        );
+    }
+}
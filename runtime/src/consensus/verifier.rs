@@ -9,12 +9,13 @@ use super::{
     beacon::EpochTime,
     roothash::Header,
     state::{registry::ImmutableState as RegistryState, ConsensusState},
-    Event, LightBlock,
+    transaction::{SignedTransactionWithProof, Transaction},
+    BlockMetadata, Event, LightBlock,
 };
 use crate::{
     common::{crypto::signature::PublicKey, namespace::Namespace, version::Version},
     identity::Identity,
-    types::{self, EventKind},
+    types::EventKind,
 };
 
 #[derive(Debug, Error)]
@@ -42,32 +43,22 @@ pub enum Error {
 
     #[error("internal consensus verifier error")]
     Internal,
-}
 
-impl Error {
-    fn code(&self) -> u32 {
-        match self {
-            Error::Builder(_) => 1,
-            Error::VerificationFailed(_) => 2,
-            Error::TrustedStateLoadingFailed => 3,
-            Error::ChainContextTransitionFailed(_) => 4,
-            Error::FreshnessVerificationFailed(_) => 5,
-            Error::TransactionVerificationFailed(_) => 6,
-            Error::StateRoot(_) => 7,
-            Error::Internal => 8,
-        }
-    }
+    #[error("fork detected: {0}")]
+    ForkDetected(#[source] anyhow::Error),
 }
 
-impl From<Error> for types::Error {
-    fn from(e: Error) -> Self {
-        Self {
-            module: "verifier".to_string(),
-            code: e.code(),
-            message: e.to_string(),
-        }
-    }
-}
+impl_error_codes!(Error, "verifier", {
+    Error::Builder(_) => 1,
+    Error::VerificationFailed(_) => 2,
+    Error::TrustedStateLoadingFailed => 3,
+    Error::ChainContextTransitionFailed(_) => 4,
+    Error::FreshnessVerificationFailed(_) => 5,
+    Error::TransactionVerificationFailed(_) => 6,
+    Error::StateRoot(_) => 7,
+    Error::Internal => 8,
+    Error::ForkDetected(_) => 9,
+});
 
 /// Verifier is the consensus layer state verifier trait.
 #[async_trait]
@@ -113,20 +104,55 @@ pub trait Verifier: Send + Sync {
 
     /// Return the verified consensus layer state for a given height.
     ///
+    /// Heights below the lowest trusted block are verified backwards by following the header
+    /// hash chain, which requires fetching all blocks in between.
+    ///
     /// # Warning
     ///
     /// The state is not verified to be fresh. Use `verify_state_freshness` to perform this
     /// verification manually if needed.
     async fn state_at(&self, height: u64) -> Result<ConsensusState, Error>;
 
-    /// Return the consensus layer events at the given height.
+    /// Return the consensus layer events of the given kind at the given height.
+    ///
+    /// Staking events are verified against the provable events root of the block at the given
+    /// height.
     ///
     /// # Warning
     ///
-    /// Event integrity is currently not verified and it thus relies on replicated computation even
-    /// when using a TEE-enabled runtime.
+    /// Only the height and the kind of other events are verified. Their integrity is not verified
+    /// and it thus relies on replicated computation even when using a TEE-enabled runtime.
     async fn events_at(&self, height: u64, kind: EventKind) -> Result<Vec<Event>, Error>;
 
+    /// Return the verified block metadata of the block at the given height.
+    ///
+    /// The metadata commits to the consensus state and the provable events after executing the
+    /// block at the given height.
+    async fn block_metadata_at(&self, _height: u64) -> Result<BlockMetadata, Error> {
+        Err(Error::VerificationFailed(anyhow!(
+            "block metadata verification not supported"
+        )))
+    }
+
+    /// Verify that the given signed transaction was included in the block its proof is for and
+    /// return the decoded transaction together with the provable events it emitted.
+    ///
+    /// The events are verified against the provable events root of the block.
+    ///
+    /// # Warning
+    ///
+    /// Inclusion in a block does not imply that the transaction executed successfully. The events
+    /// root also does not commit to which transaction emitted an event, so the attribution of the
+    /// verified events to the transaction relies on the host.
+    async fn transaction_inclusion(
+        &self,
+        _tx: SignedTransactionWithProof,
+    ) -> Result<TransactionInclusion, Error> {
+        Err(Error::VerificationFailed(anyhow!(
+            "transaction inclusion verification not supported"
+        )))
+    }
+
     /// Return the latest known consensus layer height.
     async fn latest_height(&self) -> Result<u64, Error>;
 }
@@ -171,11 +197,31 @@ impl<T: ?Sized + Verifier> Verifier for Arc<T> {
         Verifier::events_at(&**self, height, kind).await
     }
 
+    async fn block_metadata_at(&self, height: u64) -> Result<BlockMetadata, Error> {
+        Verifier::block_metadata_at(&**self, height).await
+    }
+
+    async fn transaction_inclusion(
+        &self,
+        tx: SignedTransactionWithProof,
+    ) -> Result<TransactionInclusion, Error> {
+        Verifier::transaction_inclusion(&**self, tx).await
+    }
+
     async fn latest_height(&self) -> Result<u64, Error> {
         Verifier::latest_height(&**self).await
     }
 }
 
+/// A consensus transaction included in a block together with the provable events it emitted.
+#[derive(Debug)]
+pub struct TransactionInclusion {
+    /// Decoded transaction.
+    pub tx: Transaction,
+    /// Provable events emitted by the transaction.
+    pub events: Vec<Event>,
+}
+
 /// Consensus layer trust root.
 #[derive(Debug, Clone, Default, PartialEq, Eq, cbor::Encode, cbor::Decode)]
 pub struct TrustRoot {
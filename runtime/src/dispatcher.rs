@@ -3,11 +3,14 @@ use std::{
     convert::TryInto,
     sync::{Arc, Condvar, Mutex},
     thread,
+    time::Instant,
 };
 
 use anyhow::Result as AnyResult;
+use lazy_static::lazy_static;
 use rustc_hex::ToHex;
 use slog::{debug, error, info, warn, Logger};
+use thiserror::Error as ThisError;
 use tokio::sync::mpsc;
 
 use crate::{
@@ -15,22 +18,29 @@ use crate::{
     common::{
         crypto::{hash::Hash, signature::Signer},
         logger::get_logger,
+        metrics::{self, Counter, Histogram, DEFAULT_DURATION_BUCKETS},
+        namespace::Namespace,
         process,
         sgx::QuotePolicy,
+        trace::{self, SpanContext},
     },
     consensus::{
         beacon::EpochTime,
         roothash::{self, ComputeResultsHeader, Header, COMPUTE_RESULTS_HEADER_SIGNATURE_CONTEXT},
-        state::keymanager::Status as KeyManagerStatus,
+        state::{
+            keymanager::Status as KeyManagerStatus, registry::ImmutableState as RegistryState,
+        },
         verifier::Verifier,
         LightBlock,
     },
     enclave_rpc::{
         demux::Demux as RpcDemux,
         dispatcher::Dispatcher as RpcDispatcher,
-        session::{self, SessionInfo},
+        resumption::TicketCache,
+        session,
         types::{
-            Kind as RpcKind, Message as RpcMessage, Request as RpcRequest, Response as RpcResponse,
+            Body as RpcBody, Kind as RpcKind, Message as RpcMessage, Request as RpcRequest,
+            Response as RpcResponse,
         },
         Context as RpcContext,
     },
@@ -44,6 +54,7 @@ use crate::{
         dispatcher::{Dispatcher as TxnDispatcher, NoopDispatcher as TxnNoopDispatcher},
         tree::Tree as TxnTree,
         types::TxnBatch,
+        weight::{self, BatchWeights, Fit, Limits, Weights},
         Context as TxnContext,
     },
     types::{Body, ComputedBatch, Error, ExecutionMode},
@@ -61,6 +72,70 @@ const RPC_MAX_SESSIONS_PER_PEER: usize = 8;
 /// seconds can be closed to make room for new sessions.
 const RPC_STALE_SESSION_TIMEOUT_SECS: i64 = 10;
 
+lazy_static! {
+    static ref REQUESTS: Counter = metrics::counter(
+        "runtime_dispatcher_requests",
+        "Number of requests processed by the runtime dispatcher.",
+    );
+    static ref FAILURES: Counter = metrics::counter(
+        "runtime_dispatcher_failures",
+        "Number of requests processed by the runtime dispatcher that resulted in an error.",
+    );
+    static ref DURATION: Histogram = metrics::histogram(
+        "runtime_dispatcher_request_duration_ms",
+        "Duration of requests processed by the runtime dispatcher (in milliseconds).",
+        DEFAULT_DURATION_BUCKETS,
+    );
+}
+
+/// Runtime host protocol dispatcher error.
+#[derive(ThisError, Debug)]
+pub enum DispatcherError {
+    #[error("{0}")]
+    Other(#[from] anyhow::Error),
+    #[error("query not supported")]
+    QueryNotSupported,
+    #[error("scheduling not supported")]
+    SchedulingNotSupported,
+    #[error("malformed request")]
+    MalformedRequest,
+    #[error("invalid RPC message type")]
+    InvalidRpcMessageType,
+    #[error("batch exceeds limits")]
+    BatchLimitsExceeded,
+}
+
+// NOTE: Code 1 is shared by multiple errors as it has historically been used for all of them.
+impl_error_codes!(DispatcherError, "rhp/dispatcher", {
+    DispatcherError::Other(_)
+    | DispatcherError::MalformedRequest
+    | DispatcherError::InvalidRpcMessageType => 1,
+    DispatcherError::QueryNotSupported => 2,
+    DispatcherError::SchedulingNotSupported => 3,
+    DispatcherError::BatchLimitsExceeded => 4,
+});
+
+/// Runtime request dispatch error.
+///
+/// These errors have historically been reported under a different module than the rest of the
+/// dispatcher errors.
+#[derive(ThisError, Debug)]
+pub enum RequestError {
+    #[error("Unsupported request type")]
+    UnsupportedRequestType,
+    #[error("block namespace does not match runtime id (namespace: {0:?} runtime ID: {1:?})")]
+    NamespaceMismatch(Namespace, Namespace),
+    #[error("error while processing request: {0}")]
+    RequestFailed(#[source] tokio::task::JoinError),
+}
+
+// NOTE: Code 1 is shared by all errors as it has historically been used for all of them.
+impl_error_codes!(RequestError, "dispatcher", {
+    RequestError::UnsupportedRequestType
+    | RequestError::NamespaceMismatch(..)
+    | RequestError::RequestFailed(_) => 1,
+});
+
 /// Interface for dispatcher initializers.
 pub trait Initializer: Send + Sync {
     /// Initializes the dispatcher(s).
@@ -116,11 +191,7 @@ impl Drop for AbortOnPanic {
 
 impl From<tokio::task::JoinError> for Error {
     fn from(e: tokio::task::JoinError) -> Self {
-        Error::new(
-            "dispatcher",
-            1,
-            &format!("error while processing request: {e}"),
-        )
+        RequestError::RequestFailed(e).into()
     }
 }
 
@@ -159,7 +230,7 @@ struct State {
 
 #[derive(Debug)]
 enum Command {
-    Request(u64, Body),
+    Request(u64, Option<SpanContext>, Body),
 }
 
 /// Runtime call dispatcher.
@@ -214,8 +285,14 @@ impl Dispatcher {
     }
 
     /// Queue a new request to be dispatched.
-    pub fn queue_request(&self, id: u64, body: Body) -> AnyResult<()> {
-        self.queue_tx.blocking_send(Command::Request(id, body))?;
+    pub fn queue_request(
+        &self,
+        id: u64,
+        span_context: Option<SpanContext>,
+        body: Body,
+    ) -> AnyResult<()> {
+        self.queue_tx
+            .blocking_send(Command::Request(id, span_context, body))?;
         Ok(())
     }
 
@@ -239,7 +316,9 @@ impl Dispatcher {
         // Create actual dispatchers for RPCs and transactions.
         info!(self.logger, "Starting the runtime dispatcher");
         let mut rpc_demux = RpcDemux::new(
-            session::Builder::default().local_identity(self.identity.clone()),
+            session::Builder::default()
+                .local_identity(self.identity.clone())
+                .resumption(Some(Arc::new(TicketCache::default()))),
             RPC_MAX_SESSIONS,
             RPC_MAX_SESSIONS_PER_PEER,
             RPC_STALE_SESSION_TIMEOUT_SECS,
@@ -289,22 +368,29 @@ impl Dispatcher {
             while let Some(cmd) = rx.recv().await {
                 // Process received command.
                 match cmd {
-                    Command::Request(id, request) => {
+                    Command::Request(id, span_context, request) => {
                         // Process request in its own task.
                         let state = state.clone();
 
-                        tokio::spawn(async move {
+                        tokio::spawn(trace::scope(span_context, async move {
                             let protocol = state.protocol.clone();
                             let dispatcher = state.dispatcher.clone();
+
+                            REQUESTS.inc();
+                            let start = Instant::now();
                             let result = dispatcher.handle_request(state, request).await;
+                            DURATION.observe(start.elapsed().as_millis() as u64);
 
                             // Send response.
                             let response = match result {
                                 Ok(body) => body,
-                                Err(error) => Body::Error(error),
+                                Err(error) => {
+                                    FAILURES.inc();
+                                    Body::Error(error)
+                                }
                             };
                             protocol.send_response(id, response).unwrap();
-                        });
+                        }));
                     }
                 }
             }
@@ -330,7 +416,7 @@ impl Dispatcher {
                 kind,
                 peer_id,
             } => {
-                debug!(self.logger, "Received RPC call request";
+                debug!(trace::logger(&self.logger), "Received RPC call request";
                     "kind" => ?kind,
                     "peer_id" => peer_id.to_hex::<String>(),
                 );
@@ -339,12 +425,14 @@ impl Dispatcher {
                     RpcKind::NoiseSession => {
                         self.dispatch_secure_rpc(state, request, peer_id).await
                     }
-                    RpcKind::InsecureQuery => self.dispatch_insecure_rpc(state, request).await,
+                    RpcKind::InsecureQuery => {
+                        self.dispatch_insecure_rpc(state, request, peer_id).await
+                    }
                     RpcKind::LocalQuery => self.dispatch_local_rpc(state, request).await,
                 }
             }
             Body::RuntimeLocalRPCCallRequest { request } => {
-                debug!(self.logger, "Received RPC call request";
+                debug!(trace::logger(&self.logger), "Received RPC call request";
                     "kind" => ?RpcKind::LocalQuery,
                 );
 
@@ -455,7 +543,7 @@ impl Dispatcher {
             } => {
                 if let Some(runtime_block) = runtime_block {
                     if let Err(err) = state.app.on_runtime_block(&runtime_block).await {
-                        error!(self.logger, "Application block notification failed"; "err" => ?err);
+                        error!(trace::logger(&self.logger), "Application block notification failed"; "err" => ?err);
                     }
                 }
                 if let Some(runtime_event) = runtime_event {
@@ -464,7 +552,7 @@ impl Dispatcher {
                         .on_runtime_event(&runtime_event.block, &runtime_event.tags)
                         .await
                     {
-                        error!(self.logger, "Application event notification failed"; "err" => ?err);
+                        error!(trace::logger(&self.logger), "Application event notification failed"; "err" => ?err);
                     }
                 }
 
@@ -491,8 +579,8 @@ impl Dispatcher {
                 .map(|_| Body::RuntimeConsensusSyncResponse {}),
 
             _ => {
-                error!(self.logger, "Unsupported request type");
-                Err(Error::new("dispatcher", 1, "Unsupported request type"))
+                error!(trace::logger(&self.logger), "Unsupported request type");
+                Err(RequestError::UnsupportedRequestType.into())
             }
         }
     }
@@ -507,7 +595,7 @@ impl Dispatcher {
         args: Vec<u8>,
         state: TxDispatchState,
     ) -> Result<Body, Error> {
-        debug!(self.logger, "Received query request";
+        debug!(trace::logger(&self.logger), "Received query request";
             "method" => &method,
             "state_root" => ?state.header.state_root,
             "round" => ?state.header.round,
@@ -516,15 +604,11 @@ impl Dispatcher {
         // Verify that the runtime ID matches the block's namespace. This is a protocol violation
         // as the compute node should never change the runtime ID.
         if state.header.namespace != protocol.get_runtime_id() {
-            return Err(Error::new(
-                "dispatcher",
-                1,
-                &format!(
-                    "block namespace does not match runtime id (namespace: {:?} runtime ID: {:?})",
-                    state.header.namespace,
-                    protocol.get_runtime_id(),
-                ),
-            ));
+            return Err(RequestError::NamespaceMismatch(
+                state.header.namespace,
+                protocol.get_runtime_id(),
+            )
+            .into());
         }
 
         let protocol = protocol.clone();
@@ -537,7 +621,7 @@ impl Dispatcher {
             .unverified_state(state.consensus_block.clone())
             .await?;
 
-        tokio::task::spawn_blocking(move || {
+        trace::spawn_blocking(move || {
             let cache = cache_set.query(Root {
                 namespace: state.header.namespace,
                 version: state.header.round,
@@ -609,7 +693,10 @@ impl Dispatcher {
             let _ = overlay.commit().unwrap();
         }
 
-        debug!(self.logger, "Transaction batch check complete");
+        debug!(
+            trace::logger(&self.logger),
+            "Transaction batch check complete"
+        );
 
         results.map(|results| Body::RuntimeCheckTxBatchResponse { results })
     }
@@ -637,6 +724,13 @@ impl Dispatcher {
 
         let header = &state.header;
 
+        // Determine batch limits based on the runtime descriptor.
+        let params = RegistryState::new(&consensus_state)
+            .runtime(&header.namespace)?
+            .map(|rt| rt.txn_scheduler)
+            .unwrap_or_default();
+        let limits = txn_dispatcher.batch_limits(&params);
+
         let mut cache = cache_set.execute(Root {
             namespace: state.header.namespace,
             version: state.header.round,
@@ -660,12 +754,29 @@ impl Dispatcher {
         // Perform execution based on the passed mode.
         let mut results = match state.mode {
             ExecutionMode::Execute => {
-                // Just execute the batch.
+                // Refuse batches that exceed the limits, then just execute the batch.
+                check_batch_limits(txn_dispatcher, &limits, &inputs)?;
                 txn_dispatcher.execute_batch(txn_ctx, &inputs, &in_msgs)?
             }
             ExecutionMode::Schedule => {
+                // Defer transactions that do not fit into the batch and reject transactions that
+                // can never fit into any batch. Deferred transactions are neither included nor
+                // rejected, so they remain in the host's queue for a subsequent batch.
+                let (tx_defer_hashes, tx_reject_hashes) =
+                    fit_batch_limits(txn_dispatcher, &limits, &mut inputs);
+                if !tx_defer_hashes.is_empty() {
+                    debug!(trace::logger(&self.logger), "Deferring transactions that do not fit into the batch";
+                        "count" => tx_defer_hashes.len(),
+                        "tx_hashes" => ?tx_defer_hashes,
+                    );
+                }
+
                 // Allow the runtime to arbitrarily update the batch.
-                txn_dispatcher.schedule_and_execute_batch(txn_ctx, &mut inputs, &in_msgs)?
+                let mut results =
+                    txn_dispatcher.schedule_and_execute_batch(txn_ctx, &mut inputs, &in_msgs)?;
+                check_batch_limits(txn_dispatcher, &limits, &inputs)?;
+                results.tx_reject_hashes.extend(tx_reject_hashes);
+                results
             }
         };
 
@@ -730,7 +841,7 @@ impl Dispatcher {
             in_msgs_count: results.in_msgs_count.try_into().unwrap(),
         };
 
-        debug!(self.logger, "Transaction batch execution complete";
+        debug!(trace::logger(&self.logger), "Transaction batch execution complete";
             "previous_hash" => ?header.previous_hash,
             "io_root" => ?header.io_root,
             "state_root" => ?header.state_root,
@@ -776,7 +887,7 @@ impl Dispatcher {
         // a serious problem and should make sure to clean up the process.
         let _guard = AbortOnPanic;
 
-        debug!(self.logger, "Received transaction batch request";
+        debug!(trace::logger(&self.logger), "Received transaction batch request";
             "state_root" => ?state.header.state_root,
             "round" => state.header.round + 1,
             "round_results" => ?state.round_results,
@@ -798,7 +909,7 @@ impl Dispatcher {
         let dispatcher = self.clone();
         let txn_dispatcher = txn_dispatcher.clone();
 
-        tokio::task::spawn_blocking(move || {
+        trace::spawn_blocking(move || {
             if state.check_only {
                 dispatcher.txn_check_batch(protocol, cache_set, &txn_dispatcher, inputs, state)
             } else {
@@ -844,14 +955,15 @@ impl Dispatcher {
             match message {
                 RpcMessage::Request(req) => {
                     // Request, dispatch.
+                    let rpc_ctx = RpcContext::new(session.info()).with_streams(session.streams());
                     let response = self
-                        .dispatch_rpc(req, RpcKind::NoiseSession, session.info(), &state)
+                        .dispatch_rpc(req, RpcKind::NoiseSession, rpc_ctx, &state)
                         .await?;
                     let response = RpcMessage::Response(response);
 
                     // Note: MKVS commit is omitted, this MUST be global side-effect free.
 
-                    debug!(self.logger, "RPC call dispatch complete";
+                    debug!(trace::logger(&self.logger), "RPC call dispatch complete";
                         "kind" => ?RpcKind::NoiseSession,
                     );
 
@@ -859,8 +971,8 @@ impl Dispatcher {
                     session
                         .write_message(response, &mut buffer)
                         .map_err(|err| {
-                            error!(self.logger, "Error while writing response"; "err" => %err);
-                            Error::new("rhp/dispatcher", 1, &format!("{err}"))
+                            error!(trace::logger(&self.logger), "Error while writing response"; "err" => %err);
+                            err.into()
                         })
                         .map(|_| Body::RuntimeRPCCallResponse { response: buffer })
                 }
@@ -871,14 +983,14 @@ impl Dispatcher {
                         .rpc_demux
                         .close(session, &mut buffer)
                         .map_err(|err| {
-                            error!(self.logger, "Error while closing session"; "err" => %err);
-                            Error::new("rhp/dispatcher", 1, &format!("{err}"))
+                            error!(trace::logger(&self.logger), "Error while closing session"; "err" => %err);
+                            err.into()
                         })
                         .map(|_| Body::RuntimeRPCCallResponse { response: buffer })
                 }
                 msg => {
-                    warn!(self.logger, "Ignoring invalid RPC message type"; "msg" => ?msg);
-                    Err(Error::new("rhp/dispatcher", 1, "invalid RPC message type"))
+                    warn!(trace::logger(&self.logger), "Ignoring invalid RPC message type"; "msg" => ?msg);
+                    Err(DispatcherError::InvalidRpcMessageType.into())
                 }
             }
         } else {
@@ -887,23 +999,40 @@ impl Dispatcher {
         }
     }
 
-    async fn dispatch_insecure_rpc(&self, state: State, request: Vec<u8>) -> Result<Body, Error> {
+    async fn dispatch_insecure_rpc(
+        &self,
+        state: State,
+        request: Vec<u8>,
+        peer_id: Vec<u8>,
+    ) -> Result<Body, Error> {
         // Make sure to abort the process on panic during RPC processing as that indicates a
         // serious problem and should make sure to clean up the process.
         let _guard = AbortOnPanic;
 
-        let request: RpcRequest = cbor::from_slice(&request)
-            .map_err(|_| Error::new("rhp/dispatcher", 1, "malformed request"))?;
-
-        // Request, dispatch.
-        let response = self
-            .dispatch_rpc(request, RpcKind::InsecureQuery, None, &state)
-            .await?;
+        let request: RpcRequest =
+            cbor::from_slice(&request).map_err(|_| DispatcherError::MalformedRequest)?;
+
+        // Enforce the same rate limits as for requests sent over a session.
+        let response = match state.rpc_demux.check_rate_limits(&peer_id, &request) {
+            Ok(()) => {
+                // Request, dispatch.
+                self.dispatch_rpc(
+                    request,
+                    RpcKind::InsecureQuery,
+                    RpcContext::new(None),
+                    &state,
+                )
+                .await?
+            }
+            Err(err) => RpcResponse {
+                body: RpcBody::StructuredError(err.into()),
+            },
+        };
         let response = cbor::to_vec(response);
 
         // Note: MKVS commit is omitted, this MUST be global side-effect free.
 
-        debug!(self.logger, "RPC call dispatch complete";
+        debug!(trace::logger(&self.logger), "RPC call dispatch complete";
             "kind" => ?RpcKind::InsecureQuery,
         );
 
@@ -915,17 +1044,16 @@ impl Dispatcher {
         // serious problem and should make sure to clean up the process.
         let _guard = AbortOnPanic;
 
-        let request = cbor::from_slice(&request)
-            .map_err(|_| Error::new("rhp/dispatcher", 1, "malformed request"))?;
+        let request = cbor::from_slice(&request).map_err(|_| DispatcherError::MalformedRequest)?;
 
         // Request, dispatch.
         let response = self
-            .dispatch_rpc(request, RpcKind::LocalQuery, None, &state)
+            .dispatch_rpc(request, RpcKind::LocalQuery, RpcContext::new(None), &state)
             .await?;
         let response = RpcMessage::Response(response);
         let response = cbor::to_vec(response);
 
-        debug!(self.logger, "RPC call dispatch complete";
+        debug!(trace::logger(&self.logger), "RPC call dispatch complete";
             "kind" => ?RpcKind::LocalQuery,
         );
 
@@ -936,16 +1064,21 @@ impl Dispatcher {
         &self,
         request: RpcRequest,
         kind: RpcKind,
-        session_info: Option<Arc<SessionInfo>>,
+        rpc_ctx: RpcContext,
         state: &State,
     ) -> Result<RpcResponse, Error> {
         let rpc_dispatcher = state.rpc_dispatcher.clone();
 
-        let response = tokio::task::spawn_blocking(move || {
-            let rpc_ctx = RpcContext::new(session_info);
-            rpc_dispatcher.dispatch(rpc_ctx, request, kind)
-        })
-        .await?;
+        // Asynchronous methods are dispatched on the runtime's Tokio runtime while synchronous
+        // methods may block so they are dispatched on the blocking thread pool. Both run in a
+        // separate task so that a panicking method handler results in a failed request instead
+        // of aborting the process.
+        let response = if rpc_dispatcher.is_async(&request) {
+            trace::spawn(async move { rpc_dispatcher.dispatch_async(rpc_ctx, request, kind).await })
+                .await?
+        } else {
+            trace::spawn_blocking(move || rpc_dispatcher.dispatch(rpc_ctx, request, kind)).await?
+        };
 
         Ok(response)
     }
@@ -959,12 +1092,15 @@ impl Dispatcher {
         // serious problem and should make sure to clean up the process.
         let _guard = AbortOnPanic;
 
-        debug!(self.logger, "Received km status update request");
+        debug!(
+            trace::logger(&self.logger),
+            "Received km status update request"
+        );
 
         // Verify and decode the status.
         let runtime_id = state.protocol.get_host_info().runtime_id;
 
-        tokio::task::spawn_blocking(move || -> Result<(), Error> {
+        trace::spawn_blocking(move || -> Result<(), Error> {
             let key_manager = state.policy_verifier.key_manager(&runtime_id)?;
             let published_status = state
                 .policy_verifier
@@ -979,7 +1115,10 @@ impl Dispatcher {
         })
         .await??;
 
-        debug!(self.logger, "KM status update request complete");
+        debug!(
+            trace::logger(&self.logger),
+            "KM status update request complete"
+        );
 
         Ok(Body::RuntimeKeyManagerStatusUpdateResponse {})
     }
@@ -993,12 +1132,15 @@ impl Dispatcher {
         // a serious problem and should make sure to clean up the process.
         let _guard = AbortOnPanic;
 
-        debug!(self.logger, "Received km quote policy update request");
+        debug!(
+            trace::logger(&self.logger),
+            "Received km quote policy update request"
+        );
 
         // Verify and decode the policy.
         let runtime_id = state.protocol.get_host_info().runtime_id;
 
-        tokio::task::spawn_blocking(move || -> Result<(), Error> {
+        trace::spawn_blocking(move || -> Result<(), Error> {
             let key_manager = state.policy_verifier.key_manager(&runtime_id)?;
             let policy =
                 state
@@ -1012,8 +1154,150 @@ impl Dispatcher {
         })
         .await??;
 
-        debug!(self.logger, "KM quote policy update request complete");
+        debug!(
+            trace::logger(&self.logger),
+            "KM quote policy update request complete"
+        );
 
         Ok(Body::RuntimeKeyManagerQuotePolicyUpdateResponse {})
     }
 }
+
+/// Weights of the given transaction, including the built-in weights.
+fn txn_weights(txn_dispatcher: &dyn TxnDispatcher, tx: &[u8]) -> Weights {
+    let mut weights = txn_dispatcher.tx_weights(tx);
+    // Built-in weights cannot be overridden by the runtime.
+    weights.extend(weight::builtin_weights(tx));
+    weights
+}
+
+/// Remove transactions which do not fit into the batch limits from the given batch.
+///
+/// Returns the hashes of the deferred transactions, which do not fit into the remaining capacity
+/// of the batch, and the hashes of the rejected transactions, which can never fit into any batch.
+fn fit_batch_limits(
+    txn_dispatcher: &dyn TxnDispatcher,
+    limits: &Limits,
+    batch: &mut TxnBatch,
+) -> (Vec<Hash>, Vec<Hash>) {
+    let mut weights = BatchWeights::new(limits);
+    let mut deferred = Vec::new();
+    let mut rejected = Vec::new();
+    batch.retain(|tx| match weights.add(&txn_weights(txn_dispatcher, tx)) {
+        Fit::Accepted => true,
+        Fit::Deferred => {
+            deferred.push(Hash::digest_bytes(tx));
+            false
+        }
+        Fit::Rejected => {
+            rejected.push(Hash::digest_bytes(tx));
+            false
+        }
+    });
+    (deferred, rejected)
+}
+
+/// Ensure that the given batch does not exceed the batch limits.
+fn check_batch_limits(
+    txn_dispatcher: &dyn TxnDispatcher,
+    limits: &Limits,
+    batch: &TxnBatch,
+) -> Result<(), Error> {
+    let mut weights = BatchWeights::new(limits);
+    for tx in batch.iter() {
+        if weights.add(&txn_weights(txn_dispatcher, tx)) != Fit::Accepted {
+            return Err(DispatcherError::BatchLimitsExceeded.into());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        transaction::{
+            dispatcher::{ExecuteBatchResult, NoopDispatcher},
+            weight::{WEIGHT_COUNT, WEIGHT_SIZE_BYTES},
+        },
+        types::{CheckTxResult, Error as RuntimeError},
+    };
+
+    #[test]
+    fn test_fit_batch_limits() {
+        let txn_dispatcher = NoopDispatcher;
+        let limits = Limits::new()
+            .with(WEIGHT_COUNT, 2)
+            .with(WEIGHT_SIZE_BYTES, 8);
+        let mut batch = TxnBatch::new(vec![
+            b"aaaa".to_vec(),
+            b"bbbbbbbbbb".to_vec(),
+            b"cccccc".to_vec(),
+            b"dd".to_vec(),
+            b"ee".to_vec(),
+        ]);
+
+        let (deferred, rejected) = fit_batch_limits(&txn_dispatcher, &limits, &mut batch);
+        assert_eq!(batch.0, vec![b"aaaa".to_vec(), b"dd".to_vec()]);
+        assert_eq!(
+            deferred,
+            vec![Hash::digest_bytes(b"cccccc"), Hash::digest_bytes(b"ee")],
+            "transactions that do not fit should be deferred"
+        );
+        assert_eq!(
+            rejected,
+            vec![Hash::digest_bytes(b"bbbbbbbbbb")],
+            "transactions that never fit should be rejected"
+        );
+
+        // The remaining batch is within limits.
+        check_batch_limits(&txn_dispatcher, &limits, &batch)
+            .expect("batch should be within limits");
+
+        // Batches exceeding the limits are refused.
+        let batch = TxnBatch::new(vec![b"a".to_vec(), b"b".to_vec(), b"c".to_vec()]);
+        assert!(check_batch_limits(&txn_dispatcher, &limits, &batch).is_err());
+    }
+
+    #[test]
+    fn test_builtin_weights_override() {
+        struct WeightsDispatcher;
+
+        impl TxnDispatcher for WeightsDispatcher {
+            fn execute_batch(
+                &self,
+                _ctx: TxnContext,
+                _batch: &TxnBatch,
+                _in_msgs: &[roothash::IncomingMessage],
+            ) -> Result<ExecuteBatchResult, RuntimeError> {
+                unimplemented!()
+            }
+
+            fn check_batch(
+                &self,
+                _ctx: TxnContext,
+                _batch: &TxnBatch,
+            ) -> Result<Vec<CheckTxResult>, RuntimeError> {
+                unimplemented!()
+            }
+
+            fn tx_weights(&self, _tx: &[u8]) -> Weights {
+                // Attempt to shadow the built-in weights.
+                [(WEIGHT_COUNT.to_string(), 0), ("gas".to_string(), 10)]
+                    .iter()
+                    .cloned()
+                    .collect()
+            }
+        }
+
+        let weights = txn_weights(&WeightsDispatcher, b"aaaa");
+        assert_eq!(weights.get(WEIGHT_COUNT), Some(&1));
+        assert_eq!(weights.get(WEIGHT_SIZE_BYTES), Some(&4));
+        assert_eq!(weights.get("gas"), Some(&10));
+
+        // Runtime weights cannot be used to squeeze more transactions into a batch.
+        let limits = Limits::new().with(WEIGHT_COUNT, 1);
+        let batch = TxnBatch::new(vec![b"a".to_vec(), b"b".to_vec()]);
+        assert!(check_batch_limits(&WeightsDispatcher, &limits, &batch).is_err());
+    }
+}
@@ -0,0 +1,171 @@
+//! Access control policies for RPC methods.
+use std::{collections::HashSet, fmt, sync::Arc};
+
+use anyhow::{bail, Result};
+use thiserror::Error;
+
+use super::{context::Context, types::Kind};
+use crate::common::{crypto::signature::PublicKey, namespace::Namespace, sgx::EnclaveIdentity};
+
+/// Access control error.
+#[derive(Error, Debug)]
+pub enum AccessError {
+    #[error("not authenticated")]
+    NotAuthenticated,
+    #[error("only local calls allowed")]
+    NotLocal,
+    #[error("enclave not allowed")]
+    EnclaveNotAllowed,
+    #[error("node not allowed")]
+    NodeNotAllowed,
+    #[error("runtime not allowed")]
+    RuntimeNotAllowed,
+}
+
+impl_error_codes!(AccessError, "enclave_rpc/access", {
+    AccessError::NotAuthenticated => 1,
+    AccessError::NotLocal => 2,
+    AccessError::EnclaveNotAllowed => 3,
+    AccessError::NodeNotAllowed => 4,
+    AccessError::RuntimeNotAllowed => 5,
+});
+
+/// Custom access control check.
+pub type CustomPolicy = Arc<dyn Fn(&Context) -> Result<()> + Send + Sync>;
+
+/// Custom access control check of the request arguments.
+pub type RequestPolicy = Arc<dyn Fn(&Context, &cbor::Value) -> Result<()> + Send + Sync>;
+
+/// Access control policy of a RPC method.
+///
+/// The policy is evaluated by the RPC dispatcher before the method handler is invoked.
+#[derive(Clone, Default)]
+pub enum AccessPolicy {
+    /// Anyone that is allowed to make calls of the method's kind may call the method.
+    #[default]
+    Any,
+    /// Only the host may call the method via a local query.
+    LocalOnly,
+    /// Only enclaves with one of the given identities may call the method.
+    Enclaves(HashSet<EnclaveIdentity>),
+    /// Only enclaves endorsed by one of the given nodes may call the method.
+    Nodes(HashSet<PublicKey>),
+    /// Only requests which refer to one of the given runtimes are allowed. The runtime is given
+    /// by the `runtime_id` field of the request.
+    Runtimes(HashSet<Namespace>),
+    /// All of the given policies must allow the call.
+    All(Vec<AccessPolicy>),
+    /// Custom check, used for authorization that depends on state outside of the session (e.g.,
+    /// a policy published in the consensus layer).
+    Custom(CustomPolicy),
+    /// Custom check of the request, used for authorization that depends on the request itself
+    /// (e.g., the runtime for which keys are requested).
+    Request(RequestPolicy),
+}
+
+impl AccessPolicy {
+    /// Create a custom access control policy.
+    pub fn custom<F>(f: F) -> Self
+    where
+        F: Fn(&Context) -> Result<()> + Send + Sync + 'static,
+    {
+        Self::Custom(Arc::new(f))
+    }
+
+    /// Create a custom access control policy which checks the decoded request.
+    pub fn request<Rq, F>(f: F) -> Self
+    where
+        Rq: cbor::Decode,
+        F: Fn(&Context, &Rq) -> Result<()> + Send + Sync + 'static,
+    {
+        Self::Request(Arc::new(move |ctx, args| {
+            let request = cbor::from_value(args.clone())?;
+            f(ctx, &request)
+        }))
+    }
+
+    /// Whether the policy only allows local calls.
+    pub fn is_local_only(&self) -> bool {
+        match self {
+            Self::LocalOnly => true,
+            Self::All(policies) => policies.iter().any(Self::is_local_only),
+            _ => false,
+        }
+    }
+
+    /// Extract the runtime ID from the `runtime_id` field of the request, if any.
+    fn request_runtime_id(args: &cbor::Value) -> Option<Namespace> {
+        let fields = match args {
+            cbor::Value::Map(fields) => fields,
+            _ => return None,
+        };
+        fields.iter().find_map(|(key, value)| match key {
+            cbor::Value::TextString(key) if key == "runtime_id" => {
+                cbor::from_value(value.clone()).ok()
+            }
+            _ => None,
+        })
+    }
+
+    /// Check whether a call of the given kind with the given arguments made in the given context
+    /// is allowed.
+    pub fn authorize(&self, ctx: &Context, kind: Kind, args: &cbor::Value) -> Result<()> {
+        match self {
+            Self::Any => {}
+            Self::LocalOnly => {
+                if kind != Kind::LocalQuery {
+                    bail!(AccessError::NotLocal);
+                }
+            }
+            Self::Enclaves(enclaves) => {
+                let si = ctx
+                    .session_info
+                    .as_ref()
+                    .ok_or(AccessError::NotAuthenticated)?;
+                if !enclaves.contains(&si.verified_quote.identity) {
+                    bail!(AccessError::EnclaveNotAllowed);
+                }
+            }
+            Self::Nodes(nodes) => {
+                let si = ctx
+                    .session_info
+                    .as_ref()
+                    .ok_or(AccessError::NotAuthenticated)?;
+                match si.endorsed_by {
+                    Some(node) if nodes.contains(&node) => {}
+                    _ => bail!(AccessError::NodeNotAllowed),
+                }
+            }
+            Self::Runtimes(runtimes) => {
+                let runtime_id =
+                    Self::request_runtime_id(args).ok_or(AccessError::RuntimeNotAllowed)?;
+                if !runtimes.contains(&runtime_id) {
+                    bail!(AccessError::RuntimeNotAllowed);
+                }
+            }
+            Self::All(policies) => {
+                for policy in policies {
+                    policy.authorize(ctx, kind, args)?;
+                }
+            }
+            Self::Custom(f) => f(ctx)?,
+            Self::Request(f) => f(ctx, args)?,
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for AccessPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Any => write!(f, "Any"),
+            Self::LocalOnly => write!(f, "LocalOnly"),
+            Self::Enclaves(enclaves) => f.debug_tuple("Enclaves").field(enclaves).finish(),
+            Self::Nodes(nodes) => f.debug_tuple("Nodes").field(nodes).finish(),
+            Self::Runtimes(runtimes) => f.debug_tuple("Runtimes").field(runtimes).finish(),
+            Self::All(policies) => f.debug_tuple("All").field(policies).finish(),
+            Self::Custom(_) => write!(f, "Custom"),
+            Self::Request(_) => write!(f, "Request"),
+        }
+    }
+}
@@ -1,16 +1,28 @@
 //! Enclave RPC client.
-use std::{collections::HashSet, mem, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    mem,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Instant,
+};
 
+use futures::stream::{self, BoxStream, StreamExt};
 use thiserror::Error;
 use tokio::sync::{mpsc, oneshot};
 
 use crate::{
     common::{
         crypto::signature,
+        errors::ErrorCode,
         namespace::Namespace,
         sgx::{EnclaveIdentity, QuotePolicy},
     },
     enclave_rpc::{
+        demux,
+        selector::{LeastLoaded, PeerSelector, PeerStats},
         session::{Builder, Session},
         types,
     },
@@ -23,6 +35,8 @@ use super::transport::{RuntimeTransport, Transport};
 const CMDQ_BACKLOG: usize = 32;
 /// Maximum number of retries on transport errors.
 const MAX_TRANSPORT_ERROR_RETRIES: usize = 3;
+/// Number of failures a bad peer report counts as when selecting nodes.
+const BAD_PEER_PENALTY: u32 = 5;
 
 /// RPC client error.
 #[derive(Error, Debug)]
@@ -43,17 +57,65 @@ pub enum RpcClientError {
     DecodeError(#[from] cbor::DecodeError),
     #[error("unknown error: {0}")]
     Unknown(#[from] anyhow::Error),
+    #[error("stream interrupted")]
+    StreamInterrupted,
+    #[error("invalid stream chunk")]
+    InvalidStreamChunk,
+    #[error("rate limited")]
+    RateLimited,
+}
+
+impl RpcClientError {
+    /// Error corresponding to the given structured error response.
+    fn from_structured_error(error: crate::types::Error) -> Self {
+        if demux::Error::RateLimited.matches(&error) {
+            return Self::RateLimited;
+        }
+        Self::CallFailed(error.message)
+    }
 }
 
+/// Identifies the peer that handled a call so that feedback can be attributed to it.
+#[derive(Clone, Copy, Debug)]
+struct FeedbackTarget {
+    /// Transport peer feedback identifier.
+    pfid: u64,
+    /// Remote node that handled the call (if known).
+    node: Option<signature::PublicKey>,
+}
+
+impl FeedbackTarget {
+    fn new(transport: &dyn Transport, node: Option<signature::PublicKey>) -> Self {
+        Self {
+            pfid: node
+                .map(|node| transport.get_peer_feedback_id(&node))
+                .unwrap_or_default(),
+            node,
+        }
+    }
+
+    /// Set peer feedback for the call. Feedback cannot be attributed in case the remote node is
+    /// not known and is dropped, the host accounts for such failed calls itself.
+    fn set_peer_feedback(&self, transport: &dyn Transport, peer_feedback: types::PeerFeedback) {
+        if let Some(node) = self.node {
+            transport.set_peer_feedback(node, self.pfid, Some(peer_feedback));
+        }
+    }
+}
+
+type CallResult = Result<(FeedbackTarget, types::Response), RpcClientError>;
+
+type OpenStreamResult = Result<(FeedbackTarget, types::Response, StreamSession), RpcClientError>;
+
+/// Stream of chunks of a streaming response.
+pub type ChunkStream<T> = BoxStream<'static, Result<T, RpcClientError>>;
+
 /// A command sent to the client controller task.
 #[derive(Debug)]
 enum Command {
-    Call(
-        types::Request,
-        types::Kind,
-        oneshot::Sender<Result<(u64, types::Response), RpcClientError>>,
-    ),
-    PeerFeedback(u64, types::PeerFeedback, types::Kind),
+    Call(types::Request, types::Kind, oneshot::Sender<CallResult>),
+    OpenStream(types::Request, oneshot::Sender<OpenStreamResult>),
+    PeerFeedback(FeedbackTarget, types::PeerFeedback, types::Kind),
     UpdateEnclaves(Option<HashSet<EnclaveIdentity>>),
     UpdateQuotePolicy(QuotePolicy),
     UpdateRuntimeID(Option<Namespace>),
@@ -62,6 +124,183 @@ enum Command {
     Ping(oneshot::Sender<()>),
 }
 
+/// A command sent to a session task.
+#[derive(Debug)]
+enum SessionCommand {
+    Call(types::Request, oneshot::Sender<CallResult>, InFlightGuard),
+    OpenStream(
+        types::Request,
+        oneshot::Sender<OpenStreamResult>,
+        InFlightGuard,
+        mpsc::Sender<SessionCommand>,
+    ),
+    StreamCall(
+        types::Request,
+        types::SessionID,
+        oneshot::Sender<Result<types::Response, RpcClientError>>,
+    ),
+    Reset,
+}
+
+/// Session over which a streaming response is consumed.
+#[derive(Clone, Debug)]
+struct StreamSession {
+    /// Command queue of the session task.
+    cmdq: mpsc::Sender<SessionCommand>,
+    /// Identifier of the session in which the stream was opened. Streams do not survive session
+    /// resets.
+    id: types::SessionID,
+}
+
+/// State of a streaming response that is being consumed.
+struct StreamState {
+    /// Session over which the stream is consumed.
+    session: StreamSession,
+    /// Stream identifier.
+    stream: u64,
+    /// Sequence number of the next chunk.
+    seq: u64,
+    /// Whether the stream has ended.
+    done: bool,
+}
+
+impl StreamState {
+    fn new(session: StreamSession, stream: u64) -> Self {
+        Self {
+            session,
+            stream,
+            seq: 0,
+            done: false,
+        }
+    }
+
+    /// Verify that the chunk is the next chunk of the stream and return its data.
+    ///
+    /// Chunks are delivered over the session in order, so checking the stream identifier and
+    /// sequence number is enough to detect chunks of other streams and replies to stale requests.
+    fn verify(&mut self, chunk: types::StreamChunk) -> Result<Option<cbor::Value>, RpcClientError> {
+        if chunk.stream != self.stream || chunk.seq != self.seq {
+            return Err(RpcClientError::InvalidStreamChunk);
+        }
+
+        self.seq += 1;
+        self.done = chunk.data.is_none();
+
+        Ok(chunk.data)
+    }
+
+    /// Fetch the next chunk of the stream.
+    async fn fetch(&self) -> Result<types::StreamChunk, RpcClientError> {
+        let request = types::Request {
+            method: types::METHOD_STREAM_NEXT.to_owned(),
+            args: cbor::to_value(types::StreamNextRequest {
+                stream: self.stream,
+                seq: self.seq,
+            }),
+        };
+
+        let (tx, rx) = oneshot::channel();
+        self.session
+            .cmdq
+            .send(SessionCommand::StreamCall(request, self.session.id, tx))
+            .await
+            .map_err(|_| RpcClientError::Dropped)?;
+        let response = rx.await.map_err(|_| RpcClientError::Dropped)??;
+
+        match response.body {
+            types::Body::Success(value) => Ok(cbor::from_value(value)?),
+            types::Body::Error(error) => Err(RpcClientError::CallFailed(error)),
+            types::Body::StructuredError(error) => {
+                Err(RpcClientError::from_structured_error(error))
+            }
+        }
+    }
+}
+
+impl Drop for StreamState {
+    fn drop(&mut self) {
+        if self.done {
+            return;
+        }
+
+        // Cancel the stream so that the remote side can release it. This is best effort as
+        // the stream is released anyway once the session is closed.
+        let request = types::Request {
+            method: types::METHOD_STREAM_CANCEL.to_owned(),
+            args: cbor::to_value(types::StreamCancelRequest {
+                stream: self.stream,
+            }),
+        };
+        let (tx, _) = oneshot::channel();
+        let _ =
+            self.session
+                .cmdq
+                .try_send(SessionCommand::StreamCall(request, self.session.id, tx));
+    }
+}
+
+/// Health and load of a remote node.
+#[derive(Debug, Default)]
+struct NodeState {
+    /// Number of calls currently being processed by the node.
+    in_flight: AtomicUsize,
+    /// Observed call outcomes and latencies.
+    stats: Mutex<PeerStats>,
+}
+
+impl NodeState {
+    fn update(&self, peer_feedback: types::PeerFeedback) {
+        let mut stats = self.stats.lock().unwrap();
+        match peer_feedback {
+            types::PeerFeedback::Success => stats.reset_failures(),
+            types::PeerFeedback::Failure => stats.record_failure(1),
+            types::PeerFeedback::BadPeer => stats.record_failure(BAD_PEER_PENALTY),
+        }
+    }
+
+    fn snapshot(&self) -> PeerStats {
+        let mut stats = self.stats.lock().unwrap();
+        stats.decay_failures(std::time::Instant::now());
+
+        let mut stats = *stats;
+        stats.in_flight = self.in_flight.load(Ordering::SeqCst);
+        stats
+    }
+}
+
+/// Decrements the number of in-flight calls of a node when dropped.
+#[derive(Debug)]
+struct InFlightGuard(Option<Arc<NodeState>>);
+
+impl InFlightGuard {
+    fn new(state: Option<Arc<NodeState>>) -> Self {
+        if let Some(state) = &state {
+            state.in_flight.fetch_add(1, Ordering::SeqCst);
+        }
+        Self(state)
+    }
+
+    fn record_success(&self, latency: std::time::Duration) {
+        if let Some(state) = &self.0 {
+            state.stats.lock().unwrap().record_success(latency);
+        }
+    }
+
+    fn record_failure(&self) {
+        if let Some(state) = &self.0 {
+            state.stats.lock().unwrap().record_failure(1);
+        }
+    }
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        if let Some(state) = &self.0 {
+            state.in_flight.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+}
+
 struct MultiplexedSession {
     /// Session builder for resetting sessions.
     builder: Builder,
@@ -86,121 +325,100 @@ impl MultiplexedSession {
     }
 }
 
-struct Controller {
-    /// Allowed nodes.
-    nodes: Vec<signature::PublicKey>,
+/// A task that owns a single multiplexed session and processes calls over it in order.
+struct SessionWorker {
+    /// Remote node the session is pinned to. In case no node is set, the host selects one.
+    node: Option<signature::PublicKey>,
     /// Multiplexed session.
     session: MultiplexedSession,
     /// Used transport.
-    transport: Box<dyn Transport>,
-    /// Internal command queue (receiver part).
-    cmdq: mpsc::Receiver<Command>,
+    transport: Arc<dyn Transport>,
+    /// Session command queue (receiver part).
+    cmdq: mpsc::Receiver<SessionCommand>,
 }
 
-impl Controller {
+impl SessionWorker {
     async fn run(mut self) {
         while let Some(cmd) = self.cmdq.recv().await {
             match cmd {
-                Command::Call(request, kind, sender) => self.call(request, kind, sender).await,
-                Command::PeerFeedback(pfid, peer_feedback, kind) => {
-                    self.transport.set_peer_feedback(pfid, Some(peer_feedback));
-
-                    // In case the peer feedback is bad, reset the session so a new peer can be
-                    // selected for a subsequent session.
-                    if !matches!(peer_feedback, types::PeerFeedback::Success)
-                        && kind == types::Kind::NoiseSession
-                    {
-                        self.reset().await;
-                    }
-                }
-                Command::UpdateEnclaves(enclaves) => {
-                    if self.session.builder.get_remote_enclaves() == &enclaves {
-                        continue;
-                    }
-
-                    self.session.builder =
-                        mem::take(&mut self.session.builder).remote_enclaves(enclaves);
-                    self.reset().await;
-                }
-                Command::UpdateQuotePolicy(policy) => {
-                    let policy = Some(Arc::new(policy));
-                    if self.session.builder.get_quote_policy() == &policy {
-                        continue;
-                    }
-
-                    self.session.builder =
-                        mem::take(&mut self.session.builder).quote_policy(policy);
-                    self.reset().await;
+                SessionCommand::Call(request, sender, guard) => {
+                    let _ = sender.send(self.call(request, guard).await);
                 }
-                Command::UpdateRuntimeID(id) => {
-                    if self.session.builder.get_remote_runtime_id() == &id {
-                        continue;
-                    }
-
-                    self.session.builder =
-                        mem::take(&mut self.session.builder).remote_runtime_id(id);
-                    self.reset().await;
+                SessionCommand::OpenStream(request, sender, guard, cmdq) => {
+                    let result = self.call(request, guard).await.map(|(target, rsp)| {
+                        let session = StreamSession {
+                            cmdq,
+                            id: self.session.id,
+                        };
+                        (target, rsp, session)
+                    });
+                    let _ = sender.send(result);
                 }
-                Command::UpdateNodes(nodes) => {
-                    self.nodes = nodes;
-                }
-                #[cfg(test)]
-                Command::Ping(sender) => {
-                    let _ = sender.send(());
+                SessionCommand::StreamCall(request, id, sender) => {
+                    let _ = sender.send(self.stream_call(request, id).await);
                 }
+                SessionCommand::Reset => self.reset().await,
             }
         }
 
-        // Close stream after the client is dropped.
+        // Close stream after the session is no longer needed.
         let _ = self.close().await;
     }
 
-    async fn call(
-        &mut self,
-        request: types::Request,
-        kind: types::Kind,
-        sender: oneshot::Sender<Result<(u64, types::Response), RpcClientError>>,
-    ) {
+    async fn call(&mut self, request: types::Request, guard: InFlightGuard) -> CallResult {
         let result = async {
-            match kind {
-                types::Kind::NoiseSession => {
-                    // Attempt to establish a connection. This will not do anything in case the
-                    // session has already been established.
-                    self.connect().await?;
-
-                    // Perform the call.
-                    self.secure_call_raw(request).await
-                }
-                types::Kind::InsecureQuery => {
-                    // Perform the call.
-                    self.insecure_call_raw(request).await
-                }
-                _ => Err(RpcClientError::UnsupportedRpcKind),
-            }
+            // Attempt to establish a connection. This will not do anything in case the session
+            // has already been established.
+            self.connect().await?;
+
+            // Perform the call, measuring its latency without the handshake.
+            let start = Instant::now();
+            let rsp = self.secure_call_raw(request).await?;
+            Ok((rsp, start.elapsed()))
         }
         .await;
 
         // Update peer feedback for next request.
-        let pfid = self.transport.get_peer_feedback_id();
-        if result.is_err() {
-            // Set peer feedback immediately so retries can try new peers.
-            self.transport
-                .set_peer_feedback(pfid, Some(types::PeerFeedback::Failure));
-            // In case there was a transport error we need to reset the session immediately as no
-            // progress is possible.
-            if kind == types::Kind::NoiseSession {
+        let target = FeedbackTarget::new(
+            &*self.transport,
+            self.session.inner.get_node().ok().or(self.node),
+        );
+        match &result {
+            Ok((_, latency)) => guard.record_success(*latency),
+            Err(_) => {
+                // Set peer feedback immediately so retries can try new peers.
+                target.set_peer_feedback(&*self.transport, types::PeerFeedback::Failure);
+                guard.record_failure();
+                // In case there was a transport error we need to reset the session immediately as
+                // no progress is possible.
                 self.reset().await;
             }
         }
+        drop(guard);
 
-        let _ = sender.send(result.map(|rsp| (pfid, rsp)));
+        result.map(|(rsp, _)| (target, rsp))
+    }
+
+    async fn stream_call(
+        &mut self,
+        request: types::Request,
+        id: types::SessionID,
+    ) -> Result<types::Response, RpcClientError> {
+        // Streams are bound to the session in which they were opened.
+        if self.session.id != id || !self.session.inner.is_connected() {
+            return Err(RpcClientError::StreamInterrupted);
+        }
+
+        let result = self.secure_call_raw(request).await;
+        if result.is_err() {
+            self.reset().await;
+        }
+        result
     }
 
     async fn connect(&mut self) -> Result<(), RpcClientError> {
-        // No need to create a new session if we are connected to one of the nodes.
-        if self.session.inner.is_connected()
-            && (self.nodes.is_empty() || self.session.inner.is_connected_to(&self.nodes))
-        {
+        // No need to create a new session if we are already connected.
+        if self.session.inner.is_connected() {
             return Ok(());
         }
         // Make sure the session is reset for a new connection.
@@ -217,7 +435,12 @@ impl Controller {
 
         let (data, node) = self
             .transport
-            .write_noise_session(session_id, buffer, String::new(), self.nodes.clone())
+            .write_noise_session(
+                session_id,
+                buffer,
+                String::new(),
+                self.node.into_iter().collect(),
+            )
             .await
             .map_err(|_| RpcClientError::Transport)?;
 
@@ -283,19 +506,6 @@ impl Controller {
         }
     }
 
-    async fn insecure_call_raw(
-        &mut self,
-        request: types::Request,
-    ) -> Result<types::Response, RpcClientError> {
-        let (data, _) = self
-            .transport
-            .write_insecure_query(cbor::to_vec(request), self.nodes.clone())
-            .await
-            .map_err(|_| RpcClientError::Transport)?;
-
-        cbor::from_slice(&data).map_err(RpcClientError::DecodeError)
-    }
-
     async fn reset(&mut self) {
         // Notify the other end (if any) of session closure.
         let _ = self.close_notify().await;
@@ -342,12 +552,243 @@ impl Controller {
     }
 }
 
+struct Controller {
+    /// Allowed nodes.
+    nodes: Vec<signature::PublicKey>,
+    /// Health and load of allowed nodes.
+    node_states: HashMap<signature::PublicKey, Arc<NodeState>>,
+    /// Strategy for selecting the node that handles the next call.
+    selector: Box<dyn PeerSelector>,
+    /// Session builder.
+    builder: Builder,
+    /// Session pool, keyed by remote node. The session without a node is used when any node is
+    /// allowed and the host selects the remote node.
+    sessions: HashMap<Option<signature::PublicKey>, mpsc::Sender<SessionCommand>>,
+    /// Used transport.
+    transport: Arc<dyn Transport>,
+    /// Internal command queue (receiver part).
+    cmdq: mpsc::Receiver<Command>,
+}
+
+impl Controller {
+    async fn run(mut self) {
+        while let Some(cmd) = self.cmdq.recv().await {
+            match cmd {
+                Command::Call(request, kind, sender) => self.call(request, kind, sender),
+                Command::OpenStream(request, sender) => self.open_stream(request, sender),
+                Command::PeerFeedback(target, peer_feedback, kind) => {
+                    target.set_peer_feedback(&*self.transport, peer_feedback);
+
+                    if let Some(state) = target.node.and_then(|node| self.node_states.get(&node)) {
+                        state.update(peer_feedback);
+                    }
+
+                    // In case the peer feedback is bad, reset the session so a new peer can be
+                    // selected for a subsequent session.
+                    if !matches!(peer_feedback, types::PeerFeedback::Success)
+                        && kind == types::Kind::NoiseSession
+                    {
+                        self.reset_session(target.node);
+                    }
+                }
+                Command::UpdateEnclaves(enclaves) => {
+                    if self.builder.get_remote_enclaves() == &enclaves {
+                        continue;
+                    }
+
+                    self.builder = mem::take(&mut self.builder).remote_enclaves(enclaves);
+                    self.sessions.clear();
+                }
+                Command::UpdateQuotePolicy(policy) => {
+                    let policy = Some(Arc::new(policy));
+                    if self.builder.get_quote_policy() == &policy {
+                        continue;
+                    }
+
+                    self.builder = mem::take(&mut self.builder).quote_policy(policy);
+                    self.sessions.clear();
+                }
+                Command::UpdateRuntimeID(id) => {
+                    if self.builder.get_remote_runtime_id() == &id {
+                        continue;
+                    }
+
+                    self.builder = mem::take(&mut self.builder).remote_runtime_id(id);
+                    self.sessions.clear();
+                }
+                Command::UpdateNodes(nodes) => self.update_nodes(nodes),
+                #[cfg(test)]
+                Command::Ping(sender) => {
+                    let _ = sender.send(());
+                }
+            }
+        }
+
+        // Dropping the session pool closes all sessions.
+    }
+
+    fn update_nodes(&mut self, nodes: Vec<signature::PublicKey>) {
+        // Close sessions that are no longer allowed. Dropping the sender stops the session task.
+        self.sessions.retain(|node, _| match node {
+            Some(node) => nodes.contains(node),
+            None => nodes.is_empty(),
+        });
+        self.node_states.retain(|node, _| nodes.contains(node));
+        for node in &nodes {
+            self.node_states.entry(*node).or_default();
+        }
+        self.nodes = nodes;
+    }
+
+    /// Select the next node to use according to the configured peer selection strategy.
+    ///
+    /// Nodes whose session command queue is full are skipped so that a single slow node cannot
+    /// hold up calls that could be handled by other nodes. In case all of them are full, any node
+    /// may be selected and the call waits for capacity.
+    fn select_node(&mut self) -> Option<signature::PublicKey> {
+        if self.nodes.is_empty() {
+            return None;
+        }
+
+        let has_capacity = |node: &signature::PublicKey| {
+            self.sessions
+                .get(&Some(*node))
+                .map_or(true, |session| session.capacity() > 0)
+        };
+        let candidates: Vec<_> = if self.nodes.iter().any(has_capacity) {
+            self.nodes
+                .iter()
+                .filter(|node| has_capacity(node))
+                .collect()
+        } else {
+            self.nodes.iter().collect()
+        };
+        let peers: Vec<_> = candidates
+            .into_iter()
+            .map(|node| (*node, self.node_states[node].snapshot()))
+            .collect();
+
+        Some(self.selector.select(&peers))
+    }
+
+    fn reset_session(&mut self, node: Option<signature::PublicKey>) {
+        // Sessions where the host selects the node are not keyed by the node.
+        let key = if self.sessions.contains_key(&node) {
+            node
+        } else {
+            None
+        };
+        if let Some(session) = self.sessions.get(&key) {
+            Self::queue_session_command(session, SessionCommand::Reset);
+        }
+    }
+
+    /// Queue a command on the given session without blocking the controller.
+    ///
+    /// In case the session command queue is full, the command is queued once there is capacity.
+    /// In case the session is closed, the command is dropped, which also drops any response
+    /// channel so that the caller is notified.
+    fn queue_session_command(session: &mpsc::Sender<SessionCommand>, cmd: SessionCommand) {
+        if let Err(mpsc::error::TrySendError::Full(cmd)) = session.try_send(cmd) {
+            let session = session.clone();
+            tokio::spawn(async move {
+                let _ = session.send(cmd).await;
+            });
+        }
+    }
+
+    fn call(
+        &mut self,
+        request: types::Request,
+        kind: types::Kind,
+        sender: oneshot::Sender<CallResult>,
+    ) {
+        let node = self.select_node();
+        let guard = InFlightGuard::new(node.map(|node| self.node_states[&node].clone()));
+
+        match kind {
+            types::Kind::NoiseSession => {
+                // Calls on different sessions are processed concurrently.
+                let session = self.session(node);
+                Self::queue_session_command(session, SessionCommand::Call(request, sender, guard));
+            }
+            types::Kind::InsecureQuery => {
+                // Insecure queries are stateless so each one can be processed concurrently.
+                let transport = self.transport.clone();
+                tokio::spawn(async move {
+                    let start = Instant::now();
+                    let result = Self::insecure_call_raw(&*transport, request, node).await;
+
+                    let target = FeedbackTarget::new(
+                        &*transport,
+                        result.as_ref().ok().map(|(node, _)| *node).or(node),
+                    );
+                    if result.is_err() {
+                        // Set peer feedback immediately so retries can try new peers.
+                        target.set_peer_feedback(&*transport, types::PeerFeedback::Failure);
+                        guard.record_failure();
+                    } else {
+                        guard.record_success(start.elapsed());
+                    }
+                    drop(guard);
+
+                    let _ = sender.send(result.map(|(_, rsp)| (target, rsp)));
+                });
+            }
+            _ => {
+                let _ = sender.send(Err(RpcClientError::UnsupportedRpcKind));
+            }
+        }
+    }
+
+    fn open_stream(&mut self, request: types::Request, sender: oneshot::Sender<OpenStreamResult>) {
+        let node = self.select_node();
+        let guard = InFlightGuard::new(node.map(|node| self.node_states[&node].clone()));
+
+        // Subsequent chunks are fetched directly from the session task.
+        let session = self.session(node);
+        let cmd = SessionCommand::OpenStream(request, sender, guard, session.clone());
+        Self::queue_session_command(session, cmd);
+    }
+
+    /// Session task for the given node, spawning one if needed.
+    fn session(&mut self, node: Option<signature::PublicKey>) -> &mpsc::Sender<SessionCommand> {
+        let builder = &self.builder;
+        let transport = &self.transport;
+        self.sessions.entry(node).or_insert_with(|| {
+            let (tx, rx) = mpsc::channel(CMDQ_BACKLOG);
+            let worker = SessionWorker {
+                node,
+                session: MultiplexedSession::new(builder.clone()),
+                transport: transport.clone(),
+                cmdq: rx,
+            };
+            tokio::spawn(worker.run());
+            tx
+        })
+    }
+
+    async fn insecure_call_raw(
+        transport: &dyn Transport,
+        request: types::Request,
+        node: Option<signature::PublicKey>,
+    ) -> Result<(signature::PublicKey, types::Response), RpcClientError> {
+        let (data, node) = transport
+            .write_insecure_query(cbor::to_vec(request), node.into_iter().collect())
+            .await
+            .map_err(|_| RpcClientError::Transport)?;
+
+        let rsp = cbor::from_slice(&data).map_err(RpcClientError::DecodeError)?;
+        Ok((node, rsp))
+    }
+}
+
 /// An EnclaveRPC response that can be used to provide peer feedback.
 pub struct Response<T> {
     inner: Result<T, RpcClientError>,
     kind: types::Kind,
     cmdq: mpsc::WeakSender<Command>,
-    pfid: Option<u64>,
+    target: Option<FeedbackTarget>,
 }
 
 impl<T> Response<T> {
@@ -372,6 +813,11 @@ impl<T> Response<T> {
         self.inner
     }
 
+    /// Remote node that handled the call, if known.
+    pub fn node(&self) -> Option<&signature::PublicKey> {
+        self.target.as_ref().and_then(|target| target.node.as_ref())
+    }
+
     /// Report success as peer feedback.
     pub async fn success(&mut self) {
         self.send_peer_feedback(types::PeerFeedback::Success).await;
@@ -389,16 +835,22 @@ impl<T> Response<T> {
 
     /// Send peer feedback.
     async fn send_peer_feedback(&mut self, pf: types::PeerFeedback) {
-        if let Some(pfid) = self.pfid.take() {
+        if let Some(target) = self.target.take() {
             // Only count feedback once.
             if let Some(cmdq) = self.cmdq.upgrade() {
-                let _ = cmdq.send(Command::PeerFeedback(pfid, pf, self.kind)).await;
+                let _ = cmdq
+                    .send(Command::PeerFeedback(target, pf, self.kind))
+                    .await;
             }
         }
     }
 }
 
 /// RPC client.
+///
+/// Secure calls are performed over a pool of sessions, one for each allowed remote node, so that
+/// independent calls can proceed concurrently. Nodes are selected based on their recent health (as
+/// reported via peer feedback) and the number of calls in flight.
 pub struct RpcClient {
     /// Internal command queue (sender part).
     cmdq: mpsc::Sender<Command>,
@@ -409,17 +861,22 @@ impl RpcClient {
         transport: Box<dyn Transport>,
         builder: Builder,
         nodes: Vec<signature::PublicKey>,
+        selector: Box<dyn PeerSelector>,
     ) -> Self {
         // Create the command channel.
         let (tx, rx) = mpsc::channel(CMDQ_BACKLOG);
 
         // Create the controller task and start it.
-        let controller = Controller {
-            nodes,
-            session: MultiplexedSession::new(builder),
-            transport,
+        let mut controller = Controller {
+            nodes: vec![],
+            node_states: HashMap::new(),
+            selector,
+            builder,
+            sessions: HashMap::new(),
+            transport: Arc::from(transport),
             cmdq: rx,
         };
+        controller.update_nodes(nodes);
         tokio::spawn(controller.run());
 
         Self { cmdq: tx }
@@ -431,11 +888,30 @@ impl RpcClient {
         protocol: Arc<Protocol>,
         endpoint: &str,
         nodes: Vec<signature::PublicKey>,
+    ) -> Self {
+        Self::new_runtime_with_peer_selector(
+            builder,
+            protocol,
+            endpoint,
+            nodes,
+            Box::<LeastLoaded>::default(),
+        )
+    }
+
+    /// Construct an unconnected RPC client with runtime-internal transport that selects remote
+    /// nodes using the given strategy.
+    pub fn new_runtime_with_peer_selector(
+        builder: Builder,
+        protocol: Arc<Protocol>,
+        endpoint: &str,
+        nodes: Vec<signature::PublicKey>,
+        selector: Box<dyn PeerSelector>,
     ) -> Self {
         Self::new(
             Box::new(RuntimeTransport::new(protocol, endpoint)),
             builder,
             nodes,
+            selector,
         )
     }
 
@@ -457,6 +933,174 @@ impl RpcClient {
         self.call(method, args, types::Kind::InsecureQuery).await
     }
 
+    /// Call a remote method multiple times over a secure channel in a single round trip.
+    ///
+    /// The results of the individual calls are returned in order. Peer feedback should be given
+    /// for the batch as a whole.
+    pub async fn secure_call_batch<C, O>(
+        &self,
+        method: &'static str,
+        args: Vec<C>,
+    ) -> Response<Vec<Result<O, RpcClientError>>>
+    where
+        C: cbor::Encode,
+        O: cbor::Decode + Send + 'static,
+    {
+        self.call_batch(method, args, types::Kind::NoiseSession)
+            .await
+    }
+
+    /// Call a remote method multiple times over an insecure channel in a single round trip.
+    ///
+    /// The results of the individual calls are returned in order. Peer feedback should be given
+    /// for the batch as a whole.
+    pub async fn insecure_call_batch<C, O>(
+        &self,
+        method: &'static str,
+        args: Vec<C>,
+    ) -> Response<Vec<Result<O, RpcClientError>>>
+    where
+        C: cbor::Encode,
+        O: cbor::Decode + Send + 'static,
+    {
+        self.call_batch(method, args, types::Kind::InsecureQuery)
+            .await
+    }
+
+    /// Call a remote method with a streaming response using an encrypted and authenticated Noise
+    /// session.
+    ///
+    /// Chunks of the response are fetched on demand while the returned stream is being consumed.
+    /// The stream fails in case the session is reset before all chunks have been received and is
+    /// cancelled when dropped. Peer feedback should be given for the call that opened the stream.
+    pub async fn secure_call_stream<C, O>(
+        &self,
+        method: &'static str,
+        args: C,
+    ) -> Response<ChunkStream<O>>
+    where
+        C: cbor::Encode,
+        O: cbor::Decode + Send + 'static,
+    {
+        let request = types::Request {
+            method: method.to_owned(),
+            args: cbor::to_value(args),
+        };
+
+        let result = tokio_retry::Retry::spawn(Self::retry_strategy(), || {
+            self.execute_open_stream(request.clone())
+        })
+        .await;
+
+        let (target, inner) = match result {
+            Ok((target, response, session)) => match response.body {
+                types::Body::Success(value) => (
+                    Some(target),
+                    cbor::from_value(value)
+                        .map(|chunk| Self::chunk_stream(session, chunk))
+                        .map_err(Into::into),
+                ),
+                types::Body::Error(error) => (Some(target), Err(RpcClientError::CallFailed(error))),
+                types::Body::StructuredError(error) => (
+                    Some(target),
+                    Err(RpcClientError::from_structured_error(error)),
+                ),
+            },
+            Err(err) => (None, Err(err)),
+        };
+
+        Response {
+            inner,
+            kind: types::Kind::NoiseSession,
+            cmdq: self.cmdq.downgrade(),
+            target,
+        }
+    }
+
+    /// Stream of the data of all chunks of a streaming response, starting with the given chunk.
+    fn chunk_stream<O>(session: StreamSession, first: types::StreamChunk) -> ChunkStream<O>
+    where
+        O: cbor::Decode + Send + 'static,
+    {
+        let state = StreamState::new(session, first.stream);
+
+        stream::unfold(Some((state, Some(first))), |next| async move {
+            let (mut state, pending) = next?;
+            let chunk = match pending {
+                Some(chunk) => Ok(chunk),
+                None => state.fetch().await,
+            };
+            let result = chunk
+                .and_then(|chunk| state.verify(chunk))
+                .and_then(|data| {
+                    data.map(|value| cbor::from_value(value).map_err(Into::into))
+                        .transpose()
+                });
+
+            match result {
+                Ok(Some(item)) => Some((Ok(item), Some((state, None)))),
+                Ok(None) => None,
+                // Dropping the state cancels the stream.
+                Err(err) => Some((Err(err), None)),
+            }
+        })
+        .boxed()
+    }
+
+    async fn execute_open_stream(&self, request: types::Request) -> OpenStreamResult {
+        let (tx, rx) = oneshot::channel();
+        self.cmdq
+            .send(Command::OpenStream(request, tx))
+            .await
+            .map_err(|_| RpcClientError::Dropped)?;
+
+        rx.await.map_err(|_| RpcClientError::Dropped)?
+    }
+
+    async fn call_batch<C, O>(
+        &self,
+        method: &'static str,
+        args: Vec<C>,
+        kind: types::Kind,
+    ) -> Response<Vec<Result<O, RpcClientError>>>
+    where
+        C: cbor::Encode,
+        O: cbor::Decode + Send + 'static,
+    {
+        let requests: Vec<_> = args
+            .into_iter()
+            .map(|args| types::Request {
+                method: method.to_owned(),
+                args: cbor::to_value(args),
+            })
+            .collect();
+        let request = types::Request {
+            method: types::METHOD_BATCH.to_owned(),
+            args: cbor::to_value(requests),
+        };
+
+        let response: Response<Vec<types::Body>> = self.call_request(request, kind).await;
+        let inner = response.inner.map(|results| {
+            results
+                .into_iter()
+                .map(|body| match body {
+                    types::Body::Success(value) => cbor::from_value(value).map_err(Into::into),
+                    types::Body::Error(error) => Err(RpcClientError::CallFailed(error)),
+                    types::Body::StructuredError(error) => {
+                        Err(RpcClientError::from_structured_error(error))
+                    }
+                })
+                .collect()
+        });
+
+        Response {
+            inner,
+            kind: response.kind,
+            cmdq: response.cmdq,
+            target: response.target,
+        }
+    }
+
     async fn call<C, O>(&self, method: &'static str, args: C, kind: types::Kind) -> Response<O>
     where
         C: cbor::Encode,
@@ -467,23 +1111,30 @@ impl RpcClient {
             args: cbor::to_value(args),
         };
 
+        self.call_request(request, kind).await
+    }
+
+    async fn call_request<O>(&self, request: types::Request, kind: types::Kind) -> Response<O>
+    where
+        O: cbor::Decode + Send + 'static,
+    {
         // In case the `execute_call` method returns an outer error, this means that there was a
         // problem with the transport itself and we can retry.
-        let retry_strategy = tokio_retry::strategy::ExponentialBackoff::from_millis(2)
-            .factor(25)
-            .max_delay(std::time::Duration::from_millis(250))
-            .take(MAX_TRANSPORT_ERROR_RETRIES);
-
-        let result =
-            tokio_retry::Retry::spawn(retry_strategy, || self.execute_call(request.clone(), kind))
-                .await;
+        let result = tokio_retry::Retry::spawn(Self::retry_strategy(), || {
+            self.execute_call(request.clone(), kind)
+        })
+        .await;
 
-        let (pfid, inner) = match result {
-            Ok((pfid, response)) => match response.body {
+        let (target, inner) = match result {
+            Ok((target, response)) => match response.body {
                 types::Body::Success(value) => {
-                    (Some(pfid), cbor::from_value(value).map_err(Into::into))
+                    (Some(target), cbor::from_value(value).map_err(Into::into))
                 }
-                types::Body::Error(error) => (Some(pfid), Err(RpcClientError::CallFailed(error))),
+                types::Body::Error(error) => (Some(target), Err(RpcClientError::CallFailed(error))),
+                types::Body::StructuredError(error) => (
+                    Some(target),
+                    Err(RpcClientError::from_structured_error(error)),
+                ),
             },
             Err(err) => (None, Err(err)),
         };
@@ -492,15 +1143,18 @@ impl RpcClient {
             inner,
             kind,
             cmdq: self.cmdq.downgrade(),
-            pfid,
+            target,
         }
     }
 
-    async fn execute_call(
-        &self,
-        request: types::Request,
-        kind: types::Kind,
-    ) -> Result<(u64, types::Response), RpcClientError> {
+    fn retry_strategy() -> impl Iterator<Item = std::time::Duration> {
+        tokio_retry::strategy::ExponentialBackoff::from_millis(2)
+            .factor(25)
+            .max_delay(std::time::Duration::from_millis(250))
+            .take(MAX_TRANSPORT_ERROR_RETRIES)
+    }
+
+    async fn execute_call(&self, request: types::Request, kind: types::Kind) -> CallResult {
         let (tx, rx) = oneshot::channel();
         self.cmdq
             .send(Command::Call(request, kind, tx))
@@ -571,17 +1225,28 @@ impl RpcClient {
 
 #[cfg(test)]
 mod test {
-    use std::sync::{
-        atomic::{AtomicBool, Ordering},
-        Arc, Mutex,
+    use std::{
+        collections::HashMap,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, Mutex,
+        },
     };
 
     use anyhow::anyhow;
     use async_trait::async_trait;
+    use futures::{stream, StreamExt};
 
     use crate::{
         common::crypto::signature,
-        enclave_rpc::{demux::Demux, session, types},
+        enclave_rpc::{
+            demux::Demux,
+            dispatcher::{Dispatcher, Method, MethodDescriptor},
+            selector::LeastLoaded,
+            session,
+            stream::{ResponseStream, MAX_STREAMS_PER_SESSION},
+            types, Context,
+        },
     };
 
     use super::{super::transport::Transport, RpcClient};
@@ -589,18 +1254,25 @@ mod test {
     #[derive(Clone)]
     struct MockTransport {
         demux: Arc<Demux>,
+        dispatcher: Arc<Dispatcher>,
         next_error: Arc<AtomicBool>,
-        peer_feedback: Arc<Mutex<(u64, Option<types::PeerFeedback>)>>,
+        peer_feedback:
+            Arc<Mutex<HashMap<signature::PublicKey, (u64, Option<types::PeerFeedback>)>>>,
         peer_feedback_history: Arc<Mutex<Vec<(u64, Option<types::PeerFeedback>)>>>,
+        peer_feedback_node_history: Arc<Mutex<Vec<(signature::PublicKey, types::PeerFeedback)>>>,
+        node_history: Arc<Mutex<Vec<signature::PublicKey>>>,
     }
 
     impl MockTransport {
         fn new() -> Self {
             Self {
                 demux: Arc::new(Demux::new(session::Builder::default(), 4, 4, 60)),
+                dispatcher: Arc::new(Self::dispatcher()),
                 next_error: Arc::new(AtomicBool::new(false)),
-                peer_feedback: Arc::new(Mutex::new((0, None))),
+                peer_feedback: Arc::new(Mutex::new(HashMap::new())),
                 peer_feedback_history: Arc::new(Mutex::new(Vec::new())),
+                peer_feedback_node_history: Arc::new(Mutex::new(Vec::new())),
+                node_history: Arc::new(Mutex::new(Vec::new())),
             }
         }
 
@@ -618,10 +1290,55 @@ mod test {
                 std::mem::take(&mut pfh)
             };
             // Also add the pending feedback.
-            let pf = self.peer_feedback.lock().unwrap();
-            pfh.push(pf.clone());
+            let pfs = self.peer_feedback.lock().unwrap();
+            pfh.push(pfs.get(&Default::default()).cloned().unwrap_or_default());
             pfh
         }
+
+        fn take_peer_feedback_node_history(
+            &self,
+        ) -> Vec<(signature::PublicKey, types::PeerFeedback)> {
+            let mut pfh = self.peer_feedback_node_history.lock().unwrap();
+            std::mem::take(&mut pfh)
+        }
+
+        fn take_node_history(&self) -> Vec<signature::PublicKey> {
+            let mut nh = self.node_history.lock().unwrap();
+            std::mem::take(&mut nh)
+        }
+
+        /// Dispatcher with a streaming method that counts up to the given number.
+        fn dispatcher() -> Dispatcher {
+            let mut dispatcher = Dispatcher::default();
+            dispatcher.add_method(Method::new_stream(
+                MethodDescriptor {
+                    name: "count".to_string(),
+                    kind: types::Kind::NoiseSession,
+                    access: Default::default(),
+                },
+                |_ctx: &_, request: &u64| -> anyhow::Result<ResponseStream<u64>> {
+                    Ok(stream::iter((0..*request).map(Ok)).boxed())
+                },
+            ));
+            dispatcher
+        }
+
+        /// Just echo back what was given, for each request in case of a batch.
+        fn echo(rq: types::Request) -> types::Body {
+            if rq.method != types::METHOD_BATCH {
+                return types::Body::Success(rq.args);
+            }
+
+            let requests: Vec<types::Request> = cbor::from_value(rq.args).unwrap();
+            let results: Vec<_> = requests
+                .into_iter()
+                .map(|rq| match rq.method.as_str() {
+                    "fail" => types::Body::Error("failed".to_string()),
+                    _ => types::Body::Success(rq.args),
+                })
+                .collect();
+            types::Body::Success(cbor::to_value(results))
+        }
     }
 
     #[async_trait]
@@ -630,17 +1347,28 @@ mod test {
             &self,
             request: Vec<u8>,
             kind: types::Kind,
-            _nodes: Vec<signature::PublicKey>,
+            nodes: Vec<signature::PublicKey>,
         ) -> Result<(Vec<u8>, signature::PublicKey), anyhow::Error> {
+            // Pretend that the first of the given nodes handled the request.
+            let node = nodes.first().copied().unwrap_or_default();
+            self.node_history.lock().unwrap().push(node);
+
             let pf = {
-                let mut pf = self.peer_feedback.lock().unwrap();
-                let peer_feedback = pf.1.take();
+                let mut pfs = self.peer_feedback.lock().unwrap();
+                let pending = pfs.iter_mut().find_map(|(node, pf)| {
+                    let peer_feedback = pf.1.take()?;
 
-                if !matches!(peer_feedback, None | Some(types::PeerFeedback::Success)) {
-                    pf.0 += 1;
-                }
+                    if !matches!(peer_feedback, types::PeerFeedback::Success) {
+                        pf.0 += 1;
+                    }
 
-                (pf.0, peer_feedback)
+                    self.peer_feedback_node_history
+                        .lock()
+                        .unwrap()
+                        .push((*node, peer_feedback));
+                    Some((pf.0, Some(peer_feedback)))
+                });
+                pending.unwrap_or_else(|| (pfs.get(&node).map(|pf| pf.0).unwrap_or_default(), None))
             };
             self.peer_feedback_history.lock().unwrap().push(pf);
 
@@ -665,32 +1393,47 @@ mod test {
                     match message {
                         Some(message) => {
                             // Message, process and write reply.
-                            let body = match message {
+                            let response = match message {
+                                types::Message::Request(rq)
+                                    if matches!(
+                                        rq.method.as_str(),
+                                        "count"
+                                            | types::METHOD_STREAM_NEXT
+                                            | types::METHOD_STREAM_CANCEL
+                                    ) =>
+                                {
+                                    // Streaming calls are handled by the dispatcher.
+                                    let ctx = Context::new(None).with_streams(session.streams());
+                                    types::Message::Response(
+                                        self.dispatcher
+                                            .dispatch_async(ctx, rq, types::Kind::NoiseSession)
+                                            .await,
+                                    )
+                                }
                                 types::Message::Request(rq) => {
-                                    // Just echo back what was given.
-                                    types::Body::Success(rq.args)
+                                    let body = Self::echo(rq);
+                                    types::Message::Response(types::Response { body })
                                 }
+                                types::Message::Close => types::Message::Close,
                                 _ => panic!("unhandled message type"),
                             };
-                            let response = types::Message::Response(types::Response { body });
 
                             let mut buffer = Vec::new();
                             Ok(session
                                 .write_message(response, &mut buffer)
-                                .map(|_| (buffer, Default::default()))?)
+                                .map(|_| (buffer, node))?)
                         }
                         None => {
                             // Handshake.
-                            Ok((buffer, Default::default()))
+                            Ok((buffer, node))
                         }
                     }
                 }
                 types::Kind::InsecureQuery => {
-                    // Just echo back what was given.
                     let rq: types::Request = cbor::from_slice(&request).unwrap();
-                    let body = types::Body::Success(rq.args);
+                    let body = Self::echo(rq);
                     let response = types::Response { body };
-                    return Ok((cbor::to_vec(response), Default::default()));
+                    return Ok((cbor::to_vec(response), node));
                 }
                 types::Kind::LocalQuery => {
                     panic!("unhandled RPC kind")
@@ -698,8 +1441,14 @@ mod test {
             }
         }
 
-        fn set_peer_feedback(&self, pfid: u64, peer_feedback: Option<types::PeerFeedback>) {
-            let mut pf = self.peer_feedback.lock().unwrap();
+        fn set_peer_feedback(
+            &self,
+            node: signature::PublicKey,
+            pfid: u64,
+            peer_feedback: Option<types::PeerFeedback>,
+        ) {
+            let mut pfs = self.peer_feedback.lock().unwrap();
+            let pf = pfs.entry(node).or_default();
             if pf.0 != pfid {
                 return;
             }
@@ -707,8 +1456,13 @@ mod test {
             pf.1 = peer_feedback;
         }
 
-        fn get_peer_feedback_id(&self) -> u64 {
-            self.peer_feedback.lock().unwrap().0
+        fn get_peer_feedback_id(&self, node: &signature::PublicKey) -> u64 {
+            self.peer_feedback
+                .lock()
+                .unwrap()
+                .get(node)
+                .map(|pf| pf.0)
+                .unwrap_or_default()
         }
     }
 
@@ -718,7 +1472,12 @@ mod test {
         let _guard = rt.enter(); // Ensure Tokio runtime is available.
         let transport = MockTransport::new();
         let builder = session::Builder::default();
-        let client = RpcClient::new(Box::new(transport.clone()), builder, vec![]);
+        let client = RpcClient::new(
+            Box::new(transport.clone()),
+            builder,
+            vec![],
+            Box::<LeastLoaded>::default(),
+        );
 
         // Basic secure call.
         let result: u64 = rt
@@ -832,9 +1591,207 @@ mod test {
             transport.take_peer_feedback_history(),
             vec![
                 (2, Some(types::PeerFeedback::Success)), // Previous handled call.
-                (3, Some(types::PeerFeedback::Failure)), // Failed call due to induced error.
-                (3, Some(types::PeerFeedback::Success)), // Handled call.
+                (2, None), // Failed call due to induced error, node is unknown.
+                (2, Some(types::PeerFeedback::Success)), // Handled call.
             ]
         );
     }
+
+    #[test]
+    fn test_rpc_client_batch() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let _guard = rt.enter(); // Ensure Tokio runtime is available.
+        let transport = MockTransport::new();
+        let builder = session::Builder::default();
+        let client = RpcClient::new(
+            Box::new(transport.clone()),
+            builder,
+            vec![],
+            Box::<LeastLoaded>::default(),
+        );
+
+        let results: Vec<u64> = rt
+            .block_on(async {
+                client
+                    .secure_call_batch("test", vec![1, 2, 3])
+                    .await
+                    .into_result_with_feedback()
+                    .await
+            })
+            .unwrap()
+            .into_iter()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(results, vec![1, 2, 3], "secure batch call should work");
+
+        let results = rt
+            .block_on(async {
+                client
+                    .insecure_call_batch::<_, u64>("fail", vec![1, 2])
+                    .await
+                    .into_result_with_feedback()
+                    .await
+            })
+            .unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(
+            results.iter().all(Result::is_err),
+            "errors should be reported per call"
+        );
+    }
+
+    #[test]
+    fn test_rpc_client_backpressure() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let _guard = rt.enter(); // Ensure Tokio runtime is available.
+        let transport = MockTransport::new();
+        let builder = session::Builder::default();
+        let client = RpcClient::new(
+            Box::new(transport.clone()),
+            builder,
+            vec![],
+            Box::<LeastLoaded>::default(),
+        );
+
+        // Calls exceeding the session command queue capacity should wait instead of failing.
+        let count = 4 * super::CMDQ_BACKLOG as u64;
+        let results: Vec<u64> = rt.block_on(async {
+            let client = &client;
+            let calls = (0..count).map(|i| async move {
+                client
+                    .secure_call::<_, u64>("test", i)
+                    .await
+                    .into_result_with_feedback()
+                    .await
+            });
+            futures::future::join_all(calls)
+                .await
+                .into_iter()
+                .collect::<Result<_, _>>()
+                .unwrap()
+        });
+        assert_eq!(results, (0..count).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_rpc_client_stream() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let _guard = rt.enter(); // Ensure Tokio runtime is available.
+        let transport = MockTransport::new();
+        let builder = session::Builder::default();
+        let client = RpcClient::new(
+            Box::new(transport.clone()),
+            builder,
+            vec![],
+            Box::<LeastLoaded>::default(),
+        );
+
+        let open = |count: u64| {
+            rt.block_on(async {
+                client
+                    .secure_call_stream::<_, u64>("count", count)
+                    .await
+                    .into_result_with_feedback()
+                    .await
+                    .unwrap()
+            })
+        };
+
+        // All chunks are received in order.
+        let results: Vec<u64> = rt
+            .block_on(open(100).collect::<Vec<_>>())
+            .into_iter()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(results, (0..100).collect::<Vec<_>>());
+
+        // Streams that are dropped early are cancelled, otherwise the limit of open streams
+        // would be reached.
+        for _ in 0..2 * MAX_STREAMS_PER_SESSION {
+            let mut chunks = open(100);
+            assert_eq!(rt.block_on(chunks.next()).unwrap().unwrap(), 0);
+        }
+
+        // Streams fail when the session is lost.
+        let mut chunks = open(100);
+        assert_eq!(rt.block_on(chunks.next()).unwrap().unwrap(), 0);
+        transport.reset();
+        assert!(rt.block_on(chunks.next()).unwrap().is_err());
+        assert!(rt.block_on(chunks.next()).is_none());
+
+        // Streaming methods can be called again after the session has been re-established.
+        let results: Vec<_> = rt.block_on(open(3).collect());
+        assert_eq!(results.len(), 3);
+    }
+
+    #[test]
+    fn test_rpc_client_multiple_nodes() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let _guard = rt.enter(); // Ensure Tokio runtime is available.
+        let transport = MockTransport::new();
+        let builder = session::Builder::default();
+        let node1 = signature::PublicKey::from(vec![1; 32]);
+        let node2 = signature::PublicKey::from(vec![2; 32]);
+        let client = RpcClient::new(
+            Box::new(transport.clone()),
+            builder,
+            vec![node1, node2],
+            Box::<LeastLoaded>::default(),
+        );
+
+        // Concurrent secure calls should be spread over both nodes.
+        let results: Vec<u64> = rt.block_on(async {
+            let (a, b, c, d) = tokio::join!(
+                client.secure_call("test", 1),
+                client.secure_call("test", 2),
+                client.secure_call("test", 3),
+                client.secure_call("test", 4),
+            );
+            let mut results = vec![];
+            for rsp in [a, b, c, d] {
+                results.push(rsp.into_result_with_feedback().await.unwrap());
+            }
+            results
+        });
+        rt.block_on(client.flush_cmd_queue()).unwrap();
+        assert_eq!(results, vec![1, 2, 3, 4], "secure calls should work");
+
+        let nodes = transport.take_node_history();
+        assert!(nodes.contains(&node1), "first node should be used");
+        assert!(nodes.contains(&node2), "second node should be used");
+
+        // Report the first node as bad and make sure that subsequent calls avoid it.
+        let mut rsp = rt.block_on(async {
+            loop {
+                let rsp = client.secure_call::<_, u64>("test", 5).await;
+                if rsp.node() == Some(&node1) {
+                    break rsp;
+                }
+                rsp.into_result_with_feedback().await.unwrap();
+            }
+        });
+        rt.block_on(rsp.bad_peer());
+        rt.block_on(client.flush_cmd_queue()).unwrap();
+        transport.take_peer_feedback_node_history();
+
+        for i in 0..4 {
+            let rsp = rt.block_on(client.insecure_call::<_, u64>("test", i));
+            assert_eq!(rsp.node(), Some(&node2), "healthy node should be preferred");
+            assert_eq!(rt.block_on(rsp.into_result_with_feedback()).unwrap(), i);
+        }
+
+        // Feedback should be attributed to the node that handled the call even though the
+        // subsequent calls were handled by a different node.
+        let feedback = transport.take_peer_feedback_node_history();
+        assert!(
+            feedback.contains(&(node1, types::PeerFeedback::BadPeer)),
+            "bad peer feedback should refer to the first node"
+        );
+        assert!(
+            feedback
+                .iter()
+                .all(|(node, pf)| *node == node2 || *pf == types::PeerFeedback::BadPeer),
+            "success feedback should refer to the second node"
+        );
+    }
 }
@@ -1,17 +1,28 @@
 //! RPC call context.
 use std::sync::Arc;
 
-use super::session::SessionInfo;
+use super::{session::SessionInfo, stream::Streams};
 
 /// RPC call context.
 pub struct Context {
     /// Information about the session the RPC call was delivered over.
     pub session_info: Option<Arc<SessionInfo>>,
+    /// Open streaming responses of the session the RPC call was delivered over.
+    pub streams: Option<Arc<Streams>>,
 }
 
 impl Context {
     /// Construct new transaction context.
     pub fn new(session_info: Option<Arc<SessionInfo>>) -> Self {
-        Self { session_info }
+        Self {
+            session_info,
+            streams: None,
+        }
+    }
+
+    /// Enable streaming responses using the given per-session streams.
+    pub fn with_streams(mut self, streams: Arc<Streams>) -> Self {
+        self.streams = Some(streams);
+        self
     }
 }
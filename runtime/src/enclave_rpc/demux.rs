@@ -3,16 +3,38 @@ use std::{
     collections::{BTreeSet, HashMap},
     io::Write,
     sync::{Arc, Mutex},
+    time::Instant,
 };
 
+use lazy_static::lazy_static;
 use thiserror::Error;
 use tokio::sync::OwnedMutexGuard;
 
 use super::{
+    ratelimit::{Limits as RateLimits, RateLimiter},
     session::{Builder, Session, SessionInfo},
-    types::{Frame, Message, SessionID},
+    stream::Streams,
+    types::{Body, Frame, Message, Request, Response, SessionID, METHOD_BATCH},
 };
-use crate::common::time::insecure_posix_time;
+use crate::common::{
+    metrics::{self, Counter, Gauge},
+    time::insecure_posix_time,
+};
+
+lazy_static! {
+    static ref SESSIONS: Gauge = metrics::gauge(
+        "runtime_enclave_rpc_sessions",
+        "Number of open enclave RPC sessions.",
+    );
+    static ref REJECTED_SESSIONS: Counter = metrics::counter(
+        "runtime_enclave_rpc_rejected_sessions",
+        "Number of enclave RPC sessions rejected due to session limits.",
+    );
+    static ref RATE_LIMITED_REQUESTS: Counter = metrics::counter(
+        "runtime_enclave_rpc_rate_limited_requests",
+        "Number of enclave RPC requests rejected due to rate limits.",
+    );
+}
 
 /// Demultiplexer error.
 #[derive(Error, Debug)]
@@ -23,30 +45,19 @@ pub enum Error {
     MalformedRequestMethod,
     #[error("max concurrent sessions reached")]
     MaxConcurrentSessions,
+    #[error("rate limit exceeded")]
+    RateLimited,
     #[error("{0}")]
     Other(#[from] anyhow::Error),
 }
 
-impl Error {
-    fn code(&self) -> u32 {
-        match self {
-            Error::MalformedPayload(_) => 1,
-            Error::MalformedRequestMethod => 2,
-            Error::MaxConcurrentSessions => 3,
-            Error::Other(_) => 4,
-        }
-    }
-}
-
-impl From<Error> for crate::types::Error {
-    fn from(e: Error) -> Self {
-        Self {
-            module: "demux".to_string(),
-            code: e.code(),
-            message: e.to_string(),
-        }
-    }
-}
+impl_error_codes!(Error, "demux", {
+    Error::MalformedPayload(_) => 1,
+    Error::MalformedRequestMethod => 2,
+    Error::MaxConcurrentSessions => 3,
+    Error::Other(_) => 4,
+    Error::RateLimited => 5,
+});
 
 /// Peer identifier.
 type PeerID = Vec<u8>;
@@ -132,6 +143,7 @@ impl Sessions {
                 peer_id: peer_id.clone(),
                 session_id,
                 inner: builder.build_responder(),
+                streams: Default::default(),
             })),
             peer_id,
             session_id,
@@ -248,7 +260,6 @@ impl Sessions {
     }
 
     /// Number of all sessions.
-    #[cfg(test)]
     fn session_count(&self) -> usize {
         self.by_idle_time.len()
     }
@@ -263,6 +274,7 @@ impl Sessions {
 /// Session demultiplexer.
 pub struct Demux {
     sessions: Mutex<Sessions>,
+    rate_limiter: Mutex<RateLimiter>,
 }
 
 /// A multiplexed session.
@@ -273,6 +285,8 @@ pub struct MultiplexedSession {
     session_id: SessionID,
     /// The actual session.
     inner: Session,
+    /// Open streaming responses.
+    streams: Arc<Streams>,
 }
 
 impl MultiplexedSession {
@@ -281,6 +295,11 @@ impl MultiplexedSession {
         self.inner.session_info()
     }
 
+    /// Open streaming responses of the session.
+    pub fn streams(&self) -> Arc<Streams> {
+        self.streams.clone()
+    }
+
     /// Process incoming session data.
     async fn process_data<W: Write>(
         &mut self,
@@ -311,7 +330,45 @@ impl Demux {
                 max_sessions_per_peer,
                 stale_session_timeout,
             )),
+            rate_limiter: Mutex::new(RateLimiter::new(RateLimits::default(), max_sessions)),
+        }
+    }
+
+    /// Configure request rate limits.
+    ///
+    /// Requests which exceed the limits are rejected with an error response sent over the session
+    /// so that clients can back off while the session remains usable. By default no limits are
+    /// enforced.
+    pub fn set_rate_limits(&mut self, limits: RateLimits) {
+        let rate_limiter = self.rate_limiter.get_mut().unwrap();
+        *rate_limiter = RateLimiter::new(limits, self.sessions.get_mut().unwrap().max_sessions);
+    }
+
+    /// Check whether the given request of the given peer is within the rate limits, accounting
+    /// for each request of a batch separately. The whole batch is either allowed or rejected.
+    ///
+    /// This should also be used for requests which are not sent over a session (e.g. insecure
+    /// queries) so that the same per-peer limits apply to all requests of a peer.
+    pub fn check_rate_limits(&self, peer_id: &[u8], request: &Request) -> Result<(), Error> {
+        let batch = match request.method.as_str() {
+            METHOD_BATCH => cbor::from_value::<Vec<Request>>(request.args.clone()).ok(),
+            _ => None,
+        };
+        let methods: Vec<&str> = match batch {
+            Some(ref requests) => requests.iter().map(|req| req.method.as_str()).collect(),
+            None => vec![request.method.as_str()],
+        };
+        let allowed =
+            self.rate_limiter
+                .lock()
+                .unwrap()
+                .check_all(peer_id, &methods, Instant::now());
+        if !allowed {
+            RATE_LIMITED_REQUESTS.inc();
+            return Err(Error::RateLimited);
         }
+
+        Ok(())
     }
 
     async fn get_or_create_session(
@@ -321,7 +378,12 @@ impl Demux {
     ) -> Result<OwnedMutexGuard<MultiplexedSession>, Error> {
         let (session, _) = {
             let mut sessions = self.sessions.lock().unwrap();
-            sessions.get_or_create(peer_id, session_id)?
+            let result = sessions.get_or_create(peer_id, session_id);
+            if let Err(Error::MaxConcurrentSessions) = result {
+                REJECTED_SESSIONS.inc();
+            }
+            SESSIONS.set(sessions.session_count() as i64);
+            result?
         };
 
         Ok(session.lock_owned().await)
@@ -330,24 +392,37 @@ impl Demux {
     /// Process a frame, returning the locked session guard and decoded message.
     ///
     /// Any data that needs to be transmitted back to the peer is written to the passed writer.
+    ///
+    /// Requests which exceed the rate limits are answered directly by writing an error response
+    /// to the writer, in which case no message is returned.
     pub async fn process_frame<W: Write>(
         &self,
         peer_id: PeerID,
         data: Vec<u8>,
-        writer: W,
+        mut writer: W,
     ) -> Result<(OwnedMutexGuard<MultiplexedSession>, Option<Message>), Error> {
         // Decode frame.
         let frame: Frame = cbor::from_slice(&data)?;
         // Get the existing session or create a new one.
         let mut session = self.get_or_create_session(peer_id, frame.session).await?;
         // Process session data.
-        match session.process_data(frame.payload, writer).await {
+        match session.process_data(frame.payload, &mut writer).await {
             Ok(msg) => {
                 if let Some(Message::Request(ref req)) = msg {
                     // Make sure that the untrusted_plaintext matches the request's method.
                     if frame.untrusted_plaintext != req.method {
                         return Err(Error::MalformedRequestMethod);
                     }
+
+                    if let Err(err) = self.check_rate_limits(&session.peer_id, req) {
+                        // Reply over the session as the request has already been decrypted and
+                        // the session would otherwise need to be reset.
+                        let response = Message::Response(Response {
+                            body: Body::StructuredError(err.into()),
+                        });
+                        session.write_message(response, &mut writer)?;
+                        return Ok((session, None));
+                    }
                 }
 
                 Ok((session, msg))
@@ -357,6 +432,7 @@ impl Demux {
                 if session.inner.is_closed() {
                     let mut sessions = self.sessions.lock().unwrap();
                     sessions.remove(&session);
+                    SESSIONS.set(sessions.session_count() as i64);
                 }
                 Err(err)
             }
@@ -373,6 +449,7 @@ impl Demux {
     ) -> Result<(), Error> {
         let mut sessions = self.sessions.lock().unwrap();
         sessions.remove(&session);
+        SESSIONS.set(sessions.session_count() as i64);
 
         session.write_message(Message::Close, writer)?;
         Ok(())
@@ -382,14 +459,22 @@ impl Demux {
     pub fn reset(&self) {
         let mut sessions = self.sessions.lock().unwrap();
         sessions.clear();
+        SESSIONS.set(0);
     }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::enclave_rpc::{session::Builder, types::SessionID};
+    use crate::{
+        common::errors::ErrorCode,
+        enclave_rpc::{
+            ratelimit::{Limit, Limits as RateLimits},
+            session::Builder,
+            types::{Body, Frame, Message, Request, Response, SessionID, METHOD_BATCH},
+        },
+    };
 
-    use super::{Error, Sessions};
+    use super::{Demux, Error, Sessions};
 
     fn ids() -> (Vec<Vec<u8>>, Vec<SessionID>) {
         let peer_ids: Vec<Vec<u8>> = (1..16).map(|x| vec![x]).collect();
@@ -628,4 +713,112 @@ mod test {
         assert_eq!(sessions.session_count(), 2);
         assert_eq!(sessions.peer_count(), 2);
     }
+
+    #[test]
+    fn test_rate_limits() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        rt.block_on(async {
+            let mut demux = Demux::new(Builder::default(), 4, 4, 60);
+            demux.set_rate_limits(RateLimits::default().per_method("limited", Limit::new(1, 0.0)));
+
+            let peer_id = vec![1];
+            let session_id = SessionID::random();
+            let mut client = Builder::default().build_initiator();
+            let frame = |payload: Vec<u8>, method: &str| {
+                cbor::to_vec(Frame {
+                    session: session_id,
+                    untrusted_plaintext: method.to_string(),
+                    payload,
+                })
+            };
+
+            // Establish a session.
+            let mut buffer = vec![];
+            client.process_data(vec![], &mut buffer).await.unwrap();
+            let mut response = vec![];
+            let (_, msg) = demux
+                .process_frame(peer_id.clone(), frame(buffer, ""), &mut response)
+                .await
+                .unwrap();
+            assert!(msg.is_none());
+            client.set_remote_node(Default::default()).unwrap();
+            let mut buffer = vec![];
+            client.process_data(response, &mut buffer).await.unwrap();
+            let mut response = vec![];
+            let _ = demux
+                .process_frame(peer_id.clone(), frame(buffer, ""), &mut response)
+                .await
+                .unwrap();
+            assert!(client.is_connected());
+
+            for (method, limited) in [("limited", false), ("limited", true), ("other", false)] {
+                let request = Message::Request(Request {
+                    method: method.to_string(),
+                    args: cbor::Value::Simple(cbor::SimpleValue::NullValue),
+                });
+                let mut buffer = vec![];
+                client.write_message(request, &mut buffer).unwrap();
+
+                let mut response = vec![];
+                let (mut session, msg) = demux
+                    .process_frame(peer_id.clone(), frame(buffer, method), &mut response)
+                    .await
+                    .unwrap();
+                if limited {
+                    // Rate limited requests are answered over the session by the demultiplexer.
+                    assert!(
+                        msg.is_none(),
+                        "rate limited request should not be dispatched"
+                    );
+                } else {
+                    assert!(matches!(msg, Some(Message::Request(_))));
+                    let reply = Message::Response(Response {
+                        body: Body::Success(cbor::Value::Simple(cbor::SimpleValue::NullValue)),
+                    });
+                    session.write_message(reply, &mut response).unwrap();
+                }
+                drop(session);
+
+                // The session must remain usable after a rate limited request.
+                let msg = client.process_data(response, vec![]).await.unwrap();
+                match msg {
+                    Some(Message::Response(Response {
+                        body: Body::StructuredError(err),
+                    })) => {
+                        assert!(limited, "unexpected error: {}", err);
+                        assert!(Error::RateLimited.matches(&err));
+                    }
+                    Some(Message::Response(Response {
+                        body: Body::Success(_),
+                    })) => assert!(!limited, "request should be rate limited"),
+                    msg => panic!("unexpected message: {:?}", msg),
+                }
+            }
+        });
+    }
+
+    #[test]
+    fn test_rate_limits_batch() {
+        let mut demux = Demux::new(Builder::default(), 4, 4, 60);
+        demux.set_rate_limits(RateLimits::default().per_peer(Limit::new(3, 0.0)));
+
+        let request = |method: &str| Request {
+            method: method.to_string(),
+            args: cbor::Value::Simple(cbor::SimpleValue::NullValue),
+        };
+        let batch = Request {
+            method: METHOD_BATCH.to_string(),
+            args: cbor::to_value(vec![request("a"), request("b")]),
+        };
+
+        // Each request of a batch is accounted for separately.
+        let peer_id = vec![1];
+        demux.check_rate_limits(&peer_id, &batch).unwrap();
+        demux.check_rate_limits(&peer_id, &request("a")).unwrap();
+        let err = demux.check_rate_limits(&peer_id, &batch).unwrap_err();
+        assert!(matches!(err, Error::RateLimited));
+
+        // Limits are per peer.
+        demux.check_rate_limits(&[2], &batch).unwrap();
+    }
 }
@@ -1,16 +1,51 @@
 //! RPC dispatcher.
-use std::collections::HashMap;
+use std::{collections::HashMap, future::Future};
 
 use anyhow::{bail, Result};
+use futures::{future::BoxFuture, StreamExt};
+use lazy_static::lazy_static;
 use thiserror::Error;
 
-use crate::{common::sgx::QuotePolicy, consensus::state::keymanager::Status as KeyManagerStatus};
+use crate::{
+    common::{
+        metrics::{self, Counter},
+        sgx::QuotePolicy,
+    },
+    consensus::state::keymanager::Status as KeyManagerStatus,
+    future::block_on,
+    BUILD_INFO,
+};
 
 use super::{
+    access::AccessPolicy,
     context::Context,
-    types::{Body, Kind, Request, Response},
+    stream::{ResponseStream, StreamError},
+    types::{
+        Body, DescribeResponse, Kind, MethodDescription, Request, Response, StreamCancelRequest,
+        StreamNextRequest, DESCRIBE_VERSION, MAX_BATCH_SIZE, METHOD_BATCH, METHOD_DESCRIBE,
+        METHOD_STREAM_CANCEL, METHOD_STREAM_NEXT,
+    },
 };
 
+/// Names of the built-in methods.
+const BUILTIN_METHODS: &[&str] = &[
+    METHOD_BATCH,
+    METHOD_DESCRIBE,
+    METHOD_STREAM_CANCEL,
+    METHOD_STREAM_NEXT,
+];
+
+lazy_static! {
+    static ref REQUESTS: Counter = metrics::counter(
+        "runtime_enclave_rpc_requests",
+        "Number of dispatched enclave RPC requests.",
+    );
+    static ref FAILURES: Counter = metrics::counter(
+        "runtime_enclave_rpc_failures",
+        "Number of dispatched enclave RPC requests that resulted in an error.",
+    );
+}
+
 /// Dispatch error.
 #[derive(Error, Debug)]
 enum DispatchError {
@@ -18,6 +53,10 @@ enum DispatchError {
     MethodNotFound { method: String },
     #[error("invalid RPC kind: {method:?} ({kind:?})")]
     InvalidRpcKind { method: String, kind: Kind },
+    #[error("batch too large: {size} (max {max})")]
+    BatchTooLarge { size: usize, max: usize },
+    #[error("nested batches are not supported")]
+    NestedBatch,
 }
 
 /// RPC handler.
@@ -27,12 +66,32 @@ pub trait Handler {
 }
 
 /// Descriptor of a RPC API method.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct MethodDescriptor {
     /// Method name.
     pub name: String,
     /// Specifies which kind of RPC is allowed to call the method.
     pub kind: Kind,
+    /// Access control policy which is checked before the method is invoked.
+    pub access: AccessPolicy,
+}
+
+impl MethodDescriptor {
+    /// Create a new method descriptor which allows anyone that may make calls of the given kind
+    /// to call the method.
+    pub fn new(name: &str, kind: Kind) -> Self {
+        Self {
+            name: name.to_string(),
+            kind,
+            access: AccessPolicy::Any,
+        }
+    }
+
+    /// Set the access control policy which is checked before the method is invoked.
+    pub fn with_access(mut self, access: AccessPolicy) -> Self {
+        self.access = access;
+        self
+    }
 }
 
 /// Handler for a RPC method.
@@ -52,6 +111,41 @@ where
     }
 }
 
+/// Asynchronous handler for a RPC method.
+pub trait AsyncMethodHandler<Rq, Rsp> {
+    /// Invoke the method implementation and return a response.
+    fn handle<'a>(&'a self, ctx: &'a Context, request: &'a Rq) -> BoxFuture<'a, Result<Rsp>>;
+}
+
+impl<Rq, Rsp, F> AsyncMethodHandler<Rq, Rsp> for F
+where
+    Rq: 'static,
+    Rsp: 'static,
+    F: for<'a> Fn(&'a Context, &'a Rq) -> BoxFuture<'a, Result<Rsp>> + 'static,
+{
+    fn handle<'a>(&'a self, ctx: &'a Context, request: &'a Rq) -> BoxFuture<'a, Result<Rsp>> {
+        (*self)(ctx, request)
+    }
+}
+
+/// Asynchronous function that can be used as a handler for a RPC method.
+///
+/// This is implemented for `async fn` items that take references to the context and request.
+/// Their futures borrow from the arguments, which cannot be expressed using a plain `Fn` bound.
+pub trait AsyncMethodFn<'a, Rq: 'a, Rsp>: Fn(&'a Context, &'a Rq) -> Self::Future {
+    /// Future returned by the function.
+    type Future: Future<Output = Result<Rsp>> + Send + 'a;
+}
+
+impl<'a, Rq, Rsp, F, Fut> AsyncMethodFn<'a, Rq, Rsp> for F
+where
+    Rq: 'a,
+    F: Fn(&'a Context, &'a Rq) -> Fut,
+    Fut: Future<Output = Result<Rsp>> + Send + 'a,
+{
+    type Future = Fut;
+}
+
 /// Dispatcher for a RPC method.
 pub trait MethodHandlerDispatch {
     /// Get method descriptor.
@@ -87,10 +181,105 @@ where
     }
 }
 
+/// Dispatcher for an asynchronous RPC method.
+pub trait AsyncMethodHandlerDispatch {
+    /// Get method descriptor.
+    fn get_descriptor(&self) -> &MethodDescriptor;
+
+    /// Dispatch request.
+    fn dispatch<'a>(
+        &'a self,
+        ctx: &'a Context,
+        request: Request,
+    ) -> BoxFuture<'a, Result<Response>>;
+}
+
+struct AsyncMethodHandlerDispatchImpl<Rq, Rsp> {
+    /// Method descriptor.
+    descriptor: MethodDescriptor,
+    /// Method handler.
+    handler: Box<dyn AsyncMethodHandler<Rq, Rsp> + Send + Sync>,
+}
+
+impl<Rq, Rsp> AsyncMethodHandlerDispatch for AsyncMethodHandlerDispatchImpl<Rq, Rsp>
+where
+    Rq: cbor::Decode + Send + Sync + 'static,
+    Rsp: cbor::Encode + 'static,
+{
+    fn get_descriptor(&self) -> &MethodDescriptor {
+        &self.descriptor
+    }
+
+    fn dispatch<'a>(
+        &'a self,
+        ctx: &'a Context,
+        request: Request,
+    ) -> BoxFuture<'a, Result<Response>> {
+        Box::pin(async move {
+            let request: Rq = cbor::from_value(request.args)?;
+            let response = self.handler.handle(ctx, &request).await?;
+
+            Ok(Response {
+                body: Body::Success(cbor::to_value(response)),
+            })
+        })
+    }
+}
+
+struct StreamMethodHandlerDispatchImpl<Rq, Rsp> {
+    /// Method descriptor.
+    descriptor: MethodDescriptor,
+    /// Method handler.
+    handler: Box<dyn MethodHandler<Rq, ResponseStream<Rsp>> + Send + Sync>,
+}
+
+impl<Rq, Rsp> AsyncMethodHandlerDispatch for StreamMethodHandlerDispatchImpl<Rq, Rsp>
+where
+    Rq: cbor::Decode + Send + Sync + 'static,
+    Rsp: cbor::Encode + 'static,
+{
+    fn get_descriptor(&self) -> &MethodDescriptor {
+        &self.descriptor
+    }
+
+    fn dispatch<'a>(
+        &'a self,
+        ctx: &'a Context,
+        request: Request,
+    ) -> BoxFuture<'a, Result<Response>> {
+        Box::pin(async move {
+            let streams = ctx.streams.as_ref().ok_or(StreamError::NotSupported)?;
+            let request: Rq = cbor::from_value(request.args)?;
+            let stream = self
+                .handler
+                .handle(ctx, &request)?
+                .map(|chunk| chunk.map(cbor::to_value))
+                .boxed();
+            let chunk = streams.open(stream).await?;
+
+            Ok(Response {
+                body: Body::Success(cbor::to_value(chunk)),
+            })
+        })
+    }
+}
+
+/// Method dispatcher, either synchronous or asynchronous.
+enum MethodDispatcher {
+    Sync(Box<dyn MethodHandlerDispatch + Send + Sync>),
+    Async(Box<dyn AsyncMethodHandlerDispatch + Send + Sync>),
+}
+
 /// RPC method dispatcher implementation.
 pub struct Method {
     /// Method dispatcher.
-    dispatcher: Box<dyn MethodHandlerDispatch + Send + Sync>,
+    dispatcher: MethodDispatcher,
+    /// Schema name of the request type.
+    request_schema: String,
+    /// Schema name of the response type.
+    response_schema: String,
+    /// Whether the method returns a streaming response.
+    streaming: bool,
 }
 
 impl Method {
@@ -102,26 +291,167 @@ impl Method {
         Handler: MethodHandler<Rq, Rsp> + Send + Sync + 'static,
     {
         Method {
-            dispatcher: Box::new(MethodHandlerDispatchImpl {
+            dispatcher: MethodDispatcher::Sync(Box::new(MethodHandlerDispatchImpl {
                 descriptor: method,
                 handler: Box::new(handler),
-            }),
+            })),
+            request_schema: String::new(),
+            response_schema: String::new(),
+            streaming: false,
+        }
+    }
+
+    /// Create a new enclave method descriptor with an asynchronous handler.
+    ///
+    /// Asynchronous handlers are dispatched on the runtime's Tokio runtime instead of blocking a
+    /// thread while waiting for other asynchronous work to complete.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// Method::new_async(descriptor, move |ctx, request: &FetchRequest| {
+    ///     let storage = storage.clone();
+    ///     Box::pin(async move { storage.fetch(ctx, request).await })
+    /// })
+    /// ```
+    ///
+    /// Handlers implemented as an `async fn` can be used directly via [`Method::new_async_fn`].
+    pub fn new_async<Rq, Rsp, Handler>(method: MethodDescriptor, handler: Handler) -> Self
+    where
+        Rq: cbor::Decode + Send + Sync + 'static,
+        Rsp: cbor::Encode + 'static,
+        Handler:
+            for<'a> Fn(&'a Context, &'a Rq) -> BoxFuture<'a, Result<Rsp>> + Send + Sync + 'static,
+    {
+        Method {
+            dispatcher: MethodDispatcher::Async(Box::new(AsyncMethodHandlerDispatchImpl {
+                descriptor: method,
+                handler: Box::new(handler),
+            })),
+            request_schema: String::new(),
+            response_schema: String::new(),
+            streaming: false,
+        }
+    }
+
+    /// Create a new enclave method descriptor with an `async fn` handler.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// async fn fetch(ctx: &Context, request: &FetchRequest) -> Result<FetchResponse> {
+    ///     // ...
+    /// }
+    ///
+    /// Method::new_async_fn(descriptor, fetch)
+    /// ```
+    pub fn new_async_fn<Rq, Rsp, Handler>(method: MethodDescriptor, handler: Handler) -> Self
+    where
+        Rq: cbor::Decode + Send + Sync + 'static,
+        Rsp: cbor::Encode + 'static,
+        Handler: for<'a> AsyncMethodFn<'a, Rq, Rsp> + Send + Sync + 'static,
+    {
+        Self::new_async(method, move |ctx, request| Box::pin(handler(ctx, request)))
+    }
+
+    /// Create a new enclave method descriptor with a streaming response.
+    ///
+    /// The handler returns a stream of chunks which are delivered to the caller one by one, each
+    /// in a separate round trip. This allows returning responses that would not fit into a single
+    /// message. Streaming methods can only be called over secure sessions.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// fn export(ctx: &Context, request: &ExportRequest) -> Result<ResponseStream<Vec<Key>>> {
+    ///     let keys = load_keys(request)?;
+    ///     Ok(stream::iter(keys.chunks(100).map(|keys| Ok(keys.to_vec()))).boxed())
+    /// }
+    ///
+    /// Method::new_stream(descriptor, export)
+    /// ```
+    pub fn new_stream<Rq, Rsp, Handler>(method: MethodDescriptor, handler: Handler) -> Self
+    where
+        Rq: cbor::Decode + Send + Sync + 'static,
+        Rsp: cbor::Encode + 'static,
+        Handler: MethodHandler<Rq, ResponseStream<Rsp>> + Send + Sync + 'static,
+    {
+        Method {
+            dispatcher: MethodDispatcher::Async(Box::new(StreamMethodHandlerDispatchImpl {
+                descriptor: method,
+                handler: Box::new(handler),
+            })),
+            request_schema: String::new(),
+            response_schema: String::new(),
+            streaming: true,
+        }
+    }
+
+    /// Set the schema names of the request and response types, reported by the built-in describe
+    /// method as hints to clients.
+    ///
+    /// The names are part of the method's public interface, so they should be stable and must not
+    /// be derived from Rust type paths. Methods without a schema report empty names.
+    pub fn with_schema(mut self, request: &str, response: &str) -> Self {
+        self.request_schema = request.to_string();
+        self.response_schema = response.to_string();
+        self
+    }
+
+    fn get_descriptor(&self) -> &MethodDescriptor {
+        match &self.dispatcher {
+            MethodDispatcher::Sync(dispatcher) => dispatcher.get_descriptor(),
+            MethodDispatcher::Async(dispatcher) => dispatcher.get_descriptor(),
         }
     }
 
     /// Return method name.
     fn get_name(&self) -> &String {
-        &self.dispatcher.get_descriptor().name
+        &self.get_descriptor().name
     }
 
     /// Return RPC call kind.
     fn get_kind(&self) -> Kind {
-        self.dispatcher.get_descriptor().kind
+        self.get_descriptor().kind
+    }
+
+    /// Return method description.
+    fn describe(&self) -> MethodDescription {
+        MethodDescription {
+            name: self.get_name().clone(),
+            kind: self.get_kind(),
+            request: self.request_schema.clone(),
+            response: self.response_schema.clone(),
+            streaming: self.streaming,
+            ..Default::default()
+        }
+    }
+
+    /// Whether the method can only be called locally.
+    fn is_local_only(&self) -> bool {
+        let descriptor = self.get_descriptor();
+        descriptor.kind == Kind::LocalQuery || descriptor.access.is_local_only()
+    }
+
+    /// Whether the method has an asynchronous handler.
+    fn is_async(&self) -> bool {
+        matches!(self.dispatcher, MethodDispatcher::Async(_))
     }
 
     /// Dispatch a request.
-    fn dispatch(&self, ctx: &mut Context, request: Request) -> Result<Response> {
-        self.dispatcher.dispatch(ctx, request)
+    fn dispatch(&self, ctx: &Context, request: Request) -> Result<Response> {
+        match &self.dispatcher {
+            MethodDispatcher::Sync(dispatcher) => dispatcher.dispatch(ctx, request),
+            MethodDispatcher::Async(dispatcher) => block_on(dispatcher.dispatch(ctx, request)),
+        }
+    }
+
+    /// Dispatch a request asynchronously.
+    async fn dispatch_async(&self, ctx: &Context, request: Request) -> Result<Response> {
+        match &self.dispatcher {
+            MethodDispatcher::Sync(dispatcher) => dispatcher.dispatch(ctx, request),
+            MethodDispatcher::Async(dispatcher) => dispatcher.dispatch(ctx, request).await,
+        }
     }
 }
 
@@ -143,7 +473,16 @@ pub struct Dispatcher {
 
 impl Dispatcher {
     /// Register a new method in the dispatcher.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic in case the method name is reserved for a built-in method.
     pub fn add_method(&mut self, method: Method) {
+        assert!(
+            !BUILTIN_METHODS.contains(&method.get_name().as_str()),
+            "method name is reserved: {}",
+            method.get_name()
+        );
         self.methods.insert(method.get_name().clone(), method);
     }
 
@@ -154,37 +493,239 @@ impl Dispatcher {
         }
     }
 
+    /// Whether the given request should be dispatched asynchronously via [`dispatch_async`].
+    ///
+    /// [`dispatch_async`]: Self::dispatch_async
+    pub fn is_async(&self, request: &Request) -> bool {
+        if request.method == METHOD_STREAM_NEXT {
+            return true;
+        }
+
+        self.methods
+            .get(&request.method)
+            .map(Method::is_async)
+            .unwrap_or_default()
+    }
+
     /// Dispatch request.
-    pub fn dispatch(&self, mut ctx: Context, request: Request, kind: Kind) -> Response {
-        match self.dispatch_fallible(&mut ctx, request, kind) {
-            Ok(response) => response,
-            Err(error) => Response {
-                body: Body::Error(format!("{error}")),
-            },
+    ///
+    /// Asynchronous methods are blocked on, so this must not be called from an asynchronous
+    /// execution context.
+    pub fn dispatch(&self, ctx: Context, request: Request, kind: Kind) -> Response {
+        if request.method != METHOD_BATCH {
+            return self.dispatch_one(&ctx, request, kind);
         }
+
+        let result = Self::decode_batch(request).map(|requests| {
+            let results: Vec<_> = requests
+                .into_iter()
+                .map(|request| self.dispatch_one(&ctx, request, kind).body)
+                .collect();
+            Self::encode_batch(results)
+        });
+        Self::into_response(result)
     }
 
-    fn dispatch_fallible(
-        &self,
-        ctx: &mut Context,
-        request: Request,
-        kind: Kind,
-    ) -> Result<Response> {
+    fn dispatch_one(&self, ctx: &Context, request: Request, kind: Kind) -> Response {
+        REQUESTS.inc();
+
+        match request.method.as_str() {
+            METHOD_DESCRIBE => return Self::into_response(self.dispatch_describe(kind)),
+            METHOD_STREAM_NEXT | METHOD_STREAM_CANCEL => {
+                return Self::into_response(block_on(Self::dispatch_stream(ctx, request, kind)))
+            }
+            _ => {}
+        }
+
+        let result = self
+            .lookup(ctx, &request, kind)
+            .and_then(|method| method.dispatch(ctx, request));
+        Self::into_response(result)
+    }
+
+    /// Dispatch request asynchronously.
+    ///
+    /// Synchronous methods are invoked directly, blocking the current task.
+    pub async fn dispatch_async(&self, ctx: Context, request: Request, kind: Kind) -> Response {
+        if request.method != METHOD_BATCH {
+            return self.dispatch_one_async(&ctx, request, kind).await;
+        }
+
+        let requests = match Self::decode_batch(request) {
+            Ok(requests) => requests,
+            Err(err) => return Self::into_response(Err(err)),
+        };
+        let mut results = Vec::with_capacity(requests.len());
+        for request in requests {
+            results.push(self.dispatch_one_async(&ctx, request, kind).await.body);
+        }
+        Self::into_response(Ok(Self::encode_batch(results)))
+    }
+
+    async fn dispatch_one_async(&self, ctx: &Context, request: Request, kind: Kind) -> Response {
+        REQUESTS.inc();
+
+        match request.method.as_str() {
+            METHOD_DESCRIBE => return Self::into_response(self.dispatch_describe(kind)),
+            METHOD_STREAM_NEXT | METHOD_STREAM_CANCEL => {
+                return Self::into_response(Self::dispatch_stream(ctx, request, kind).await)
+            }
+            _ => {}
+        }
+
+        let result = match self.lookup(ctx, &request, kind) {
+            Ok(method) => method.dispatch_async(ctx, request).await,
+            Err(err) => Err(err),
+        };
+        Self::into_response(result)
+    }
+
+    /// Decode the requests of a batch.
+    pub(crate) fn decode_batch(request: Request) -> Result<Vec<Request>> {
+        let requests: Vec<Request> = cbor::from_value(request.args)?;
+        if requests.len() > MAX_BATCH_SIZE {
+            bail!(DispatchError::BatchTooLarge {
+                size: requests.len(),
+                max: MAX_BATCH_SIZE,
+            });
+        }
+        Ok(requests)
+    }
+
+    fn encode_batch(results: Vec<Body>) -> Response {
+        Response {
+            body: Body::Success(cbor::to_value(results)),
+        }
+    }
+
+    fn lookup(&self, ctx: &Context, request: &Request, kind: Kind) -> Result<&Method> {
+        if request.method == METHOD_BATCH {
+            bail!(DispatchError::NestedBatch);
+        }
+
         let method = match self.methods.get(&request.method) {
             Some(method) => method,
             None => bail!(DispatchError::MethodNotFound {
-                method: request.method,
+                method: request.method.clone(),
             }),
         };
 
         if method.get_kind() != kind {
+            bail!(DispatchError::InvalidRpcKind {
+                method: request.method.clone(),
+                kind,
+            });
+        };
+
+        method
+            .get_descriptor()
+            .access
+            .authorize(ctx, kind, &request.args)?;
+
+        Ok(method)
+    }
+
+    /// Describe the registered methods, including the built-in ones, that may be called by
+    /// callers using the given kind of RPC.
+    ///
+    /// Methods which can only be called locally are only described to local callers.
+    pub fn describe(&self, kind: Kind) -> DescribeResponse {
+        let mut methods: Vec<_> = self
+            .methods
+            .values()
+            .filter(|method| kind == Kind::LocalQuery || !method.is_local_only())
+            .map(Method::describe)
+            .collect();
+        methods.push(MethodDescription {
+            name: METHOD_BATCH.to_string(),
+            kind: Kind::NoiseSession,
+            // Batches are allowed for any kind, each request is checked against its method.
+            other_kinds: match kind {
+                Kind::LocalQuery => vec![Kind::InsecureQuery, Kind::LocalQuery],
+                _ => vec![Kind::InsecureQuery],
+            },
+            request: "[]Request".to_string(),
+            response: "[]Body".to_string(),
+            ..Default::default()
+        });
+        methods.push(MethodDescription {
+            name: METHOD_DESCRIBE.to_string(),
+            kind: Kind::InsecureQuery,
+            request: "null".to_string(),
+            response: "DescribeResponse".to_string(),
+            ..Default::default()
+        });
+        methods.push(MethodDescription {
+            name: METHOD_STREAM_NEXT.to_string(),
+            kind: Kind::NoiseSession,
+            request: "StreamNextRequest".to_string(),
+            response: "StreamChunk".to_string(),
+            ..Default::default()
+        });
+        methods.push(MethodDescription {
+            name: METHOD_STREAM_CANCEL.to_string(),
+            kind: Kind::NoiseSession,
+            request: "StreamCancelRequest".to_string(),
+            response: "null".to_string(),
+            ..Default::default()
+        });
+        methods.sort_by(|a, b| a.name.cmp(&b.name));
+
+        DescribeResponse {
+            version: DESCRIBE_VERSION,
+            protocol_version: BUILD_INFO.protocol_version,
+            methods,
+        }
+    }
+
+    fn dispatch_describe(&self, kind: Kind) -> Result<Response> {
+        if kind != Kind::InsecureQuery {
+            bail!(DispatchError::InvalidRpcKind {
+                method: METHOD_DESCRIBE.to_string(),
+                kind,
+            });
+        }
+
+        Ok(Response {
+            body: Body::Success(cbor::to_value(self.describe(kind))),
+        })
+    }
+
+    /// Dispatch the built-in methods used to consume streaming responses.
+    async fn dispatch_stream(ctx: &Context, request: Request, kind: Kind) -> Result<Response> {
+        if kind != Kind::NoiseSession {
             bail!(DispatchError::InvalidRpcKind {
                 method: request.method,
                 kind,
             });
+        }
+        let streams = ctx.streams.as_ref().ok_or(StreamError::NotSupported)?;
+
+        let body = if request.method == METHOD_STREAM_NEXT {
+            let request: StreamNextRequest = cbor::from_value(request.args)?;
+            cbor::to_value(streams.next(request.stream, request.seq).await?)
+        } else {
+            let request: StreamCancelRequest = cbor::from_value(request.args)?;
+            streams.cancel(request.stream);
+            cbor::to_value(())
         };
 
-        method.dispatch(ctx, request)
+        Ok(Response {
+            body: Body::Success(body),
+        })
+    }
+
+    fn into_response(result: Result<Response>) -> Response {
+        match result {
+            Ok(response) => response,
+            Err(error) => {
+                FAILURES.inc();
+
+                Response {
+                    body: Body::Error(format!("{error}")),
+                }
+            }
+        }
     }
 
     /// Handle key manager status update.
@@ -217,3 +758,318 @@ impl Dispatcher {
         self.km_quote_policy_handler = f;
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use super::{
+        super::{stream::Streams, types::StreamChunk},
+        *,
+    };
+    use crate::common::namespace::Namespace;
+
+    async fn add_async(_ctx: &Context, request: &u64) -> Result<u64> {
+        tokio::task::yield_now().await;
+        Ok(request + 1)
+    }
+
+    fn dispatcher() -> Dispatcher {
+        let mut dispatcher = Dispatcher::default();
+        dispatcher.add_methods(vec![
+            Method::new(
+                MethodDescriptor {
+                    name: "add".to_string(),
+                    kind: Kind::NoiseSession,
+                    access: AccessPolicy::Any,
+                },
+                |_ctx: &_, request: &u64| Ok(request + 1),
+            )
+            .with_schema("uint64", "uint64"),
+            Method::new_async_fn(
+                MethodDescriptor {
+                    name: "add_async".to_string(),
+                    kind: Kind::NoiseSession,
+                    access: AccessPolicy::Any,
+                },
+                add_async,
+            ),
+            Method::new(
+                MethodDescriptor {
+                    name: "add_local".to_string(),
+                    kind: Kind::NoiseSession,
+                    access: AccessPolicy::All(vec![
+                        AccessPolicy::custom(|_ctx| Ok(())),
+                        AccessPolicy::LocalOnly,
+                    ]),
+                },
+                |_ctx: &_, request: &u64| Ok(request + 1),
+            ),
+            Method::new(
+                MethodDescriptor {
+                    name: "add_enclave".to_string(),
+                    kind: Kind::NoiseSession,
+                    access: AccessPolicy::Enclaves(Default::default()),
+                },
+                |_ctx: &_, request: &u64| Ok(request + 1),
+            ),
+            Method::new_stream(
+                MethodDescriptor {
+                    name: "count".to_string(),
+                    kind: Kind::NoiseSession,
+                    access: AccessPolicy::Any,
+                },
+                |_ctx: &_, request: &u64| -> Result<ResponseStream<u64>> {
+                    Ok(futures::stream::iter((0..*request).map(Ok)).boxed())
+                },
+            ),
+        ]);
+        dispatcher
+    }
+
+    fn request(method: &str) -> Request {
+        Request {
+            method: method.to_string(),
+            args: cbor::to_value(41u64),
+        }
+    }
+
+    #[test]
+    fn test_dispatch() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let dispatcher = dispatcher();
+        let check = |rsp: Response| match rsp.body {
+            Body::Success(value) => assert_eq!(cbor::from_value::<u64>(value).unwrap(), 42),
+            body => panic!("unexpected response: {:?}", body),
+        };
+
+        assert!(!dispatcher.is_async(&request("add")));
+        assert!(dispatcher.is_async(&request("add_async")));
+
+        for method in ["add", "add_async"] {
+            let rsp = rt.block_on(dispatcher.dispatch_async(
+                Context::new(None),
+                request(method),
+                Kind::NoiseSession,
+            ));
+            check(rsp);
+
+            // Synchronous dispatch of both kinds of methods should also work.
+            let _guard = rt.enter();
+            let rsp = dispatcher.dispatch(Context::new(None), request(method), Kind::NoiseSession);
+            check(rsp);
+        }
+
+        let rsp = rt.block_on(dispatcher.dispatch_async(
+            Context::new(None),
+            request("add_async"),
+            Kind::InsecureQuery,
+        ));
+        assert!(matches!(rsp.body, Body::Error(_)));
+    }
+
+    #[test]
+    fn test_access_policy() {
+        let dispatcher = dispatcher();
+
+        // Policies are checked before the handler is invoked.
+        for method in ["add_local", "add_enclave"] {
+            let rsp = dispatcher.dispatch(Context::new(None), request(method), Kind::NoiseSession);
+            assert!(matches!(rsp.body, Body::Error(_)));
+        }
+
+        // Policies may depend on the request.
+        #[derive(Default, cbor::Encode, cbor::Decode)]
+        struct RuntimeRequest {
+            runtime_id: Namespace,
+            value: u64,
+        }
+        let allowed = Namespace::from(vec![1; 32]);
+        let policy = AccessPolicy::All(vec![
+            AccessPolicy::Runtimes(std::iter::once(allowed).collect()),
+            AccessPolicy::request(|_ctx, rq: &RuntimeRequest| match rq.value {
+                0..=10 => Ok(()),
+                _ => bail!("value too large"),
+            }),
+        ]);
+        let authorize = |runtime_id, value| {
+            let args = cbor::to_value(RuntimeRequest { runtime_id, value });
+            policy.authorize(&Context::new(None), Kind::NoiseSession, &args)
+        };
+        assert!(authorize(allowed, 1).is_ok());
+        assert!(authorize(Namespace::default(), 1).is_err());
+        assert!(authorize(allowed, 11).is_err());
+        let args = cbor::to_value(41u64);
+        assert!(policy
+            .authorize(&Context::new(None), Kind::NoiseSession, &args)
+            .is_err());
+    }
+
+    #[test]
+    fn test_describe() {
+        let dispatcher = dispatcher();
+        let describe = Request {
+            method: METHOD_DESCRIBE.to_string(),
+            args: cbor::Value::Simple(cbor::SimpleValue::NullValue),
+        };
+
+        let rsp = dispatcher.dispatch(Context::new(None), describe.clone(), Kind::NoiseSession);
+        assert!(matches!(rsp.body, Body::Error(_)));
+
+        let rsp = dispatcher.dispatch(Context::new(None), describe, Kind::InsecureQuery);
+        let rsp: DescribeResponse = match rsp.body {
+            Body::Success(value) => cbor::from_value(value).unwrap(),
+            body => panic!("unexpected response: {:?}", body),
+        };
+        assert_eq!(rsp.version, DESCRIBE_VERSION);
+        assert_eq!(
+            rsp.methods
+                .iter()
+                .map(|m| m.name.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                METHOD_BATCH,
+                METHOD_DESCRIBE,
+                METHOD_STREAM_CANCEL,
+                METHOD_STREAM_NEXT,
+                "add",
+                "add_async",
+                "add_enclave",
+                "count",
+            ],
+            "local methods should not be described to remote callers",
+        );
+        assert_eq!(rsp.methods[0].kind, Kind::NoiseSession);
+        assert_eq!(rsp.methods[0].other_kinds, vec![Kind::InsecureQuery]);
+        assert_eq!(rsp.methods[4].kind, Kind::NoiseSession);
+        assert!(rsp.methods[4].other_kinds.is_empty());
+        assert_eq!(rsp.methods[4].request, "uint64");
+        assert_eq!(rsp.methods[4].response, "uint64");
+        assert!(!rsp.methods[4].streaming);
+        assert_eq!(rsp.methods[5].request, "");
+        assert!(rsp.methods[7].streaming);
+
+        // Local callers see all methods.
+        let rsp = dispatcher.describe(Kind::LocalQuery);
+        assert!(rsp.methods.iter().any(|m| m.name == "add_local"));
+    }
+
+    #[test]
+    fn test_batch() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let dispatcher = dispatcher();
+        let batch = |requests: Vec<Request>| Request {
+            method: METHOD_BATCH.to_string(),
+            args: cbor::to_value(requests),
+        };
+        let check = |rsp: Response| {
+            let results: Vec<Body> = match rsp.body {
+                Body::Success(value) => cbor::from_value(value).unwrap(),
+                body => panic!("unexpected response: {:?}", body),
+            };
+            assert_eq!(results.len(), 4);
+            assert!(matches!(&results[0], Body::Success(_)));
+            assert!(matches!(&results[1], Body::Success(_)));
+            // Errors are reported per request.
+            assert!(matches!(&results[2], Body::Error(_)));
+            assert!(matches!(&results[3], Body::Error(_)));
+        };
+        let requests = || {
+            batch(vec![
+                request("add"),
+                request("add_async"),
+                request("add_local"),
+                batch(vec![request("add")]),
+            ])
+        };
+
+        let _guard = rt.enter();
+        check(dispatcher.dispatch(Context::new(None), requests(), Kind::NoiseSession));
+        check(rt.block_on(dispatcher.dispatch_async(
+            Context::new(None),
+            requests(),
+            Kind::NoiseSession,
+        )));
+
+        let rsp = dispatcher.dispatch(
+            Context::new(None),
+            batch(vec![request("add"); MAX_BATCH_SIZE + 1]),
+            Kind::NoiseSession,
+        );
+        assert!(matches!(rsp.body, Body::Error(_)));
+    }
+
+    #[test]
+    fn test_stream() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let dispatcher = dispatcher();
+        let streams = Arc::new(Streams::default());
+        let ctx = || Context::new(None).with_streams(streams.clone());
+        let chunk = |rsp: Response| -> StreamChunk {
+            match rsp.body {
+                Body::Success(value) => cbor::from_value(value).unwrap(),
+                body => panic!("unexpected response: {:?}", body),
+            }
+        };
+        let next = |stream: u64, seq: u64| Request {
+            method: METHOD_STREAM_NEXT.to_string(),
+            args: cbor::to_value(StreamNextRequest { stream, seq }),
+        };
+
+        assert!(dispatcher.is_async(&request("count")));
+        assert!(dispatcher.is_async(&next(0, 0)));
+
+        // Streaming requires per-session streams.
+        let rsp = rt.block_on(dispatcher.dispatch_async(
+            Context::new(None),
+            request("count"),
+            Kind::NoiseSession,
+        ));
+        assert!(matches!(rsp.body, Body::Error(_)));
+
+        let first = chunk(rt.block_on(dispatcher.dispatch_async(
+            ctx(),
+            request("count"),
+            Kind::NoiseSession,
+        )));
+        assert_eq!(first.seq, 0);
+        assert_eq!(cbor::from_value::<u64>(first.data.unwrap()).unwrap(), 0);
+
+        let mut seq = 1;
+        loop {
+            let rsp = rt.block_on(dispatcher.dispatch_async(
+                ctx(),
+                next(first.stream, seq),
+                Kind::NoiseSession,
+            ));
+            match chunk(rsp).data {
+                Some(value) => assert_eq!(cbor::from_value::<u64>(value).unwrap(), seq),
+                None => break,
+            }
+            seq += 1;
+        }
+        assert_eq!(seq, 41);
+
+        // Cancelled streams can no longer be consumed.
+        let first = chunk(rt.block_on(dispatcher.dispatch_async(
+            ctx(),
+            request("count"),
+            Kind::NoiseSession,
+        )));
+        let cancel = Request {
+            method: METHOD_STREAM_CANCEL.to_string(),
+            args: cbor::to_value(StreamCancelRequest {
+                stream: first.stream,
+            }),
+        };
+        let _guard = rt.enter();
+        let rsp = dispatcher.dispatch(ctx(), cancel, Kind::NoiseSession);
+        assert!(matches!(rsp.body, Body::Success(_)));
+        let rsp = rt.block_on(dispatcher.dispatch_async(
+            ctx(),
+            next(first.stream, 1),
+            Kind::NoiseSession,
+        ));
+        assert!(matches!(rsp.body, Body::Error(_)));
+    }
+}
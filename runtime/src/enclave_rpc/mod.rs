@@ -1,10 +1,15 @@
 //! Secure inter-enclave RPC.
 
+pub mod access;
 pub mod client;
 pub mod context;
 pub mod demux;
 pub mod dispatcher;
+pub mod ratelimit;
+pub mod resumption;
+pub mod selector;
 pub mod session;
+pub mod stream;
 mod transport;
 pub mod types;
 
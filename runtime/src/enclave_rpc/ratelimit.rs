@@ -0,0 +1,285 @@
+//! Request rate limiting.
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+/// Peer identifier.
+type PeerID = Vec<u8>;
+
+/// Token bucket rate limit.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Limit {
+    /// Maximum number of requests that can be made in a burst.
+    pub burst: u32,
+    /// Number of requests per second that are replenished.
+    pub rate: f64,
+}
+
+impl Limit {
+    /// Create a new rate limit.
+    pub fn new(burst: u32, rate: f64) -> Self {
+        Self { burst, rate }
+    }
+
+    /// Time after which an empty bucket is full again.
+    fn refill_time(&self) -> Duration {
+        if self.rate <= 0.0 {
+            return Duration::MAX;
+        }
+        Duration::try_from_secs_f64(self.burst as f64 / self.rate).unwrap_or(Duration::MAX)
+    }
+}
+
+/// Rate limits for enclave RPC requests.
+///
+/// Each peer is subject to the per-peer limit across all of its requests and to the per-method
+/// limit of each method that it calls. Limits are tracked separately for each peer.
+#[derive(Clone, Debug, Default)]
+pub struct Limits {
+    /// Limit for all requests made by a peer.
+    pub per_peer: Option<Limit>,
+    /// Limits for requests to a specific method made by a peer.
+    pub per_method: HashMap<String, Limit>,
+}
+
+impl Limits {
+    /// Set the limit for all requests made by a peer.
+    pub fn per_peer(mut self, limit: Limit) -> Self {
+        self.per_peer = Some(limit);
+        self
+    }
+
+    /// Set the limit for requests to the given method made by a peer.
+    pub fn per_method(mut self, method: &str, limit: Limit) -> Self {
+        self.per_method.insert(method.to_string(), limit);
+        self
+    }
+}
+
+/// Token bucket.
+struct Bucket {
+    tokens: f64,
+    last_update: Instant,
+}
+
+impl Bucket {
+    fn new(limit: &Limit, now: Instant) -> Self {
+        Self {
+            tokens: limit.burst as f64,
+            last_update: now,
+        }
+    }
+
+    /// Replenish tokens and return whether the given number of tokens is available.
+    fn refill(&mut self, limit: &Limit, cost: f64, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.last_update);
+        self.tokens = (self.tokens + elapsed.as_secs_f64() * limit.rate).min(limit.burst as f64);
+        self.last_update = now;
+        self.tokens >= cost
+    }
+}
+
+/// Buckets of a single peer.
+struct PeerBuckets {
+    peer: Option<Bucket>,
+    methods: HashMap<String, Bucket>,
+    last_access_time: Instant,
+}
+
+/// Token bucket rate limiter.
+pub(crate) struct RateLimiter {
+    limits: Limits,
+    /// Maximum number of tracked peers. Once reached, idle peers are pruned and, in case all
+    /// peers are active, the least recently used peer is evicted.
+    max_peers: usize,
+    buckets: HashMap<PeerID, PeerBuckets>,
+}
+
+impl RateLimiter {
+    /// Create a new rate limiter.
+    pub(crate) fn new(limits: Limits, max_peers: usize) -> Self {
+        Self {
+            limits,
+            max_peers,
+            buckets: HashMap::new(),
+        }
+    }
+
+    /// Check whether the given peer may call the given method and consume a token if so.
+    #[cfg(test)]
+    fn check(&mut self, peer_id: &[u8], method: &str, now: Instant) -> bool {
+        self.check_all(peer_id, &[method], now)
+    }
+
+    /// Check whether the given peer may call all of the given methods at once (e.g., in a batch)
+    /// and consume the tokens of all calls if so. Either all or none of the calls are allowed.
+    pub(crate) fn check_all(&mut self, peer_id: &[u8], methods: &[&str], now: Instant) -> bool {
+        let mut method_costs: HashMap<&str, f64> = HashMap::new();
+        for method in methods {
+            if self.limits.per_method.contains_key(*method) {
+                *method_costs.entry(method).or_default() += 1.0;
+            }
+        }
+        if self.limits.per_peer.is_none() && method_costs.is_empty() {
+            return true;
+        }
+
+        if self.buckets.len() >= self.max_peers && !self.buckets.contains_key(peer_id) {
+            self.prune(now);
+        }
+
+        let buckets = self
+            .buckets
+            .entry(peer_id.to_vec())
+            .or_insert_with(|| PeerBuckets {
+                peer: None,
+                methods: HashMap::new(),
+                last_access_time: now,
+            });
+        buckets.last_access_time = now;
+        let PeerBuckets {
+            peer,
+            methods: method_buckets,
+            ..
+        } = buckets;
+
+        // Check all applicable buckets before consuming any tokens. Even an empty batch costs
+        // a request.
+        let peer_cost = methods.len().max(1) as f64;
+        let peer_bucket = self.limits.per_peer.as_ref().map(|limit| {
+            let bucket = peer.get_or_insert_with(|| Bucket::new(limit, now));
+            (bucket.refill(limit, peer_cost, now), bucket)
+        });
+        let mut allowed = peer_bucket.as_ref().map(|(ok, _)| *ok).unwrap_or(true);
+        for (method, cost) in &method_costs {
+            let limit = &self.limits.per_method[*method];
+            let bucket = method_buckets
+                .entry(method.to_string())
+                .or_insert_with(|| Bucket::new(limit, now));
+            allowed &= bucket.refill(limit, *cost, now);
+        }
+
+        if allowed {
+            if let Some((_, bucket)) = peer_bucket {
+                bucket.tokens -= peer_cost;
+            }
+            for (method, cost) in &method_costs {
+                if let Some(bucket) = method_buckets.get_mut(*method) {
+                    bucket.tokens -= cost;
+                }
+            }
+        }
+        allowed
+    }
+
+    /// Remove peers whose buckets would have been fully replenished by now as they are
+    /// indistinguishable from new peers. In case no such peers exist, the least recently used
+    /// peer is removed to make room for a new peer.
+    fn prune(&mut self, now: Instant) {
+        let refill_time = self
+            .limits
+            .per_peer
+            .iter()
+            .chain(self.limits.per_method.values())
+            .map(Limit::refill_time)
+            .max()
+            .unwrap_or_default();
+
+        self.buckets.retain(|_, buckets| {
+            now.saturating_duration_since(buckets.last_access_time) < refill_time
+        });
+        if self.buckets.len() < self.max_peers {
+            return;
+        }
+
+        let lru = self
+            .buckets
+            .iter()
+            .min_by_key(|(_, buckets)| buckets.last_access_time)
+            .map(|(peer_id, _)| peer_id.clone());
+        if let Some(peer_id) = lru {
+            self.buckets.remove(&peer_id);
+        }
+    }
+
+    /// Number of tracked peers.
+    #[cfg(test)]
+    fn peer_count(&self) -> usize {
+        self.buckets.len()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_rate_limiter() {
+        let limits = Limits::default()
+            .per_peer(Limit::new(4, 1.0))
+            .per_method("expensive", Limit::new(2, 0.5));
+        let mut limiter = RateLimiter::new(limits, 2);
+        let (peer1, peer2) = (vec![1], vec![2]);
+        let now = Instant::now();
+
+        // Method limit is reached first.
+        assert!(limiter.check(&peer1, "expensive", now));
+        assert!(limiter.check(&peer1, "expensive", now));
+        assert!(!limiter.check(&peer1, "expensive", now));
+        // Other methods are only subject to the peer limit.
+        assert!(limiter.check(&peer1, "cheap", now));
+        assert!(limiter.check(&peer1, "cheap", now));
+        assert!(!limiter.check(&peer1, "cheap", now));
+        // Limits are tracked separately for each peer.
+        assert!(limiter.check(&peer2, "expensive", now));
+
+        // Tokens are replenished over time.
+        let now = now + Duration::from_secs(2);
+        assert!(limiter.check(&peer1, "expensive", now));
+        assert!(!limiter.check(&peer1, "expensive", now));
+        assert!(limiter.check(&peer1, "cheap", now));
+        assert!(!limiter.check(&peer1, "cheap", now));
+
+        // Idle peers are pruned once too many peers are tracked.
+        assert_eq!(limiter.peer_count(), 2);
+        let now = now + Duration::from_secs(10);
+        assert!(limiter.check(&[3], "cheap", now));
+        assert_eq!(limiter.peer_count(), 1);
+
+        // In case all peers are active, the least recently used peer is evicted.
+        assert!(limiter.check(&peer1, "cheap", now + Duration::from_secs(1)));
+        assert!(limiter.check(&peer2, "cheap", now + Duration::from_secs(2)));
+        assert_eq!(limiter.peer_count(), 2);
+        assert!(limiter.buckets.contains_key(&peer1));
+        assert!(!limiter.buckets.contains_key(&vec![3]));
+    }
+
+    #[test]
+    fn test_rate_limiter_batch() {
+        let limits = Limits::default()
+            .per_peer(Limit::new(4, 1.0))
+            .per_method("expensive", Limit::new(2, 0.5));
+        let mut limiter = RateLimiter::new(limits, 2);
+        let peer = vec![1];
+        let now = Instant::now();
+
+        // Batches exceeding a limit are rejected without consuming any tokens.
+        assert!(!limiter.check_all(&peer, &["expensive"; 3], now));
+        assert!(!limiter.check_all(&peer, &["cheap"; 5], now));
+        assert!(limiter.check_all(&peer, &["expensive", "cheap", "expensive"], now));
+        assert!(!limiter.check(&peer, "expensive", now));
+        assert!(limiter.check(&peer, "cheap", now));
+        assert!(!limiter.check_all(&peer, &[], now));
+    }
+
+    #[test]
+    fn test_rate_limiter_unlimited() {
+        let mut limiter = RateLimiter::new(Limits::default(), 2);
+        let now = Instant::now();
+        for peer in 0..100u8 {
+            assert!(limiter.check(&[peer], "method", now));
+        }
+        assert_eq!(limiter.peer_count(), 0);
+    }
+}
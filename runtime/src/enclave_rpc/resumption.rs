@@ -0,0 +1,197 @@
+//! Session resumption.
+//!
+//! Establishing a session requires verification of the remote attestation (and optionally of the
+//! remote node's registration) which can be expensive. As the remote side generates a fresh
+//! static key for each session, only the RAK binding signature differs between sessions with the
+//! same remote enclave. Successful verifications are therefore remembered in tickets, bound to the
+//! remote attestation, the quote policy and the expected remote node, so that reconnecting
+//! sessions only need to verify the binding of the new static key.
+//!
+//! Tickets never outlive the time-dependent checks of the remote attestation (e.g., the TCB
+//! validity period) and the registration of the remote node is verified on every resumption.
+use std::{collections::HashMap, sync::Mutex};
+
+use lazy_static::lazy_static;
+
+use super::session::RAKBinding;
+use crate::{
+    common::{
+        crypto::{hash::Hash, signature::PublicKey},
+        metrics::{self, Counter},
+        namespace::Namespace,
+        sgx::QuotePolicy,
+        time::insecure_posix_time,
+    },
+    consensus::registry::VerifiedEndorsedCapabilityTEE,
+};
+
+lazy_static! {
+    static ref RESUMED_SESSIONS: Counter = metrics::counter(
+        "runtime_enclave_rpc_resumed_sessions",
+        "Number of enclave RPC sessions that skipped attestation verification using a ticket.",
+    );
+}
+
+/// Default lifetime of a ticket (in seconds).
+pub const DEFAULT_TICKET_LIFETIME_SECS: i64 = 600;
+/// Default maximum number of tickets.
+pub const DEFAULT_MAX_TICKETS: usize = 1024;
+
+/// A ticket that remembers a successful verification.
+struct Ticket {
+    /// Verified endorsed TEE capability.
+    vect: VerifiedEndorsedCapabilityTEE,
+    /// Timestamp after which the ticket can no longer be used.
+    expiration: i64,
+}
+
+/// Cache of session resumption tickets.
+pub struct TicketCache {
+    /// Ticket lifetime (in seconds).
+    lifetime: i64,
+    /// Maximum number of tickets.
+    max_tickets: usize,
+    /// Tickets, keyed by the hash of everything that the verification depends on.
+    tickets: Mutex<HashMap<Hash, Ticket>>,
+}
+
+impl Default for TicketCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_TICKET_LIFETIME_SECS, DEFAULT_MAX_TICKETS)
+    }
+}
+
+impl TicketCache {
+    /// Create a new ticket cache.
+    pub fn new(lifetime: i64, max_tickets: usize) -> Self {
+        Self {
+            lifetime,
+            max_tickets,
+            tickets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Derive the ticket key.
+    ///
+    /// The binding signature is excluded as it covers the per-session static key.
+    pub(crate) fn key(
+        rak_binding: &RAKBinding,
+        policy: &QuotePolicy,
+        verify_node: bool,
+        use_endorsement: bool,
+        remote_node: Option<PublicKey>,
+        remote_runtime_id: Option<Namespace>,
+    ) -> Hash {
+        let attestation = rak_binding.without_binding();
+        Hash::digest_bytes_list(&[
+            &cbor::to_vec(attestation),
+            &cbor::to_vec(policy.clone()),
+            &[verify_node as u8, use_endorsement as u8],
+            &cbor::to_vec(remote_node),
+            &cbor::to_vec(remote_runtime_id),
+        ])
+    }
+
+    /// Return the verification result remembered by a valid ticket, if any.
+    pub(crate) fn get(&self, key: &Hash) -> Option<VerifiedEndorsedCapabilityTEE> {
+        self.get_at(key, insecure_posix_time())
+    }
+
+    fn get_at(&self, key: &Hash, now: i64) -> Option<VerifiedEndorsedCapabilityTEE> {
+        let mut tickets = self.tickets.lock().unwrap();
+        match tickets.get(key) {
+            Some(ticket) if ticket.expiration > now => {
+                RESUMED_SESSIONS.inc();
+                Some(ticket.vect.clone())
+            }
+            Some(_) => {
+                tickets.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Issue a ticket for a successful verification which remains valid until the given time at
+    /// the latest.
+    pub(crate) fn insert(&self, key: Hash, vect: VerifiedEndorsedCapabilityTEE, valid_until: i64) {
+        self.insert_at(key, vect, valid_until, insecure_posix_time())
+    }
+
+    fn insert_at(
+        &self,
+        key: Hash,
+        vect: VerifiedEndorsedCapabilityTEE,
+        valid_until: i64,
+        now: i64,
+    ) {
+        if self.max_tickets == 0 {
+            return;
+        }
+
+        let mut tickets = self.tickets.lock().unwrap();
+        if tickets.len() >= self.max_tickets && !tickets.contains_key(&key) {
+            // Prune expired tickets and if that is not enough, the one closest to expiration.
+            tickets.retain(|_, ticket| ticket.expiration > now);
+            if tickets.len() >= self.max_tickets {
+                let oldest = tickets
+                    .iter()
+                    .min_by_key(|(_, ticket)| ticket.expiration)
+                    .map(|(key, _)| *key);
+                if let Some(oldest) = oldest {
+                    tickets.remove(&oldest);
+                }
+            }
+        }
+
+        tickets.insert(
+            key,
+            Ticket {
+                vect,
+                expiration: now.saturating_add(self.lifetime).min(valid_until),
+            },
+        );
+    }
+
+    /// Remove all tickets.
+    pub fn clear(&self) {
+        self.tickets.lock().unwrap().clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn key(n: u8) -> Hash {
+        Hash::digest_bytes(&[n])
+    }
+
+    #[test]
+    fn test_ticket_cache() {
+        let cache = TicketCache::new(10, 2);
+        let vect = VerifiedEndorsedCapabilityTEE::default();
+
+        assert!(cache.get_at(&key(1), 100).is_none());
+        cache.insert_at(key(1), vect.clone(), i64::MAX, 100);
+        assert!(cache.get_at(&key(1), 105).is_some());
+        // Tickets expire.
+        assert!(cache.get_at(&key(1), 110).is_none());
+
+        // Tickets do not outlive the verified attestation.
+        cache.insert_at(key(1), vect.clone(), 103, 100);
+        assert!(cache.get_at(&key(1), 102).is_some());
+        assert!(cache.get_at(&key(1), 103).is_none());
+
+        // The ticket closest to expiration is evicted when the cache is full.
+        cache.insert_at(key(1), vect.clone(), i64::MAX, 100);
+        cache.insert_at(key(2), vect.clone(), i64::MAX, 101);
+        cache.insert_at(key(3), vect, i64::MAX, 102);
+        assert!(cache.get_at(&key(1), 103).is_none());
+        assert!(cache.get_at(&key(2), 103).is_some());
+        assert!(cache.get_at(&key(3), 103).is_some());
+
+        cache.clear();
+        assert!(cache.get_at(&key(3), 103).is_none());
+    }
+}
@@ -0,0 +1,367 @@
+//! Peer selection strategies.
+//!
+//! The RPC client records statistics about each allowed remote node based on the outcome and
+//! latency of calls it makes, as well as the peer feedback given by the caller. A peer selector
+//! uses these statistics to choose the node that handles the next call.
+use std::time::{Duration, Instant};
+
+use rand::{rngs::OsRng, Rng};
+
+use crate::common::crypto::signature::PublicKey;
+
+/// Weight of the most recent observation in moving averages.
+const EWMA_WEIGHT: f64 = 0.2;
+/// Time after which a single recorded failure is forgotten.
+const FAILURE_DECAY_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Client-observed statistics of a remote node.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PeerStats {
+    /// Number of calls currently being processed by the node.
+    pub in_flight: usize,
+    /// Number of recent failures.
+    ///
+    /// The number is reset when the caller reports success and otherwise decays by one failure
+    /// per decay interval, so that nodes which failed in the past eventually get used again.
+    pub failures: u32,
+    /// Moving average of the latency of successful calls, if any.
+    pub latency: Option<Duration>,
+    /// Moving average of the error rate (between 0 and 1).
+    pub error_rate: f64,
+    /// Time from which the next failure decay step is counted.
+    failure_time: Option<Instant>,
+}
+
+impl PeerStats {
+    /// Record a call that completed successfully after the given time.
+    pub(crate) fn record_success(&mut self, latency: Duration) {
+        self.latency = Some(match self.latency {
+            Some(avg) => avg.mul_f64(1.0 - EWMA_WEIGHT) + latency.mul_f64(EWMA_WEIGHT),
+            None => latency,
+        });
+        self.error_rate *= 1.0 - EWMA_WEIGHT;
+    }
+
+    /// Record a failure, counting it as the given number of failures.
+    pub(crate) fn record_failure(&mut self, penalty: u32) {
+        let now = Instant::now();
+        self.decay_failures(now);
+        self.failures = self.failures.saturating_add(penalty);
+        self.failure_time = Some(now);
+        self.error_rate = self.error_rate * (1.0 - EWMA_WEIGHT) + EWMA_WEIGHT;
+    }
+
+    /// Forget past failures after the caller reported success.
+    pub(crate) fn reset_failures(&mut self) {
+        self.failures = 0;
+        self.failure_time = None;
+    }
+
+    /// Forget one failure for each decay interval that passed since the last failure.
+    pub(crate) fn decay_failures(&mut self, now: Instant) {
+        let failure_time = match self.failure_time {
+            Some(failure_time) if self.failures > 0 => failure_time,
+            _ => return,
+        };
+        let elapsed = now.saturating_duration_since(failure_time);
+        let steps = (elapsed.as_secs_f64() / FAILURE_DECAY_INTERVAL.as_secs_f64()) as u32;
+        if steps == 0 {
+            return;
+        }
+
+        self.failures = self.failures.saturating_sub(steps);
+        self.failure_time = match self.failures {
+            0 => None,
+            _ => Some(failure_time + FAILURE_DECAY_INTERVAL * steps),
+        };
+    }
+}
+
+/// Strategy for selecting the remote node that handles the next call.
+pub trait PeerSelector: Send {
+    /// Select one of the given nodes based on their statistics.
+    ///
+    /// The list of nodes is never empty and is always in the same order.
+    fn select(&mut self, peers: &[(PublicKey, PeerStats)]) -> PublicKey;
+}
+
+/// Prefers nodes with fewer recent failures and then nodes with fewer calls in flight, breaking
+/// ties in a round-robin fashion.
+///
+/// This is the default strategy.
+#[derive(Clone, Debug, Default)]
+pub struct LeastLoaded {
+    next: usize,
+}
+
+impl PeerSelector for LeastLoaded {
+    fn select(&mut self, peers: &[(PublicKey, PeerStats)]) -> PublicKey {
+        // Rotate the starting point so that ties are broken in a round-robin fashion.
+        let count = peers.len();
+        let start = self.next % count;
+        self.next = start + 1;
+
+        (0..count)
+            .map(|i| &peers[(start + i) % count])
+            .min_by_key(|(_, stats)| (stats.failures, stats.in_flight))
+            .map(|(node, _)| *node)
+            .unwrap()
+    }
+}
+
+/// Uses all nodes in turn, regardless of their statistics.
+#[derive(Clone, Debug, Default)]
+pub struct RoundRobin {
+    next: usize,
+}
+
+impl PeerSelector for RoundRobin {
+    fn select(&mut self, peers: &[(PublicKey, PeerStats)]) -> PublicKey {
+        let index = self.next % peers.len();
+        self.next = index + 1;
+        peers[index].0
+    }
+}
+
+/// Selects nodes randomly with probability inversely proportional to their expected latency.
+///
+/// The expected latency accounts for the calls in flight, the error rate and recent failures.
+/// Nodes without latency observations are assumed to be as fast as the fastest known node so
+/// that they get a chance to be measured.
+#[derive(Clone, Debug, Default)]
+pub struct LatencyWeighted;
+
+impl LatencyWeighted {
+    /// Weight of a node given the fastest observed latency (in seconds).
+    fn weight(stats: &PeerStats, fastest: f64) -> f64 {
+        let latency = stats.latency.map(|l| l.as_secs_f64()).unwrap_or(fastest);
+        let load = (stats.in_flight + 1) as f64;
+        let failures = stats.failures.saturating_add(1) as f64;
+        let success_rate = 1.0 - stats.error_rate;
+
+        success_rate / (latency.max(f64::EPSILON) * load * failures)
+    }
+}
+
+impl PeerSelector for LatencyWeighted {
+    fn select(&mut self, peers: &[(PublicKey, PeerStats)]) -> PublicKey {
+        let fastest = peers
+            .iter()
+            .filter_map(|(_, stats)| stats.latency)
+            .min()
+            .map(|l| l.as_secs_f64())
+            .unwrap_or(1.0);
+        let weights: Vec<_> = peers
+            .iter()
+            .map(|(_, stats)| Self::weight(stats, fastest))
+            .collect();
+
+        let total: f64 = weights.iter().sum();
+        if total <= 0.0 || !total.is_finite() {
+            return peers[OsRng.gen_range(0..peers.len())].0;
+        }
+
+        let mut point = OsRng.gen_range(0.0..total);
+        for ((node, _), weight) in peers.iter().zip(weights) {
+            if point < weight {
+                return *node;
+            }
+            point -= weight;
+        }
+        peers[peers.len() - 1].0
+    }
+}
+
+/// Keeps using the same node until it fails the given number of times in a row, then fails over
+/// to the node with the fewest failures and lowest latency.
+///
+/// As with [`LatencyWeighted`], nodes without latency observations are assumed to be as fast as
+/// the fastest known node.
+#[derive(Clone, Debug)]
+pub struct StickyWithFailover {
+    max_failures: u32,
+    current: Option<PublicKey>,
+}
+
+impl StickyWithFailover {
+    /// Create a new strategy that fails over after the given number of failures.
+    pub fn new(max_failures: u32) -> Self {
+        Self {
+            max_failures: max_failures.max(1),
+            current: None,
+        }
+    }
+}
+
+impl Default for StickyWithFailover {
+    fn default() -> Self {
+        Self::new(3)
+    }
+}
+
+impl PeerSelector for StickyWithFailover {
+    fn select(&mut self, peers: &[(PublicKey, PeerStats)]) -> PublicKey {
+        let current = self
+            .current
+            .and_then(|current| peers.iter().find(|(node, _)| *node == current));
+        if let Some((node, stats)) = current {
+            if stats.failures < self.max_failures {
+                return *node;
+            }
+        }
+
+        // Fail over to the healthiest other node, unless the current node is the only one.
+        let candidates: Vec<_> = peers
+            .iter()
+            .filter(|(node, _)| peers.len() == 1 || Some(*node) != self.current)
+            .collect();
+        let fastest = candidates
+            .iter()
+            .filter_map(|(_, stats)| stats.latency)
+            .min()
+            .unwrap_or_default();
+        let node = candidates
+            .into_iter()
+            .min_by_key(|(_, stats)| (stats.failures, stats.latency.unwrap_or(fastest)))
+            .map(|(node, _)| *node)
+            .unwrap();
+        self.current = Some(node);
+        node
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn peers(stats: Vec<PeerStats>) -> Vec<(PublicKey, PeerStats)> {
+        stats
+            .into_iter()
+            .enumerate()
+            .map(|(i, stats)| (PublicKey::from(vec![i as u8; 32]), stats))
+            .collect()
+    }
+
+    fn node(i: u8) -> PublicKey {
+        PublicKey::from(vec![i; 32])
+    }
+
+    #[test]
+    fn test_peer_stats() {
+        let mut stats = PeerStats::default();
+        stats.record_success(Duration::from_millis(100));
+        assert_eq!(stats.latency, Some(Duration::from_millis(100)));
+        stats.record_success(Duration::from_millis(200));
+        assert_eq!(stats.latency, Some(Duration::from_millis(120)));
+
+        stats.record_failure(5);
+        assert_eq!(stats.failures, 5);
+        assert!(stats.error_rate > 0.0);
+        stats.reset_failures();
+        assert_eq!(stats.failures, 0);
+        assert!(stats.error_rate > 0.0);
+    }
+
+    #[test]
+    fn test_peer_stats_failure_decay() {
+        let mut stats = PeerStats::default();
+        stats.record_failure(3);
+        let start = stats.failure_time.unwrap();
+
+        stats.decay_failures(start + FAILURE_DECAY_INTERVAL / 2);
+        assert_eq!(stats.failures, 3);
+        stats.decay_failures(start + FAILURE_DECAY_INTERVAL);
+        assert_eq!(stats.failures, 2);
+        stats.decay_failures(start + FAILURE_DECAY_INTERVAL * 3);
+        assert_eq!(stats.failures, 0);
+        assert_eq!(stats.failure_time, None);
+
+        // Failures are never forgotten before the decay interval passes.
+        stats.record_failure(1);
+        stats.decay_failures(stats.failure_time.unwrap());
+        assert_eq!(stats.failures, 1);
+    }
+
+    #[test]
+    fn test_least_loaded() {
+        let mut selector = LeastLoaded::default();
+        let mut stats = vec![PeerStats::default(); 3];
+        assert_eq!(selector.select(&peers(stats.clone())), node(0));
+        assert_eq!(selector.select(&peers(stats.clone())), node(1));
+
+        stats[2].failures = 1;
+        stats[0].in_flight = 1;
+        assert_eq!(selector.select(&peers(stats.clone())), node(1));
+        assert_eq!(selector.select(&peers(stats)), node(1));
+    }
+
+    #[test]
+    fn test_round_robin() {
+        let mut selector = RoundRobin::default();
+        let mut stats = vec![PeerStats::default(); 2];
+        stats[0].failures = 10;
+        let selected: Vec<_> = (0..4)
+            .map(|_| selector.select(&peers(stats.clone())))
+            .collect();
+        assert_eq!(selected, vec![node(0), node(1), node(0), node(1)]);
+    }
+
+    #[test]
+    fn test_latency_weighted() {
+        let mut selector = LatencyWeighted;
+        let mut stats = vec![PeerStats::default(); 3];
+        stats[0].latency = Some(Duration::from_millis(1));
+        stats[1].latency = Some(Duration::from_millis(500));
+        let peers = peers(stats);
+
+        let mut counts = [0; 3];
+        for _ in 0..1000 {
+            let selected = selector.select(&peers);
+            let index = peers
+                .iter()
+                .position(|(node, _)| *node == selected)
+                .unwrap();
+            counts[index] += 1;
+        }
+        // Slow nodes are rarely selected while unknown nodes are assumed to be fast.
+        assert!(counts[1] < 50, "slow node selected {} times", counts[1]);
+        assert!(counts[0] > 300);
+        assert!(counts[2] > 300);
+    }
+
+    #[test]
+    fn test_sticky_with_failover() {
+        let mut selector = StickyWithFailover::new(2);
+        let mut stats = vec![PeerStats::default(); 3];
+        stats[1].latency = Some(Duration::from_millis(10));
+        stats[2].latency = Some(Duration::from_millis(20));
+
+        assert_eq!(selector.select(&peers(stats.clone())), node(0));
+        stats[0].failures = 1;
+        assert_eq!(selector.select(&peers(stats.clone())), node(0));
+
+        // Fail over to the healthiest other node.
+        stats[0].failures = 2;
+        assert_eq!(selector.select(&peers(stats.clone())), node(1));
+        stats[0].failures = 0;
+        assert_eq!(selector.select(&peers(stats.clone())), node(1));
+
+        // Fail over in case the current node is no longer allowed.
+        let remaining = peers(stats)
+            .into_iter()
+            .filter(|(n, _)| *n != node(1))
+            .collect::<Vec<_>>();
+        assert_eq!(selector.select(&remaining), node(0));
+    }
+
+    #[test]
+    fn test_sticky_with_failover_unmeasured() {
+        let mut selector = StickyWithFailover::new(1);
+        let mut stats = vec![PeerStats::default(); 3];
+        stats[0].failures = 1;
+        stats[1].latency = Some(Duration::from_millis(10));
+
+        // Unmeasured nodes are not preferred over the fastest known node.
+        assert_eq!(selector.select(&peers(stats)), node(1));
+    }
+}
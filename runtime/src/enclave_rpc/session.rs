@@ -4,7 +4,7 @@ use std::{collections::HashSet, io::Write, mem, sync::Arc};
 use anyhow::Result;
 use thiserror::Error;
 
-use super::types::Message;
+use super::{resumption::TicketCache, types::Message};
 use crate::{
     common::{
         crypto::signature::{self, PublicKey, Signature, Signer},
@@ -12,7 +12,7 @@ use crate::{
         sgx::{ias, EnclaveIdentity, Quote, QuotePolicy, VerifiedQuote},
     },
     consensus::{
-        registry::{EndorsedCapabilityTEE, VerifiedEndorsedCapabilityTEE},
+        registry::{EndorsedCapabilityTEE, SGXAttestation, VerifiedEndorsedCapabilityTEE},
         state::registry::ImmutableState as RegistryState,
         verifier::Verifier,
     },
@@ -77,6 +77,7 @@ pub struct Session {
     remote_runtime_id: Option<Namespace>,
     policy: Option<Arc<QuotePolicy>>,
     use_endorsement: bool,
+    tickets: Option<Arc<TicketCache>>,
     info: Option<Arc<SessionInfo>>,
     state: State,
     buf: Vec<u8>,
@@ -93,6 +94,7 @@ impl Session {
         remote_runtime_id: Option<Namespace>,
         policy: Option<Arc<QuotePolicy>>,
         use_endorsement: bool,
+        tickets: Option<Arc<TicketCache>>,
     ) -> Self {
         Self {
             consensus_verifier,
@@ -103,6 +105,7 @@ impl Session {
             remote_runtime_id,
             policy,
             use_endorsement,
+            tickets,
             info: None,
             state: State::Handshake1(handshake_state),
             buf: vec![0u8; 65535],
@@ -271,6 +274,34 @@ impl Session {
             .ok_or(SessionError::MissingQuotePolicy)?;
 
         let rak_binding: RAKBinding = cbor::from_slice(rak_binding)?;
+
+        // Resume the session in case a valid ticket exists, skipping attestation verification.
+        let tickets = self.tickets.as_ref().map(|tickets| {
+            let key = TicketCache::key(
+                &rak_binding,
+                policy,
+                self.consensus_verifier.is_some(),
+                self.use_endorsement,
+                self.remote_node,
+                self.remote_runtime_id,
+            );
+            (tickets, key)
+        });
+        if let Some(vect) = tickets.as_ref().and_then(|(tickets, key)| tickets.get(key)) {
+            rak_binding.verify_session(&vect, remote_static, &self.remote_enclaves)?;
+
+            // The node's registration can change at any time, so it is always verified.
+            if self.consensus_verifier.is_some() {
+                self.verify_node_identity(rak_binding.rak_pub()).await?;
+            }
+
+            return Ok(Some(Arc::new(SessionInfo {
+                rak_binding,
+                verified_quote: vect.verified_quote,
+                endorsed_by: vect.node_id,
+            })));
+        }
+
         let vect = rak_binding.verify(remote_static, &self.remote_enclaves, policy)?;
 
         // Verify node identity if verification is enabled.
@@ -279,6 +310,12 @@ impl Session {
             self.verify_node_identity(rak).await?;
         }
 
+        if let Some((tickets, key)) = tickets {
+            // Tickets must not outlive the time-dependent checks of the quote.
+            let valid_until = rak_binding.valid_until(policy)?;
+            tickets.insert(key, vect.clone(), valid_until);
+        }
+
         Ok(Some(Arc::new(SessionInfo {
             rak_binding,
             verified_quote: vect.verified_quote,
@@ -417,6 +454,23 @@ impl RAKBinding {
         }
     }
 
+    /// Copy of the RAK binding without the signature binding the session's static public key.
+    pub(crate) fn without_binding(&self) -> Self {
+        let mut rak_binding = self.clone();
+        match rak_binding {
+            Self::V0 {
+                ref mut binding, ..
+            }
+            | Self::V1 {
+                ref mut binding, ..
+            }
+            | Self::V2 {
+                ref mut binding, ..
+            } => *binding = Signature::default(),
+        }
+        rak_binding
+    }
+
     /// Verify the RAK binding.
     pub fn verify(
         &self,
@@ -425,7 +479,19 @@ impl RAKBinding {
         policy: &QuotePolicy,
     ) -> Result<VerifiedEndorsedCapabilityTEE> {
         let vect = self.verify_inner(policy)?;
+        self.verify_session(&vect, remote_static, remote_enclaves)?;
+
+        Ok(vect)
+    }
 
+    /// Verify the RAK binding of the session's static public key given an already verified
+    /// attestation.
+    fn verify_session(
+        &self,
+        vect: &VerifiedEndorsedCapabilityTEE,
+        remote_static: &[u8],
+        remote_enclaves: &Option<HashSet<EnclaveIdentity>>,
+    ) -> Result<()> {
         // Ensure that the report data includes the hash of the node's RAK.
         // NOTE: For V2 this check is part of verify_inner so it is not really needed.
         Identity::verify_binding(&vect.verified_quote, &self.rak_pub())?;
@@ -441,7 +507,20 @@ impl RAKBinding {
         self.binding()
             .verify(&self.rak_pub(), &RAK_SESSION_BINDING_CONTEXT, remote_static)?;
 
-        Ok(vect)
+        Ok(())
+    }
+
+    /// Time (POSIX) until which the time-dependent checks of the already verified attestation
+    /// pass.
+    fn valid_until(&self, policy: &QuotePolicy) -> Result<i64> {
+        match self {
+            Self::V0 { ref avr, .. } => ias::valid_until(avr),
+            Self::V1 { ref quote, .. } => quote.valid_until(policy),
+            Self::V2 { ref ect, .. } => {
+                let attestation: SGXAttestation = ect.capability_tee.try_decode_attestation()?;
+                attestation.quote().valid_until(policy)
+            }
+        }
     }
 
     fn verify_inner(&self, policy: &QuotePolicy) -> Result<VerifiedEndorsedCapabilityTEE> {
@@ -464,6 +543,7 @@ pub struct Builder {
     remote_runtime_id: Option<Namespace>,
     use_endorsement: bool,
     policy: Option<Arc<QuotePolicy>>,
+    tickets: Option<Arc<TicketCache>>,
 }
 
 impl Builder {
@@ -512,6 +592,14 @@ impl Builder {
         self
     }
 
+    /// Enable session resumption using tickets from the given cache.
+    ///
+    /// The cache should be shared between all sessions that should be able to resume each other.
+    pub fn resumption(mut self, tickets: Option<Arc<TicketCache>>) -> Self {
+        self.tickets = tickets;
+        self
+    }
+
     /// Return the local identity if configured in the builder.
     pub fn get_local_identity(&self) -> &Option<Arc<Identity>> {
         &self.identity
@@ -535,6 +623,7 @@ impl Builder {
         Option<HashSet<EnclaveIdentity>>,
         Option<Arc<QuotePolicy>>,
         bool,
+        Option<Arc<TicketCache>>,
     ) {
         let noise_builder = snow::Builder::new(NOISE_PATTERN.parse().unwrap());
         let verifier = self.consensus_verifier.take();
@@ -543,6 +632,7 @@ impl Builder {
         let remote_runtime_id = self.remote_runtime_id.take();
         let quote_policy = self.policy.take();
         let use_endorsement = self.use_endorsement;
+        let tickets = self.tickets.take();
         let keypair = noise_builder.generate_keypair().unwrap();
 
         (
@@ -554,13 +644,23 @@ impl Builder {
             remote_enclaves,
             quote_policy,
             use_endorsement,
+            tickets,
         )
     }
 
     /// Build initiator session.
     pub fn build_initiator(self) -> Session {
-        let (builder, keypair, runtime_id, verifier, identity, enclaves, policy, use_endorsement) =
-            self.build();
+        let (
+            builder,
+            keypair,
+            runtime_id,
+            verifier,
+            identity,
+            enclaves,
+            policy,
+            use_endorsement,
+            tickets,
+        ) = self.build();
         let session = builder
             .local_private_key(&keypair.private)
             .build_initiator()
@@ -574,13 +674,23 @@ impl Builder {
             runtime_id,
             policy,
             use_endorsement,
+            tickets,
         )
     }
 
     /// Build responder session.
     pub fn build_responder(self) -> Session {
-        let (builder, keypair, runtime_id, verifier, identity, enclaves, policy, use_endorsement) =
-            self.build();
+        let (
+            builder,
+            keypair,
+            runtime_id,
+            verifier,
+            identity,
+            enclaves,
+            policy,
+            use_endorsement,
+            tickets,
+        ) = self.build();
         let session = builder
             .local_private_key(&keypair.private)
             .build_responder()
@@ -594,6 +704,7 @@ impl Builder {
             runtime_id,
             policy,
             use_endorsement,
+            tickets,
         )
     }
 }
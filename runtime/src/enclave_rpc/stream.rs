@@ -0,0 +1,229 @@
+//! Streaming responses.
+//!
+//! Responses of streaming methods are delivered in chunks over the session in which the method
+//! was called. The client pulls each chunk explicitly so the handler's stream is only polled when
+//! the client is ready to receive more data, which bounds the amount of buffered data on both
+//! sides. Each chunk carries a sequence number so that the client can detect missing or reordered
+//! chunks.
+//!
+//! Streams that the client stops consuming without cancelling them (e.g., because the cancel
+//! request could not be delivered) are released once they have been idle for too long.
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use anyhow::{bail, Result};
+use futures::{stream::BoxStream, StreamExt};
+use rand::{rngs::OsRng, Rng};
+use thiserror::Error;
+
+use super::types::StreamChunk;
+
+/// Maximum number of open streams per session.
+pub const MAX_STREAMS_PER_SESSION: usize = 4;
+/// Time after which an open stream that is not consumed is released.
+pub const STREAM_IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+/// Maximum size of encoded chunk data.
+///
+/// Chunks need to fit into a single session message (together with the framing overhead).
+pub const MAX_CHUNK_SIZE: usize = 60 * 1024;
+
+/// Stream of response chunks produced by a streaming method handler.
+pub type ResponseStream<T> = BoxStream<'static, Result<T>>;
+
+/// Streaming response error.
+#[derive(Error, Debug)]
+pub enum StreamError {
+    #[error("streaming responses are only supported over secure sessions")]
+    NotSupported,
+    #[error("too many open streams")]
+    TooManyStreams,
+    #[error("unknown stream")]
+    UnknownStream,
+    #[error("unexpected chunk sequence number: {got} (expected {expected})")]
+    UnexpectedSequence { got: u64, expected: u64 },
+    #[error("chunk too large: {size} (max {max})")]
+    ChunkTooLarge { size: usize, max: usize },
+}
+
+impl_error_codes!(StreamError, "enclave_rpc/stream", {
+    StreamError::NotSupported => 1,
+    StreamError::TooManyStreams => 2,
+    StreamError::UnknownStream => 3,
+    StreamError::UnexpectedSequence { .. } => 4,
+    StreamError::ChunkTooLarge { .. } => 5,
+});
+
+/// An open stream.
+struct OpenStream {
+    /// Chunks that have not yet been produced.
+    inner: ResponseStream<cbor::Value>,
+    /// Sequence number of the next chunk.
+    seq: u64,
+    /// Time when the last chunk was produced.
+    last_access_time: Instant,
+}
+
+/// Open streams of a single session.
+pub struct Streams {
+    /// Time after which an open stream that is not consumed is released.
+    idle_timeout: Duration,
+    streams: Mutex<HashMap<u64, OpenStream>>,
+}
+
+impl Default for Streams {
+    fn default() -> Self {
+        Self::new(STREAM_IDLE_TIMEOUT)
+    }
+}
+
+impl Streams {
+    /// Create a new set of open streams with the given idle timeout.
+    pub fn new(idle_timeout: Duration) -> Self {
+        Self {
+            idle_timeout,
+            streams: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Register a new stream and produce its first chunk.
+    pub(crate) async fn open(&self, inner: ResponseStream<cbor::Value>) -> Result<StreamChunk> {
+        let id = {
+            let mut streams = self.streams.lock().unwrap();
+            let now = Instant::now();
+            streams.retain(|_, stream| {
+                now.saturating_duration_since(stream.last_access_time) < self.idle_timeout
+            });
+            if streams.len() >= MAX_STREAMS_PER_SESSION {
+                bail!(StreamError::TooManyStreams);
+            }
+
+            let id = loop {
+                let id = OsRng.gen();
+                if !streams.contains_key(&id) {
+                    break id;
+                }
+            };
+            streams.insert(
+                id,
+                OpenStream {
+                    inner,
+                    seq: 0,
+                    last_access_time: Instant::now(),
+                },
+            );
+            id
+        };
+
+        self.next(id, 0).await
+    }
+
+    /// Produce the next chunk of the given stream.
+    ///
+    /// The stream is removed once it has ended or in case of any errors.
+    pub(crate) async fn next(&self, id: u64, seq: u64) -> Result<StreamChunk> {
+        // Calls within a session are processed in order, so the stream can be taken out while the
+        // next chunk is being produced.
+        let mut stream = self
+            .streams
+            .lock()
+            .unwrap()
+            .remove(&id)
+            .ok_or(StreamError::UnknownStream)?;
+        if seq != stream.seq {
+            bail!(StreamError::UnexpectedSequence {
+                got: seq,
+                expected: stream.seq,
+            });
+        }
+
+        let data = stream.inner.next().await.transpose()?;
+        let encoded = data.clone().map(cbor::to_vec).unwrap_or_default();
+        if encoded.len() > MAX_CHUNK_SIZE {
+            bail!(StreamError::ChunkTooLarge {
+                size: encoded.len(),
+                max: MAX_CHUNK_SIZE,
+            });
+        }
+        let done = data.is_none();
+
+        if !done {
+            stream.seq += 1;
+            stream.last_access_time = Instant::now();
+            self.streams.lock().unwrap().insert(id, stream);
+        }
+
+        Ok(StreamChunk {
+            stream: id,
+            seq,
+            data,
+        })
+    }
+
+    /// Cancel the given stream.
+    pub(crate) fn cancel(&self, id: u64) {
+        self.streams.lock().unwrap().remove(&id);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use futures::stream;
+
+    use super::*;
+
+    fn numbers(count: u64) -> ResponseStream<cbor::Value> {
+        stream::iter((0..count).map(|n| Ok(cbor::to_value(n)))).boxed()
+    }
+
+    fn check(chunk: StreamChunk, seq: u64, data: Option<u64>) {
+        assert_eq!(chunk.seq, seq);
+        assert_eq!(chunk.data.map(|v| cbor::from_value(v).unwrap()), data);
+    }
+
+    #[tokio::test]
+    async fn test_streams() {
+        let streams = Streams::default();
+
+        let chunk = streams.open(numbers(2)).await.unwrap();
+        let id = chunk.stream;
+        check(chunk, 0, Some(0));
+        check(streams.next(id, 1).await.unwrap(), 1, Some(1));
+        check(streams.next(id, 2).await.unwrap(), 2, None);
+        // Ended streams are removed.
+        assert!(streams.next(id, 3).await.is_err());
+
+        // Chunks must be requested in order, otherwise the stream is aborted.
+        let id = streams.open(numbers(2)).await.unwrap().stream;
+        assert!(streams.next(id, 2).await.is_err());
+        assert!(streams.next(id, 1).await.is_err());
+
+        // The number of open streams is limited.
+        let mut ids = vec![];
+        for _ in 0..MAX_STREAMS_PER_SESSION {
+            ids.push(streams.open(numbers(10)).await.unwrap().stream);
+        }
+        assert!(streams.open(numbers(10)).await.is_err());
+        streams.cancel(ids[0]);
+        assert!(streams.open(numbers(10)).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_streams_idle_timeout() {
+        let streams = Streams::new(Duration::from_millis(50));
+
+        let mut ids = vec![];
+        for _ in 0..MAX_STREAMS_PER_SESSION {
+            ids.push(streams.open(numbers(10)).await.unwrap().stream);
+        }
+        assert!(streams.open(numbers(10)).await.is_err());
+
+        // Streams which are not consumed are released after the idle timeout.
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let id = streams.open(numbers(10)).await.unwrap().stream;
+        assert!(streams.next(ids[0], 1).await.is_err());
+        assert!(streams.next(id, 1).await.is_ok());
+    }
+}
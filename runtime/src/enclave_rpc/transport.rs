@@ -1,4 +1,7 @@
-use std::sync::{Arc, Mutex};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
 
 use anyhow::{anyhow, Error as AnyError};
 use async_trait::async_trait;
@@ -44,11 +47,19 @@ pub trait Transport: Send + Sync {
         nodes: Vec<signature::PublicKey>,
     ) -> Result<(Vec<u8>, signature::PublicKey), AnyError>;
 
-    fn set_peer_feedback(&self, _pfid: u64, _peer_feedback: Option<types::PeerFeedback>) {
+    /// Set peer feedback on the last call handled by the given node. The feedback is ignored in
+    /// case the peer feedback identifier of the node has changed in the meantime.
+    fn set_peer_feedback(
+        &self,
+        _node: signature::PublicKey,
+        _pfid: u64,
+        _peer_feedback: Option<types::PeerFeedback>,
+    ) {
         // Default implementation doesn't do anything.
     }
 
-    fn get_peer_feedback_id(&self) -> u64 {
+    /// Current peer feedback identifier of the given node.
+    fn get_peer_feedback_id(&self, _node: &signature::PublicKey) -> u64 {
         // Default implementation doesn't do anything.
         0
     }
@@ -60,7 +71,9 @@ pub struct RuntimeTransport {
     pub protocol: Arc<Protocol>,
     pub endpoint: String,
 
-    peer_feedback: Mutex<(u64, Option<types::PeerFeedback>)>,
+    /// Pending peer feedback and peer feedback identifier for each node. Feedback is kept per
+    /// node as calls to different nodes may be in flight concurrently.
+    peer_feedback: Mutex<HashMap<signature::PublicKey, (u64, Option<types::PeerFeedback>)>>,
 }
 
 impl RuntimeTransport {
@@ -68,7 +81,7 @@ impl RuntimeTransport {
         Self {
             protocol,
             endpoint: endpoint.to_string(),
-            peer_feedback: Mutex::new((0, None)),
+            peer_feedback: Mutex::new(HashMap::new()),
         }
     }
 }
@@ -81,30 +94,47 @@ impl Transport for RuntimeTransport {
         kind: types::Kind,
         nodes: Vec<signature::PublicKey>,
     ) -> Result<(Vec<u8>, signature::PublicKey), AnyError> {
-        let peer_feedback = {
-            let mut pf = self.peer_feedback.lock().unwrap();
-            let peer_feedback = pf.1.take();
-
-            // If non-success feedback was propagated this means that the peer will be changed for
-            // subsequent requests. Increment pfid to make sure that we don't incorporate stale
-            // feedback.
-            if !matches!(peer_feedback, None | Some(types::PeerFeedback::Success)) {
-                pf.0 += 1;
-            }
+        // Propagate any pending feedback together with the node it refers to, so that the host
+        // can attribute it correctly even when calls are performed concurrently.
+        let (peer_feedback_node, peer_feedback) = {
+            let mut pfs = self.peer_feedback.lock().unwrap();
+            pfs.iter_mut()
+                .find_map(|(node, pf)| {
+                    let peer_feedback = pf.1.take()?;
+
+                    // If non-success feedback was propagated this means that the peer will be
+                    // changed for subsequent requests. Increment pfid to make sure that we don't
+                    // incorporate stale feedback.
+                    if !matches!(peer_feedback, types::PeerFeedback::Success) {
+                        pf.0 += 1;
+                    }
+
+                    Some((Some(*node), Some(peer_feedback)))
+                })
+                .unwrap_or_default()
+        };
 
-            peer_feedback
+        let mut request = Body::HostRPCCallRequest {
+            endpoint: self.endpoint.clone(),
+            request: data,
+            kind,
+            nodes,
+            peer_feedback,
+            peer_feedback_node,
         };
 
-        let rsp = self
-            .protocol
-            .call_host_async(Body::HostRPCCallRequest {
-                endpoint: self.endpoint.clone(),
-                request: data,
-                kind,
-                nodes,
-                peer_feedback,
-            })
-            .await?;
+        // Older hosts cannot attribute feedback to a node and attribute it to the node that
+        // handled the last call instead.
+        if !self.protocol.is_supported_by_host(&request) {
+            if let Body::HostRPCCallRequest {
+                peer_feedback_node, ..
+            } = &mut request
+            {
+                *peer_feedback_node = None;
+            }
+        }
+
+        let rsp = self.protocol.call_host_async(request).await?;
 
         match rsp {
             Body::HostRPCCallResponse { response, node } => Ok((response, node)),
@@ -112,8 +142,14 @@ impl Transport for RuntimeTransport {
         }
     }
 
-    fn set_peer_feedback(&self, pfid: u64, peer_feedback: Option<types::PeerFeedback>) {
-        let mut pf = self.peer_feedback.lock().unwrap();
+    fn set_peer_feedback(
+        &self,
+        node: signature::PublicKey,
+        pfid: u64,
+        peer_feedback: Option<types::PeerFeedback>,
+    ) {
+        let mut pfs = self.peer_feedback.lock().unwrap();
+        let pf = pfs.entry(node).or_default();
         if pf.0 != pfid {
             return;
         }
@@ -121,7 +157,12 @@ impl Transport for RuntimeTransport {
         pf.1 = peer_feedback;
     }
 
-    fn get_peer_feedback_id(&self) -> u64 {
-        self.peer_feedback.lock().unwrap().0
+    fn get_peer_feedback_id(&self, node: &signature::PublicKey) -> u64 {
+        self.peer_feedback
+            .lock()
+            .unwrap()
+            .get(node)
+            .map(|pf| pf.0)
+            .unwrap_or_default()
     }
 }
@@ -1,6 +1,23 @@
 //! RPC protocol types.
 use rand::{rngs::OsRng, Rng};
 
+use crate::common::version::Version;
+
+/// Name of the built-in method which describes the methods exposed by the dispatcher.
+pub const METHOD_DESCRIBE: &str = "__describe";
+/// Version of the method description format.
+pub const DESCRIBE_VERSION: u16 = 1;
+/// Name of the built-in method which dispatches a batch of requests.
+///
+/// The arguments are a list of requests and the response is a list of per-request bodies.
+pub const METHOD_BATCH: &str = "__batch";
+/// Maximum number of requests in a batch.
+pub const MAX_BATCH_SIZE: usize = 64;
+/// Name of the built-in method which fetches the next chunk of a streaming response.
+pub const METHOD_STREAM_NEXT: &str = "__stream_next";
+/// Name of the built-in method which cancels a streaming response.
+pub const METHOD_STREAM_CANCEL: &str = "__stream_cancel";
+
 impl_bytes!(
     SessionID,
     32,
@@ -66,6 +83,9 @@ pub struct Error {
 pub enum Body {
     Success(cbor::Value),
     Error(String),
+    /// Error identified by its module and code, used for errors which the client needs to be
+    /// able to tell apart (e.g. rate limiting).
+    StructuredError(crate::types::Error),
 }
 
 #[derive(Clone, Debug, cbor::Encode, cbor::Decode)]
@@ -89,3 +109,65 @@ pub enum PeerFeedback {
     Failure = 1,
     BadPeer = 2,
 }
+
+/// Description of a RPC method.
+#[derive(Clone, Debug, Default, PartialEq, Eq, cbor::Encode, cbor::Decode)]
+pub struct MethodDescription {
+    /// Method name.
+    pub name: String,
+    /// Kind of RPC that is allowed to call the method.
+    pub kind: Kind,
+    /// Other kinds of RPC that are also allowed to call the method.
+    #[cbor(optional)]
+    pub other_kinds: Vec<Kind>,
+    /// Schema name of the CBOR-encoded request arguments or empty if not known.
+    pub request: String,
+    /// Schema name of the CBOR-encoded response or empty if not known.
+    pub response: String,
+    /// Whether the method returns a streaming response. In this case the response is a
+    /// [`StreamChunk`] and the response schema refers to the type of chunk data.
+    #[cbor(optional)]
+    pub streaming: bool,
+}
+
+/// Response of the built-in describe method.
+#[derive(Clone, Debug, Default, PartialEq, Eq, cbor::Encode, cbor::Decode)]
+pub struct DescribeResponse {
+    /// Version of the description format.
+    pub version: u16,
+    /// Runtime protocol version.
+    pub protocol_version: Version,
+    /// Registered methods, ordered by name.
+    pub methods: Vec<MethodDescription>,
+}
+
+/// Chunk of a streaming response.
+///
+/// The first chunk is returned by the streaming method itself while subsequent chunks are fetched
+/// using the built-in [`METHOD_STREAM_NEXT`] method.
+#[derive(Clone, Debug, Default, cbor::Encode, cbor::Decode)]
+pub struct StreamChunk {
+    /// Stream identifier.
+    pub stream: u64,
+    /// Sequence number of the chunk within the stream.
+    pub seq: u64,
+    /// Chunk data or `None` in case the stream has ended.
+    #[cbor(optional)]
+    pub data: Option<cbor::Value>,
+}
+
+/// Request of the built-in method which fetches the next chunk of a streaming response.
+#[derive(Clone, Debug, Default, cbor::Encode, cbor::Decode)]
+pub struct StreamNextRequest {
+    /// Stream identifier.
+    pub stream: u64,
+    /// Expected sequence number of the next chunk.
+    pub seq: u64,
+}
+
+/// Request of the built-in method which cancels a streaming response.
+#[derive(Clone, Debug, Default, cbor::Encode, cbor::Decode)]
+pub struct StreamCancelRequest {
+    /// Stream identifier.
+    pub stream: u64,
+}
@@ -4,7 +4,7 @@ use std::sync::Arc;
 use slog::{error, info};
 
 use crate::{
-    common::logger::{get_logger, init_logger},
+    common::logger::{self, get_logger, init_logger},
     config::Config,
     dispatcher::{Dispatcher, Initializer},
     future::new_tokio_runtime,
@@ -15,6 +15,8 @@ use crate::{
 /// Starts the runtime.
 pub fn start_runtime(initializer: Box<dyn Initializer>, config: Config) {
     // Initialize logging.
+    logger::set_filter(config.logging.filter.clone());
+    logger::set_format(config.logging.format);
     init_logger(log::Level::Info);
     let logger = get_logger("runtime");
     info!(logger, "Runtime is starting");
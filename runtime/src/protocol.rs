@@ -15,14 +15,23 @@ use thiserror::Error;
 use tokio::sync::oneshot;
 
 use crate::{
-    common::{logger::get_logger, namespace::Namespace, version::Version},
+    common::{
+        logger::{self, get_logger},
+        metrics,
+        namespace::Namespace,
+        trace::{self, SpanContext},
+        version::Version,
+    },
     config::Config,
     consensus::{tendermint, verifier::Verifier},
-    dispatcher::Dispatcher,
+    dispatcher::{Dispatcher, DispatcherError},
     future::block_on,
     identity::Identity,
     storage::KeyValue,
-    types::{Body, Error, Message, MessageType, RuntimeInfoRequest, RuntimeInfoResponse},
+    types::{
+        Body, Error, EventKind, HostFetchConsensusEventsRequest, Message, MessageType,
+        RuntimeInfoRequest, RuntimeInfoResponse,
+    },
     BUILD_INFO,
 };
 
@@ -34,6 +43,9 @@ pub type Stream = ::std::net::TcpStream;
 /// Maximum message size.
 const MAX_MESSAGE_SIZE: usize = 16 * 1024 * 1024; // 16MiB
 
+/// Runtime host protocol version assumed for hosts that do not report their version.
+const DEFAULT_HOST_PROTOCOL_VERSION: Version = Version::new(5, 1, 0);
+
 #[derive(Error, Debug)]
 pub enum ProtocolError {
     #[error("message too large")]
@@ -55,17 +67,14 @@ pub enum ProtocolError {
     AlreadyInitialized,
     #[error("channel closed")]
     ChannelClosed,
+    #[error("request not supported by host (host protocol version: {0:?})")]
+    NotSupportedByHost(Version),
 }
 
-impl From<ProtocolError> for Error {
-    fn from(err: ProtocolError) -> Self {
-        Self {
-            module: "protocol".to_string(),
-            code: 1,
-            message: err.to_string(),
-        }
-    }
-}
+// NOTE: All protocol errors have historically been reported using the same code.
+impl_error_codes!(ProtocolError, "protocol", {
+    _ => 1,
+});
 
 /// Information about the host environment.
 #[derive(Debug, Clone)]
@@ -111,6 +120,8 @@ pub struct Protocol {
     config: Config,
     /// Host environment information.
     host_info: Mutex<Option<HostInfo>>,
+    /// Runtime host protocol version supported by the host.
+    host_version: Mutex<Version>,
     /// Tokio runtime handle.
     tokio_runtime: tokio::runtime::Handle,
 }
@@ -139,6 +150,7 @@ impl Protocol {
             pending_out_requests: Mutex::new(HashMap::new()),
             config,
             host_info: Mutex::new(None),
+            host_version: Mutex::new(DEFAULT_HOST_PROTOCOL_VERSION),
             tokio_runtime,
         }
     }
@@ -182,6 +194,34 @@ impl Protocol {
             .clone()
     }
 
+    /// The runtime host protocol version supported by the host.
+    ///
+    /// Hosts that do not report their version are assumed to support version 5.1.0.
+    pub fn get_host_version(&self) -> Version {
+        *self.host_version.lock().unwrap()
+    }
+
+    /// Whether the host supports the given request.
+    ///
+    /// Requests introduced in newer versions of the runtime host protocol must not be sent to
+    /// older hosts as they would fail to decode them and drop the connection.
+    pub fn is_supported_by_host(&self, request: &Body) -> bool {
+        let required = match request {
+            Body::HostFetchWitnessBlockRequest { .. }
+            | Body::HostRPCCallRequest {
+                peer_feedback_node: Some(_),
+                ..
+            }
+            | Body::HostFetchConsensusEventsRequest(HostFetchConsensusEventsRequest {
+                kind: EventKind::Beacon | EventKind::KeyManager | EventKind::KeyManagerChurp,
+                ..
+            }) => Version::new(5, 2, 0),
+            _ => return true,
+        };
+
+        u64::from(self.get_host_version()) >= u64::from(required)
+    }
+
     /// Start the protocol handler loop.
     pub(crate) fn start(self: &Arc<Protocol>) {
         // Spawn write end in a separate thread.
@@ -231,11 +271,16 @@ impl Protocol {
 
     /// Make a new request to the runtime host and wait for the response.
     pub async fn call_host_async(&self, body: Body) -> Result<Body, Error> {
+        if !self.is_supported_by_host(&body) {
+            return Err(ProtocolError::NotSupportedByHost(self.get_host_version()).into());
+        }
+
         let id = self.last_request_id.fetch_add(1, Ordering::SeqCst) as u64;
         let message = Message {
             id,
             body,
             message_type: MessageType::Request,
+            span_context: trace::current(),
         };
 
         // Create a response channel and register an outstanding pending request.
@@ -263,6 +308,7 @@ impl Protocol {
             id,
             body,
             message_type: MessageType::Response,
+            ..Default::default()
         })
     }
 
@@ -312,14 +358,14 @@ impl Protocol {
                 // Incoming request.
                 let id = message.id;
 
-                let body = match self.handle_request(id, message.body) {
+                let body = match self.handle_request(id, message.span_context, message.body) {
                     Ok(Some(result)) => result,
                     Ok(None) => {
                         // A message will be sent later by another thread so there
                         // is no need to do anything more.
                         return Ok(());
                     }
-                    Err(error) => Body::Error(Error::new("rhp/dispatcher", 1, &format!("{error}"))),
+                    Err(error) => Body::Error(DispatcherError::Other(error).into()),
                 };
 
                 // Send response back.
@@ -327,6 +373,7 @@ impl Protocol {
                     id,
                     message_type: MessageType::Response,
                     body,
+                    ..Default::default()
                 })?;
             }
             MessageType::Response => {
@@ -356,6 +403,7 @@ impl Protocol {
     fn handle_request(
         self: &Arc<Protocol>,
         id: u64,
+        span_context: Option<SpanContext>,
         request: Body,
     ) -> anyhow::Result<Option<Body>> {
         match request {
@@ -364,6 +412,15 @@ impl Protocol {
                 self.initialize_guest(request)?,
             ))),
             Body::RuntimePingRequest {} => Ok(Some(Body::Empty {})),
+            Body::RuntimeMetricsRequest {} => Ok(Some(Body::RuntimeMetricsResponse {
+                metrics: metrics::collect(),
+            })),
+            Body::RuntimeHostVersionRequest { version } => {
+                self.ensure_initialized()?;
+                info!(self.logger, "Received host protocol version"; "version" => ?version);
+                *self.host_version.lock().unwrap() = version;
+                Ok(Some(Body::RuntimeHostVersionResponse {}))
+            }
             Body::RuntimeShutdownRequest {} => {
                 info!(self.logger, "Received worker shutdown request");
                 Err(ProtocolError::MethodNotSupported.into())
@@ -380,7 +437,7 @@ impl Protocol {
             | Body::RuntimeCapabilityTEERakAvrRequest { .. }
             | Body::RuntimeCapabilityTEERakQuoteRequest { .. }
             | Body::RuntimeCapabilityTEEUpdateEndorsementRequest { .. } => {
-                self.dispatcher.queue_request(id, request)?;
+                self.dispatcher.queue_request(id, span_context, request)?;
                 Ok(None)
             }
 
@@ -395,7 +452,7 @@ impl Protocol {
             | Body::RuntimeQueryRequest { .. }
             | Body::RuntimeConsensusSyncRequest { .. } => {
                 self.ensure_initialized()?;
-                self.dispatcher.queue_request(id, request)?;
+                self.dispatcher.queue_request(id, span_context, request)?;
                 Ok(None)
             }
 
@@ -406,6 +463,30 @@ impl Protocol {
         }
     }
 
+    fn configure_logging(&self, local_config: &BTreeMap<String, cbor::Value>) {
+        let text = |key: &str| match local_config.get(key) {
+            Some(cbor::Value::TextString(value)) => Some(value.clone()),
+            _ => None,
+        };
+
+        if let Some(spec) = text("log_level") {
+            match spec.parse() {
+                Ok(filter) => logger::set_filter(filter),
+                Err(err) => {
+                    warn!(self.logger, "Ignoring malformed log level override"; "err" => %err);
+                }
+            }
+        }
+        if let Some(format) = text("log_format") {
+            match format.parse() {
+                Ok(format) => logger::set_format(format),
+                Err(err) => {
+                    warn!(self.logger, "Ignoring malformed log format override"; "err" => %err);
+                }
+            }
+        }
+    }
+
     fn initialize_guest(
         self: &Arc<Protocol>,
         host_info: RuntimeInfoRequest,
@@ -426,10 +507,14 @@ impl Protocol {
             return Err(ProtocolError::AlreadyInitialized.into());
         }
 
+        // Apply any logging overrides from the host.
+        self.configure_logging(&host_info.local_config);
+
         // Create and start the consensus verifier.
-        let consensus_verifier: Box<dyn Verifier> =
-            if let Some(ref trust_root) = self.config.trust_root {
-                // Make sure that the host environment matches the trust root.
+        let trust_roots = self.config.trust_roots.clone();
+        let consensus_verifier: Box<dyn Verifier> = if !trust_roots.is_empty() {
+            // Make sure that the host environment matches the trust roots.
+            for trust_root in &trust_roots {
                 if host_info.runtime_id != trust_root.runtime_id {
                     return Err(ProtocolError::InvalidRuntimeId(
                         trust_root.runtime_id,
@@ -437,26 +522,27 @@ impl Protocol {
                     )
                     .into());
                 }
+            }
 
-                // Create the Tendermint consensus layer verifier and spawn it in a separate thread.
-                let verifier = tendermint::verifier::Verifier::new(
-                    self.clone(),
-                    self.tokio_runtime.clone(),
-                    trust_root.clone(),
-                    host_info.runtime_id,
-                    host_info.consensus_chain_context.clone(),
-                );
-                let handle = verifier.handle();
-                verifier.start();
-
-                Box::new(handle)
-            } else {
-                // Create a no-op verifier.
-                let verifier = tendermint::verifier::NopVerifier::new(self.clone());
-                verifier.start();
-
-                Box::new(verifier)
-            };
+            // Create the Tendermint consensus layer verifier and spawn it in a separate thread.
+            let verifier = tendermint::verifier::Verifier::new(
+                self.clone(),
+                self.tokio_runtime.clone(),
+                trust_roots,
+                host_info.runtime_id,
+                host_info.consensus_chain_context.clone(),
+            )?;
+            let handle = verifier.handle();
+            verifier.start();
+
+            Box::new(handle)
+        } else {
+            // Create a no-op verifier.
+            let verifier = tendermint::verifier::NopVerifier::new(self.clone());
+            verifier.start();
+
+            Box::new(verifier)
+        };
 
         // Configure the host environment info.
         *local_host_info = Some(HostInfo {
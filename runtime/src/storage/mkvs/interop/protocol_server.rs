@@ -38,12 +38,16 @@ struct ProtocolServerReadSyncer {
 pub enum Fixture {
     None,
     ConsensusMock,
+    ConsensusGovernanceMock,
+    ConsensusSchedulerMock,
 }
 
 impl fmt::Display for Fixture {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Fixture::ConsensusMock => write!(f, "consensus_mock"),
+            Fixture::ConsensusGovernanceMock => write!(f, "consensus_governance_mock"),
+            Fixture::ConsensusSchedulerMock => write!(f, "consensus_scheduler_mock"),
             _ => write!(f, ""),
         }
     }
@@ -1,8 +1,15 @@
-use std::{any::Any, sync::Arc};
+use std::{any::Any, sync::Arc, time::Instant};
 
 use anyhow::Result;
+use lazy_static::lazy_static;
+use slog::{debug, Logger};
 
 use crate::{
+    common::{
+        logger::get_logger,
+        metrics::{self, Counter, Histogram, DEFAULT_DURATION_BUCKETS},
+        trace,
+    },
     protocol::{Protocol, ProtocolError},
     storage::mkvs::sync::{
         GetPrefixesRequest, GetRequest, IterateRequest, ProofResponse, ReadSync,
@@ -13,8 +20,25 @@ use crate::{
     },
 };
 
+lazy_static! {
+    static ref SYNC_REQUESTS: Counter = metrics::counter(
+        "runtime_storage_sync_requests",
+        "Number of storage sync requests sent to the host.",
+    );
+    static ref SYNC_FAILURES: Counter = metrics::counter(
+        "runtime_storage_sync_failures",
+        "Number of failed storage sync requests sent to the host.",
+    );
+    static ref SYNC_DURATION: Histogram = metrics::histogram(
+        "runtime_storage_sync_duration_ms",
+        "Duration of storage sync requests sent to the host (in milliseconds).",
+        DEFAULT_DURATION_BUCKETS,
+    );
+}
+
 /// A proxy read syncer which forwards calls to the runtime host.
 pub struct HostReadSyncer {
+    logger: Logger,
     protocol: Arc<Protocol>,
     endpoint: HostStorageEndpoint,
 }
@@ -22,7 +46,11 @@ pub struct HostReadSyncer {
 impl HostReadSyncer {
     /// Construct a new host proxy instance.
     pub fn new(protocol: Arc<Protocol>, endpoint: HostStorageEndpoint) -> HostReadSyncer {
-        HostReadSyncer { protocol, endpoint }
+        HostReadSyncer {
+            logger: get_logger("runtime/storage/sync"),
+            protocol,
+            endpoint,
+        }
     }
 
     fn call_host_with_proof(&self, request: StorageSyncRequest) -> Result<ProofResponse> {
@@ -30,13 +58,24 @@ impl HostReadSyncer {
             endpoint: self.endpoint,
             request,
         });
-        match self.protocol.call_host(request) {
+        SYNC_REQUESTS.inc();
+        let start = Instant::now();
+        let result = match self.protocol.call_host(request) {
             Ok(Body::HostStorageSyncResponse(StorageSyncResponse::ProofResponse(response))) => {
                 Ok(response)
             }
             Ok(_) => Err(ProtocolError::InvalidResponse.into()),
             Err(error) => Err(error.into()),
+        };
+        SYNC_DURATION.observe(start.elapsed().as_millis() as u64);
+        if let Err(ref err) = result {
+            SYNC_FAILURES.inc();
+            debug!(trace::logger(&self.logger), "Storage sync request failed";
+                "endpoint" => ?self.endpoint,
+                "err" => %err,
+            );
         }
+        result
     }
 }
 
@@ -1,10 +1,16 @@
 //! Runtime transaction batch dispatcher.
 use std::sync::{atomic::AtomicBool, Arc};
 
-use super::{context::Context, tags::Tags, types::TxnBatch};
+use super::{
+    context::Context,
+    tags::Tags,
+    types::TxnBatch,
+    weight::{Limits, Weights},
+};
 use crate::{
     common::crypto::hash::Hash,
-    consensus::roothash,
+    consensus::{registry::TxnSchedulerParameters, roothash},
+    dispatcher::DispatcherError,
     types::{CheckTxResult, Error as RuntimeError},
 };
 
@@ -33,7 +39,8 @@ pub trait Dispatcher: Send + Sync {
     /// Schedule and execute transactions in the given batch.
     ///
     /// The passed batch is an initial batch. In case the runtime needs additional items it should
-    /// request them from the host.
+    /// request them from the host. The final batch must not exceed the batch limits (see
+    /// [`Dispatcher::batch_limits`]).
     ///
     /// # Consensus Layer State Integrity
     ///
@@ -44,11 +51,7 @@ pub trait Dispatcher: Send + Sync {
         _initial_batch: &mut TxnBatch,
         _in_msgs: &[roothash::IncomingMessage],
     ) -> Result<ExecuteBatchResult, RuntimeError> {
-        Err(RuntimeError::new(
-            "rhp/dispatcher",
-            3,
-            "scheduling not supported",
-        ))
+        Err(DispatcherError::SchedulingNotSupported.into())
     }
 
     /// Check the transactions in the given batch for validity.
@@ -64,6 +67,26 @@ pub trait Dispatcher: Send + Sync {
         batch: &TxnBatch,
     ) -> Result<Vec<CheckTxResult>, RuntimeError>;
 
+    /// Declared weights of the given transaction.
+    ///
+    /// The runtime dispatcher always accounts for the built-in weights (see
+    /// [`builtin_weights`](super::weight::builtin_weights)) in addition to the weights returned
+    /// here. The result must be deterministic as it is used to enforce batch limits during
+    /// execution.
+    fn tx_weights(&self, _tx: &[u8]) -> Weights {
+        // Default implementation declares no additional weights.
+        Weights::new()
+    }
+
+    /// Limits on the sum of transaction weights in a single batch.
+    ///
+    /// The runtime dispatcher defers transactions which do not fit into the remaining capacity
+    /// of a scheduled batch and rejects transactions which exceed the limits on their own. Batches
+    /// which exceed the limits are refused during execution.
+    fn batch_limits(&self, params: &TxnSchedulerParameters) -> Limits {
+        Limits::from(params)
+    }
+
     /// Invoke the finalizer (if any).
     fn finalize(&self, _new_storage_root: Hash) {
         // Default implementation does nothing.
@@ -83,11 +106,7 @@ pub trait Dispatcher: Send + Sync {
     /// query-by-query basis.
     fn query(&self, _ctx: Context, _method: &str, _args: Vec<u8>) -> Result<Vec<u8>, RuntimeError> {
         // Default implementation returns an error.
-        Err(RuntimeError::new(
-            "rhp/dispatcher",
-            2,
-            "query not supported",
-        ))
+        Err(DispatcherError::QueryNotSupported.into())
     }
 }
 
@@ -118,6 +137,14 @@ impl<T: Dispatcher + ?Sized> Dispatcher for Box<T> {
         T::check_batch(&**self, ctx, batch)
     }
 
+    fn tx_weights(&self, tx: &[u8]) -> Weights {
+        T::tx_weights(&**self, tx)
+    }
+
+    fn batch_limits(&self, params: &TxnSchedulerParameters) -> Limits {
+        T::batch_limits(&**self, params)
+    }
+
     fn finalize(&self, new_storage_root: Hash) {
         T::finalize(&**self, new_storage_root)
     }
@@ -158,6 +185,14 @@ impl<T: Dispatcher + ?Sized> Dispatcher for Arc<T> {
         T::check_batch(&**self, ctx, batch)
     }
 
+    fn tx_weights(&self, tx: &[u8]) -> Weights {
+        T::tx_weights(&**self, tx)
+    }
+
+    fn batch_limits(&self, params: &TxnSchedulerParameters) -> Limits {
+        T::batch_limits(&**self, params)
+    }
+
     fn finalize(&self, new_storage_root: Hash) {
         T::finalize(&**self, new_storage_root)
     }
@@ -6,6 +6,7 @@ pub mod rwset;
 pub mod tags;
 pub mod tree;
 pub mod types;
+pub mod weight;
 
 // Re-exports.
 pub use self::context::Context;
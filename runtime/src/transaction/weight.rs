@@ -0,0 +1,174 @@
+//! Transaction weight accounting.
+//!
+//! Each transaction has a set of weights (e.g., its size in bytes or the amount of gas it wants to
+//! use) and each batch has limits on the sum of those weights. The runtime dispatcher uses these to
+//! deterministically bound the size of the batches that it schedules and executes.
+// NOTE: Weight names should be kept in sync with go/runtime/host/protocol/types.go.
+use std::collections::BTreeMap;
+
+use crate::consensus::registry::TxnSchedulerParameters;
+
+/// Weight that counts the number of transactions.
+pub const WEIGHT_COUNT: &str = "count";
+/// Weight that counts the size of transactions in bytes.
+pub const WEIGHT_SIZE_BYTES: &str = "size_bytes";
+
+/// Transaction weights, keyed by weight name.
+pub type Weights = BTreeMap<String, u64>;
+
+/// Built-in weights which are accounted for every transaction.
+pub fn builtin_weights(tx: &[u8]) -> Weights {
+    let mut weights = Weights::new();
+    weights.insert(WEIGHT_COUNT.to_string(), 1);
+    weights.insert(WEIGHT_SIZE_BYTES.to_string(), tx.len() as u64);
+    weights
+}
+
+/// Batch limits, keyed by weight name. Weights without a limit are unbounded.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Limits(BTreeMap<String, u64>);
+
+impl Limits {
+    /// Create new empty (unbounded) limits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the limit for the given weight.
+    pub fn with(mut self, weight: &str, limit: u64) -> Self {
+        self.0.insert(weight.to_string(), limit);
+        self
+    }
+
+    /// Limit for the given weight, if any.
+    pub fn get(&self, weight: &str) -> Option<u64> {
+        self.0.get(weight).copied()
+    }
+
+    /// Iterate over all configured limits.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, u64)> {
+        self.0
+            .iter()
+            .map(|(weight, limit)| (weight.as_str(), *limit))
+    }
+}
+
+impl From<&TxnSchedulerParameters> for Limits {
+    fn from(params: &TxnSchedulerParameters) -> Self {
+        let mut limits = Self::new();
+        if params.max_batch_size > 0 {
+            limits = limits.with(WEIGHT_COUNT, params.max_batch_size);
+        }
+        if params.max_batch_size_bytes > 0 {
+            limits = limits.with(WEIGHT_SIZE_BYTES, params.max_batch_size_bytes);
+        }
+        limits
+    }
+}
+
+/// Outcome of adding a transaction to a batch.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Fit {
+    /// Transaction fits into the batch and its weights have been accounted for.
+    Accepted,
+    /// Transaction does not fit into the remaining capacity of the batch, but could fit into a
+    /// subsequent batch.
+    Deferred,
+    /// Transaction exceeds the limits on its own and can never be included in a batch.
+    Rejected,
+}
+
+/// Accumulator of the weights used by a batch.
+pub struct BatchWeights<'a> {
+    limits: &'a Limits,
+    used: BTreeMap<&'a str, u64>,
+}
+
+impl<'a> BatchWeights<'a> {
+    /// Create a new accumulator for an empty batch.
+    pub fn new(limits: &'a Limits) -> Self {
+        Self {
+            limits,
+            used: BTreeMap::new(),
+        }
+    }
+
+    /// Sum of the given weight over all accepted transactions.
+    pub fn used(&self, weight: &str) -> u64 {
+        self.used.get(weight).copied().unwrap_or_default()
+    }
+
+    /// Try to add a transaction with the given weights to the batch.
+    pub fn add(&mut self, weights: &Weights) -> Fit {
+        let mut fit = Fit::Accepted;
+        for (name, limit) in self.limits.iter() {
+            let weight = weights.get(name).copied().unwrap_or_default();
+            if weight > limit {
+                return Fit::Rejected;
+            }
+            if self.used(name).saturating_add(weight) > limit {
+                fit = Fit::Deferred;
+            }
+        }
+        if fit != Fit::Accepted {
+            return fit;
+        }
+
+        for (name, _) in self.limits.0.iter() {
+            let weight = weights.get(name).copied().unwrap_or_default();
+            *self.used.entry(name.as_str()).or_default() += weight;
+        }
+        Fit::Accepted
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_batch_weights() {
+        let limits = Limits::from(&TxnSchedulerParameters {
+            max_batch_size: 3,
+            max_batch_size_bytes: 10,
+            ..Default::default()
+        })
+        .with("gas", 100);
+        let mut batch = BatchWeights::new(&limits);
+
+        let weights = |tx: &[u8], gas: u64| {
+            let mut weights = builtin_weights(tx);
+            weights.insert("gas".to_string(), gas);
+            weights
+        };
+
+        assert_eq!(batch.add(&weights(b"aaaa", 50)), Fit::Accepted);
+        // Too much gas for the remaining capacity.
+        assert_eq!(batch.add(&weights(b"bb", 60)), Fit::Deferred);
+        // Too large to ever fit.
+        assert_eq!(batch.add(&weights(b"ccccccccccc", 1)), Fit::Rejected);
+        assert_eq!(batch.add(&weights(b"dd", 1000)), Fit::Rejected);
+        assert_eq!(batch.add(&weights(b"eeee", 50)), Fit::Accepted);
+        // Size limit reached.
+        assert_eq!(batch.add(&weights(b"fff", 0)), Fit::Deferred);
+        assert_eq!(batch.add(&weights(b"gg", 0)), Fit::Accepted);
+        // Count limit reached.
+        assert_eq!(batch.add(&weights(b"", 0)), Fit::Deferred);
+
+        assert_eq!(batch.used(WEIGHT_COUNT), 3);
+        assert_eq!(batch.used(WEIGHT_SIZE_BYTES), 10);
+        assert_eq!(batch.used("gas"), 100);
+    }
+
+    #[test]
+    fn test_limits_from_params() {
+        let limits = Limits::from(&TxnSchedulerParameters::default());
+        assert_eq!(limits, Limits::new());
+        assert_eq!(limits.get(WEIGHT_COUNT), None);
+
+        let mut batch = BatchWeights::new(&limits);
+        for _ in 0..1000 {
+            assert_eq!(batch.add(&builtin_weights(b"foo")), Fit::Accepted);
+        }
+    }
+}
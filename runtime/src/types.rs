@@ -10,8 +10,11 @@ use crate::{
             signature::{self, Signature},
             x25519,
         },
+        errors,
+        metrics::Metric,
         namespace::Namespace,
         sgx::{ias::AVR, Quote, QuotePolicy},
+        trace::SpanContext,
         version::Version,
     },
     consensus::{
@@ -225,6 +228,15 @@ pub enum Body {
         runtime_event: Option<RuntimeNotifyEvent>,
     },
     RuntimeNotifyResponse {},
+    RuntimeMetricsRequest {},
+    RuntimeMetricsResponse {
+        #[cbor(optional)]
+        metrics: Vec<Metric>,
+    },
+    RuntimeHostVersionRequest {
+        version: Version,
+    },
+    RuntimeHostVersionResponse {},
 
     // Host interface.
     HostRPCCallRequest {
@@ -234,6 +246,8 @@ pub enum Body {
         nodes: Vec<signature::PublicKey>,
         #[cbor(optional, rename = "pf")]
         peer_feedback: Option<enclave_rpc::types::PeerFeedback>,
+        #[cbor(optional, rename = "pf_node")]
+        peer_feedback_node: Option<signature::PublicKey>,
     },
     HostRPCCallResponse {
         response: Vec<u8>,
@@ -258,6 +272,12 @@ pub enum Body {
     HostFetchConsensusBlockResponse {
         block: LightBlock,
     },
+    HostFetchWitnessBlockRequest {
+        height: u64,
+    },
+    HostFetchWitnessBlockResponse {
+        block: LightBlock,
+    },
     HostFetchConsensusEventsRequest(HostFetchConsensusEventsRequest),
     HostFetchConsensusEventsResponse(HostFetchConsensusEventsResponse),
     HostFetchTxBatchRequest {
@@ -341,13 +361,18 @@ impl Error {
             message: msg.to_owned(),
         }
     }
+
+    /// Whether the error has the given module name and code.
+    pub fn is(&self, module: &str, code: u32) -> bool {
+        self.module == module && self.code == code
+    }
 }
 
 impl From<anyhow::Error> for Error {
     fn from(err: anyhow::Error) -> Self {
         Self {
-            module: "unknown".to_string(),
-            code: 1,
+            module: errors::UNKNOWN_MODULE.to_string(),
+            code: errors::CODE_UNKNOWN_ERROR,
             message: err.to_string(),
         }
     }
@@ -463,13 +488,16 @@ pub struct CheckTxMetadata {
 }
 
 /// Consensus event kind.
-#[derive(Clone, Copy, Debug, cbor::Encode, cbor::Decode)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, cbor::Encode, cbor::Decode)]
 #[repr(u8)]
 pub enum EventKind {
     Staking = 1,
     Registry = 2,
     RootHash = 3,
     Governance = 4,
+    Beacon = 5,
+    KeyManager = 6,
+    KeyManagerChurp = 7,
 }
 
 /// Request to host to fetch the consensus events for the given height.
@@ -528,4 +556,7 @@ pub struct Message {
     pub message_type: MessageType,
     /// Message body.
     pub body: Body,
+    /// Optional span context of the request.
+    #[cbor(optional)]
+    pub span_context: Option<SpanContext>,
 }
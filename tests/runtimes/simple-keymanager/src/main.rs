@@ -29,7 +29,7 @@ pub fn main_with_version(version: Version) {
         init,
         Config {
             version,
-            trust_root,
+            trust_roots: trust_root.into_iter().collect(),
             ..Default::default()
         },
     );
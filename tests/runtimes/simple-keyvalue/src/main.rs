@@ -432,7 +432,7 @@ pub fn main_with_version(version: Version) {
         Box::new(init),
         Config {
             version,
-            trust_root,
+            trust_roots: trust_root.into_iter().collect(),
             features: Features {
                 // Enable the schedule control feature.
                 schedule_control: Some(FeatureScheduleControl {
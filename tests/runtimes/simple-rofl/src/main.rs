@@ -192,7 +192,7 @@ pub fn main() {
         rofl::new(Box::new(App::new())),
         Config {
             version: Version::new(0, 0, 0),
-            trust_root,
+            trust_roots: trust_root.into_iter().collect(),
             ..Default::default()
         },
     );